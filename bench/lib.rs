@@ -0,0 +1,16 @@
+//! Gas and weight benchmarks for the food order contract.
+//!
+//! `FoodOrder` is deployed to a local `substrate-contracts-node`, its state
+//! is grown to each requested size and every message is dry-run against it.
+//! The weights and storage deposits are written to a JSON report that can be
+//! diffed between commits.
+//!
+//! Building the contract with `--features chain-extension` measures the
+//! bookkeeping chain extension instead of in-contract order indexes; the node
+//! must then provide that extension.
+
+pub mod report;
+pub mod scenario;
+
+pub use report::{Measurement, Report};
+pub use scenario::Bench;
@@ -0,0 +1,46 @@
+use anyhow::{anyhow, Context, Result};
+use bench::Bench;
+use clap::Parser;
+use indexer::ContractMetadata;
+use std::{fs, path::PathBuf};
+
+#[derive(Parser)]
+#[command(about = "Benchmark gas and storage deposits of every FoodOrder message")]
+struct Cli {
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+    /// `food_order.contract` bundle produced by `cargo contract build`.
+    #[arg(long)]
+    contract: PathBuf,
+    /// Storage sizes to measure at, in foods, customers and orders.
+    #[arg(long, value_delimiter = ',', default_value = "1,10,100")]
+    sizes: Vec<u32>,
+    #[arg(long, default_value = "gas-report.json")]
+    output: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let bundle = fs::read_to_string(&cli.contract)
+        .with_context(|| format!("reading {}", cli.contract.display()))?;
+    let json: serde_json::Value = serde_json::from_str(&bundle)?;
+    let wasm = json["source"]["wasm"]
+        .as_str()
+        .ok_or_else(|| anyhow!("{} has no source.wasm", cli.contract.display()))?;
+    let code = hex::decode(wasm.trim_start_matches("0x"))?;
+    let metadata = ContractMetadata::from_json(&bundle)?;
+
+    let mut sizes = cli.sizes;
+    sizes.sort_unstable();
+    sizes.dedup();
+    let mut bench = Bench::deploy(&cli.url, code, metadata).await?;
+    bench.report.sizes = sizes.clone();
+    for size in sizes {
+        println!("measuring at size {size}");
+        bench.run(size).await?;
+    }
+    bench.report.write(&cli.output)?;
+    println!("wrote {}", cli.output.display());
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use gateway::contract::{StorageDeposit, Weight};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// One dry run of a message at a given data size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub message: String,
+    /// Number of foods, customers and orders in storage when measured.
+    pub size: u32,
+    pub ref_time: u64,
+    pub proof_size: u64,
+    pub ref_time_required: u64,
+    pub proof_size_required: u64,
+    /// Positive when the call charges a deposit, negative when it refunds.
+    pub storage_deposit: i128,
+}
+
+impl Measurement {
+    pub fn new(
+        message: &str,
+        size: u32,
+        consumed: Weight,
+        required: Weight,
+        deposit: &StorageDeposit,
+    ) -> Self {
+        Measurement {
+            message: message.to_string(),
+            size,
+            ref_time: consumed.ref_time,
+            proof_size: consumed.proof_size,
+            ref_time_required: required.ref_time,
+            proof_size_required: required.proof_size,
+            storage_deposit: match deposit {
+                StorageDeposit::Charge(amount) => *amount as i128,
+                StorageDeposit::Refund(amount) => -(*amount as i128),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub sizes: Vec<u32>,
+    /// Wasm code size in bytes.
+    pub code_size: usize,
+    pub instantiate: Option<Measurement>,
+    pub messages: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn record(&mut self, measurement: Measurement) {
+        self.messages.push(measurement);
+    }
+
+    /// Writes the report with messages sorted by label and size so that
+    /// reports of different commits diff line by line.
+    pub fn write(&mut self, path: &Path) -> Result<()> {
+        self.messages
+            .sort_by(|a, b| (&a.message, a.size).cmp(&(&b.message, b.size)));
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+}
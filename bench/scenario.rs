@@ -0,0 +1,1160 @@
+use crate::report::{Measurement, Report};
+use anyhow::{anyhow, Context, Result};
+use gateway::{contract::DryRun, ContractClient};
+use indexer::{ContractMetadata, DecodedEvent};
+use scale_value::Value;
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use subxt::utils::AccountId32;
+use subxt_signer::{
+    sr25519::{dev, Keypair},
+    SecretUri,
+};
+
+const FUNDING: u128 = 1_000_000_000_000_000;
+const PRICE: u128 = 1_000_000_000;
+const DELIVERY_FEE: u128 = 100_000_000;
+const DELIVER_BOND: u128 = 1_000_000_000;
+const ETA: u64 = 3_600_000;
+const DAY: u64 = 86_400_000;
+const RESTAURANT_KEY: [u8; 32] = [0x11; 32];
+const DELIVER_KEY: [u8; 32] = [0x22; 32];
+const DELIVER_ATTESTATION_KEY: [u8; 20] = [0x33; 20];
+const CURRENCY: [u8; 3] = *b"USD";
+const RATE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Drives `FoodOrder` through the order lifecycle and records the cost of
+/// every message.
+///
+/// The contract does not verify sealed boxes, so the encryption keys and
+/// ciphertexts are fixed placeholders of realistic size.
+pub struct Bench {
+    client: ContractClient,
+    manager: Keypair,
+    restaurant: Keypair,
+    deliver: Keypair,
+    customer: Keypair,
+    processor: Keypair,
+    restaurant_id: u64,
+    deliver_id: u64,
+    customer_id: u64,
+    processor_id: u64,
+    foods: Vec<u64>,
+    customers: u32,
+    orders: u32,
+    accounts: u32,
+    pub report: Report,
+}
+
+impl Bench {
+    /// Deploys the contract signed by Alice, who becomes the manager, and
+    /// registers Bob as restaurant, Dave as courier, Eve as payment processor
+    /// and Charlie as customer.
+    pub async fn deploy(url: &str, code: Vec<u8>, metadata: ContractMetadata) -> Result<Self> {
+        let manager = dev::alice();
+        let code_size = code.len();
+        let (client, instantiated) =
+            ContractClient::instantiate(url, &manager, code, metadata, "new", vec![], 0).await?;
+        let mut bench = Bench {
+            client,
+            manager,
+            restaurant: dev::bob(),
+            deliver: dev::dave(),
+            customer: dev::charlie(),
+            processor: dev::eve(),
+            restaurant_id: 0,
+            deliver_id: 0,
+            customer_id: 0,
+            processor_id: 0,
+            foods: Vec::new(),
+            customers: 0,
+            orders: 0,
+            accounts: 0,
+            report: Report {
+                code_size,
+                instantiate: Some(Measurement::new(
+                    "new",
+                    0,
+                    instantiated.gas_consumed,
+                    instantiated.gas_required,
+                    &instantiated.storage_deposit,
+                )),
+                ..Report::default()
+            },
+        };
+        let manager = bench.manager.clone();
+        bench
+            .exec(
+                0,
+                &manager,
+                "OrderingManagerService::set_delivery_fee",
+                vec![Value::u128(DELIVERY_FEE)],
+                0,
+            )
+            .await?;
+        bench
+            .exec(
+                0,
+                &manager,
+                "OrderingManagerService::set_commission",
+                vec![Value::u128(1_000), Value::u128(500)],
+                0,
+            )
+            .await?;
+        bench
+            .exec(
+                0,
+                &manager,
+                "LoyaltyService::set_loyalty_rate",
+                vec![Value::u128(100)],
+                0,
+            )
+            .await?;
+        let restaurant = account(&bench.restaurant);
+        let events = bench
+            .exec(
+                0,
+                &manager,
+                "OrderingManagerService::add_restaurant",
+                vec![
+                    account_value(&restaurant),
+                    Value::string("Bench Restaurant"),
+                    Value::string("1 Main Street"),
+                    Value::string("555-0100"),
+                ],
+                0,
+            )
+            .await?;
+        bench.restaurant_id = event_field(&events, "AddRestaurantEvent", "restaurant_id")?;
+        let deliver = account(&bench.deliver);
+        let events = bench
+            .exec(
+                0,
+                &manager,
+                "DeliveryManagerService::add_deliver",
+                vec![
+                    account_value(&deliver),
+                    Value::string("Bench Courier"),
+                    Value::string("3 Main Street"),
+                    Value::string("555-0102"),
+                ],
+                0,
+            )
+            .await?;
+        bench.deliver_id = event_field(&events, "AddDeliverEvent", "deliver_id")?;
+        let processor = account(&bench.processor);
+        let events = bench
+            .exec(
+                0,
+                &manager,
+                "PaymentManagerService::add_payment_processor",
+                vec![account_value(&processor), Value::string("Bench Payments")],
+                0,
+            )
+            .await?;
+        bench.processor_id = event_field(&events, "AddPaymentProcessorEvent", "processor_id")?;
+        let processor_id = Value::u128(bench.processor_id as u128);
+        bench
+            .exec(
+                0,
+                &manager,
+                "PaymentManagerService::set_processor_fee_rate",
+                vec![processor_id, Value::u128(100)],
+                0,
+            )
+            .await?;
+        let customer = bench.customer.clone();
+        bench.add_customer(0, &customer, true).await?;
+        bench.customer_id = bench.customers as u64;
+        Ok(bench)
+    }
+
+    /// Grows storage to `size` foods, customers and orders, then measures
+    /// every message against it.
+    pub async fn run(&mut self, size: u32) -> Result<()> {
+        self.grow(size).await?;
+        self.measure_restaurant_and_lifecycle(size).await?;
+        self.measure_manager(size).await
+    }
+
+    async fn grow(&mut self, size: u32) -> Result<()> {
+        let restaurant = self.restaurant.clone();
+        while (self.foods.len() as u32) < size {
+            let events = self
+                .call(
+                    &restaurant,
+                    "RestaurantService::add_food",
+                    food_args(self.foods.len()),
+                    0,
+                )
+                .await?;
+            self.foods
+                .push(event_field(&events, "AddFoodEvent", "food_id")?);
+        }
+        while self.customers < size {
+            let customer = self.new_account("customer").await?;
+            self.add_customer(size, &customer, false).await?;
+        }
+        let customer = self.customer.clone();
+        while self.orders < size {
+            let food_id = self.foods[self.orders as usize % self.foods.len()];
+            self.submit_order(
+                size,
+                &customer,
+                food_id,
+                plain("2 Main Street"),
+                None,
+                false,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn measure_restaurant_and_lifecycle(&mut self, size: u32) -> Result<()> {
+        let restaurant = self.restaurant.clone();
+        let deliver = self.deliver.clone();
+        let customer = self.customer.clone();
+        let processor = self.processor.clone();
+        let manager = self.manager.clone();
+        let restaurant_id = Value::u128(self.restaurant_id as u128);
+
+        let events = self
+            .exec(
+                size,
+                &restaurant,
+                "RestaurantService::add_food",
+                food_args(size as usize),
+                0,
+            )
+            .await?;
+        let food_id = event_field(&events, "AddFoodEvent", "food_id")?;
+        let food = Value::u128(food_id as u128);
+        let mut args = food_args(size as usize);
+        args.insert(0, food.clone());
+        self.exec(size, &restaurant, "RestaurantService::update_food", args, 0)
+            .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::set_food_availability",
+            vec![food.clone(), Value::bool(true)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::set_food_stock",
+            vec![food.clone(), some(Value::u128(1_000))],
+            0,
+        )
+        .await?;
+        let always_open = (0..7)
+            .map(|weekday| {
+                Value::named_composite([
+                    ("weekday", Value::u128(weekday)),
+                    ("open", Value::u128(0)),
+                    ("close", Value::u128(1_440)),
+                ])
+            })
+            .collect::<Vec<_>>();
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::set_opening_hours",
+            vec![Value::unnamed_composite(always_open)],
+            0,
+        )
+        .await?;
+        // Dry-run only: the lifecycle below pays the native price.
+        self.measure(
+            size,
+            &restaurant,
+            "RestaurantService::set_food_currency",
+            vec![food.clone(), some(Value::from_bytes(CURRENCY))],
+            0,
+        )
+        .await?;
+        let closure_start = now() + 365 * DAY;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::add_closure",
+            vec![
+                Value::u128(closure_start as u128),
+                Value::u128((closure_start + DAY) as u128),
+            ],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::remove_closure",
+            vec![Value::u128(closure_start as u128)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::set_restaurant_encryption_key",
+            vec![some(Value::from_bytes(RESTAURANT_KEY))],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &deliver,
+            "DeliverService::set_deliver_encryption_key",
+            vec![some(Value::from_bytes(DELIVER_KEY))],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &deliver,
+            "DeliverService::set_deliver_attestation_key",
+            vec![some(Value::from_bytes(DELIVER_ATTESTATION_KEY))],
+            0,
+        )
+        .await?;
+
+        // Happy path with a sealed delivery address.
+        let sealed = Value::unnamed_variant(
+            "Sealed",
+            [Value::unnamed_composite([sealed_box(RESTAURANT_KEY)])],
+        );
+        let order_id = self
+            .submit_order(size, &customer, food_id, sealed, None, true)
+            .await?;
+        let order = Value::u128(order_id as u128);
+        self.exec(
+            size,
+            &customer,
+            "PaymentService::select_payment_processor",
+            vec![order.clone(), Value::u128(self.processor_id as u128)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &processor,
+            "PaymentService::authorize_charge",
+            vec![
+                order.clone(),
+                Value::string("ch_bench"),
+                Value::u128(PRICE / 100),
+            ],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::confirm_order",
+            vec![order.clone()],
+            0,
+        )
+        .await?;
+        let events = self
+            .exec(
+                size,
+                &restaurant,
+                "RestaurantService::deliver_order",
+                vec![order.clone()],
+                0,
+            )
+            .await?;
+        let delivery_id = event_field(&events, "DeliverOrderEvent", "delivery_id")?;
+        self.exec(
+            size,
+            &deliver,
+            "DeliverService::confirm_delivery",
+            vec![Value::u128(delivery_id as u128)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::share_delivery_address",
+            vec![order.clone(), sealed_box(DELIVER_KEY)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &customer,
+            "CustomerService::confrim_delivery",
+            vec![order.clone()],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &processor,
+            "PaymentService::capture_charge",
+            vec![order.clone()],
+            0,
+        )
+        .await?;
+        let balance = self
+            .amount(
+                "GetPayment::get_balance",
+                vec![account_value(&account(&restaurant))],
+            )
+            .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "BalanceService::withdraw",
+            vec![Value::u128(balance)],
+            0,
+        )
+        .await?;
+
+        // Order completed by the courier revealing the customer's handoff code.
+        let (handoff_code, handoff_hash) = privacy::handoff_code();
+        let handed_off = self
+            .submit_order(
+                size,
+                &customer,
+                food_id,
+                plain("2 Main Street"),
+                Some(handoff_hash),
+                false,
+            )
+            .await?;
+        let handed_off = Value::u128(handed_off as u128);
+        self.call(
+            &restaurant,
+            "RestaurantService::confirm_order",
+            vec![handed_off.clone()],
+            0,
+        )
+        .await?;
+        let events = self
+            .call(
+                &restaurant,
+                "RestaurantService::deliver_order",
+                vec![handed_off],
+                0,
+            )
+            .await?;
+        let delivery =
+            Value::u128(event_field(&events, "DeliverOrderEvent", "delivery_id")? as u128);
+        self.call(
+            &deliver,
+            "DeliverService::confirm_delivery",
+            vec![delivery.clone()],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &deliver,
+            "DeliverService::complete_handoff",
+            vec![delivery, Value::string(handoff_code)],
+            0,
+        )
+        .await?;
+
+        // Rejected and cancelled orders.
+        let rejected = self
+            .submit_order(
+                size,
+                &customer,
+                food_id,
+                plain("2 Main Street"),
+                None,
+                false,
+            )
+            .await?;
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::reject_order",
+            vec![Value::u128(rejected as u128)],
+            0,
+        )
+        .await?;
+        let cancelled = self
+            .submit_order(
+                size,
+                &customer,
+                food_id,
+                plain("2 Main Street"),
+                None,
+                false,
+            )
+            .await?;
+        self.exec(
+            size,
+            &customer,
+            "CustomerService::cancel_order",
+            vec![Value::u128(cancelled as u128)],
+            0,
+        )
+        .await?;
+
+        // Group order funded and submitted by its organizer, then a second
+        // one cancelled before submission.
+        let events = self
+            .exec(
+                size,
+                &customer,
+                "GroupOrderService::open_group_order",
+                vec![restaurant_id.clone(), plain("2 Main Street")],
+                0,
+            )
+            .await?;
+        let group = Value::u128(event_field(&events, "OpenGroupOrderEvent", "group_id")? as u128);
+        self.exec(
+            size,
+            &customer,
+            "GroupOrderService::add_group_item",
+            vec![group.clone(), Value::u128(food_id as u128)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &customer,
+            "GroupOrderService::fund_group_share",
+            vec![group.clone()],
+            PRICE + DELIVERY_FEE,
+        )
+        .await?;
+        let events = self
+            .exec(
+                size,
+                &customer,
+                "GroupOrderService::submit_group_order",
+                vec![group.clone()],
+                0,
+            )
+            .await?;
+        self.orders += 1;
+        let group_order = event_field(&events, "SubmitGroupOrderEvent", "order_id")?;
+        self.call(
+            &customer,
+            "CustomerService::cancel_order",
+            vec![Value::u128(group_order as u128)],
+            0,
+        )
+        .await?;
+        let events = self
+            .call(
+                &customer,
+                "GroupOrderService::open_group_order",
+                vec![restaurant_id.clone(), plain("2 Main Street")],
+                0,
+            )
+            .await?;
+        let cancelled_group = event_field(&events, "OpenGroupOrderEvent", "group_id")?;
+        self.exec(
+            size,
+            &customer,
+            "GroupOrderService::cancel_group_order",
+            vec![Value::u128(cancelled_group as u128)],
+            0,
+        )
+        .await?;
+
+        // Weekday lunch schedule. Triggering needs a due slot, so only its
+        // creation and cancellation are measured.
+        let events = self
+            .exec(
+                size,
+                &customer,
+                "ScheduleService::create_schedule",
+                vec![
+                    Value::u128(food_id as u128),
+                    restaurant_id.clone(),
+                    plain("2 Main Street"),
+                    Value::unnamed_composite((0..5).map(Value::u128)),
+                    Value::u128(720),
+                    Value::u128(5),
+                ],
+                5 * (PRICE + DELIVERY_FEE),
+            )
+            .await?;
+        let schedule_id = event_field(&events, "CreateScheduleEvent", "schedule_id")?;
+        self.exec(
+            size,
+            &customer,
+            "ScheduleService::cancel_schedule",
+            vec![Value::u128(schedule_id as u128)],
+            0,
+        )
+        .await?;
+
+        // Disputed order, released to the restaurant and courier.
+        let disputed = self
+            .submit_order(
+                size,
+                &customer,
+                food_id,
+                plain("2 Main Street"),
+                None,
+                false,
+            )
+            .await?;
+        let disputed_order = Value::u128(disputed as u128);
+        self.call(
+            &restaurant,
+            "RestaurantService::confirm_order",
+            vec![disputed_order.clone()],
+            0,
+        )
+        .await?;
+        let events = self
+            .call(
+                &restaurant,
+                "RestaurantService::deliver_order",
+                vec![disputed_order.clone()],
+                0,
+            )
+            .await?;
+        let disputed_delivery = event_field(&events, "DeliverOrderEvent", "delivery_id")?;
+        self.call(
+            &deliver,
+            "DeliverService::confirm_delivery",
+            vec![Value::u128(disputed_delivery as u128)],
+            0,
+        )
+        .await?;
+        let events = self
+            .exec(
+                size,
+                &customer,
+                "DisputeService::open_dispute",
+                vec![disputed_order.clone(), hash_value(0x01)],
+                0,
+            )
+            .await?;
+        let dispute_id = event_field(&events, "OpenDisputeEvent", "dispute_id")?;
+        let dispute = Value::u128(dispute_id as u128);
+        self.exec(
+            size,
+            &restaurant,
+            "DisputeService::respond_dispute",
+            vec![dispute.clone(), hash_value(0x02)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "DisputeService::resolve_dispute",
+            vec![
+                dispute.clone(),
+                Value::unnamed_variant("Release", []),
+                Value::bool(false),
+            ],
+            0,
+        )
+        .await?;
+        self.measure(
+            size,
+            &manager,
+            "DisputeService::set_arbiter",
+            vec![account_value(&account(&manager))],
+            0,
+        )
+        .await?;
+
+        let everything = Value::u128(u64::MAX as u128);
+        let queries = vec![
+            ("Get::get_restaurant_from_id", vec![restaurant_id.clone()]),
+            (
+                "Get::get_deliver_from_id",
+                vec![Value::u128(self.deliver_id as u128)],
+            ),
+            ("Get::get_eta", vec![order.clone()]),
+            ("Get::get_order_from_id", vec![order.clone()]),
+            ("Get::get_order_status_history", vec![order.clone()]),
+            (
+                "Get::get_order_from_restaurant",
+                vec![restaurant_id.clone()],
+            ),
+            (
+                "Get::get_order_from_customer",
+                vec![Value::u128(self.customer_id as u128)],
+            ),
+            (
+                "Get::get_order_all",
+                vec![Value::u128(1), everything.clone()],
+            ),
+            ("Get::get_food_from_id", vec![food.clone()]),
+            ("Get::get_food_from_restaurant", vec![restaurant_id.clone()]),
+            (
+                "Get::get_food_all",
+                vec![Value::u128(1), everything.clone()],
+            ),
+            (
+                "GetDelivery::get_delivery_from_id",
+                vec![Value::u128(delivery_id as u128)],
+            ),
+            ("GetDelivery::get_delivery_from_order", vec![order.clone()]),
+            (
+                "GetDelivery::get_delivery_from_deliver",
+                vec![Value::u128(self.deliver_id as u128)],
+            ),
+            (
+                "GetDelivery::get_delivery_all",
+                vec![Value::u128(1), everything],
+            ),
+            ("Get::get_dispute_from_id", vec![dispute]),
+            ("Get::get_dispute_from_order", vec![disputed_order]),
+            ("GetPayment::get_charge_from_order", vec![order.clone()]),
+            (
+                "GetPayment::get_payment_processor_from_id",
+                vec![Value::u128(1)],
+            ),
+            ("Get::get_food_price", vec![food.clone()]),
+            ("Get::get_group_order_from_id", vec![group]),
+            (
+                "Get::get_schedule_from_id",
+                vec![Value::u128(schedule_id as u128)],
+            ),
+            (
+                "Get::get_schedule_from_customer",
+                vec![Value::u128(self.customer_id as u128)],
+            ),
+            ("Get::get_delivery_fee", vec![]),
+            ("Get::get_food_commission", vec![restaurant_id.clone()]),
+            ("Get::get_delivery_commission", vec![]),
+            ("GetPayment::get_treasury_balance", vec![]),
+            (
+                "GetPayment::get_balance",
+                vec![account_value(&account(&restaurant))],
+            ),
+            (
+                "Get::is_restaurant_open",
+                vec![restaurant_id, Value::u128(now() as u128)],
+            ),
+        ];
+        for (message, args) in queries {
+            self.measure(size, &manager, message, args, 0).await?;
+        }
+
+        self.exec(
+            size,
+            &restaurant,
+            "RestaurantService::remove_food",
+            vec![food],
+            0,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn measure_manager(&mut self, size: u32) -> Result<()> {
+        let manager = self.manager.clone();
+        let restaurant_id = Value::u128(self.restaurant_id as u128);
+        self.exec(
+            size,
+            &manager,
+            "OrderingManagerService::set_delivery_fee",
+            vec![Value::u128(DELIVERY_FEE)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "OrderingManagerService::set_commission",
+            vec![Value::u128(1_000), Value::u128(500)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "OrderingManagerService::set_restaurant_commission",
+            vec![restaurant_id.clone(), some(Value::u128(800))],
+            0,
+        )
+        .await?;
+        self.call(
+            &manager,
+            "OrderingManagerService::set_restaurant_commission",
+            vec![restaurant_id.clone(), none()],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "LoyaltyService::set_loyalty_rate",
+            vec![Value::u128(100)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "LoyaltyService::set_restaurant_loyalty_multiplier",
+            vec![restaurant_id.clone(), some(Value::u128(20_000))],
+            0,
+        )
+        .await?;
+        self.call(
+            &manager,
+            "LoyaltyService::set_restaurant_loyalty_multiplier",
+            vec![restaurant_id, none()],
+            0,
+        )
+        .await?;
+        let deliver = self.deliver.clone();
+        self.exec(
+            size,
+            &deliver,
+            "BondService::post_bond",
+            vec![],
+            DELIVER_BOND,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "BondService::set_bond_policy",
+            vec![
+                Value::u128(DELIVER_BOND),
+                Value::u128(DAY as u128),
+                Value::u128(2_000),
+                Value::u128(DAY as u128),
+            ],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "OracleService::set_oracle",
+            vec![account_value(&account(&manager))],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "OracleService::set_max_rate_age",
+            vec![Value::u128(DAY as u128)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "OracleService::post_exchange_rate",
+            vec![
+                Value::from_bytes(CURRENCY),
+                Value::u128(RATE_PRECISION),
+                Value::u128((now() - 60_000) as u128),
+            ],
+            0,
+        )
+        .await?;
+        // Every round registers a code of its own, only its hash is stored.
+        let mut code_hash = [0x70; 32];
+        code_hash[..4].copy_from_slice(&size.to_le_bytes());
+        let code_hash = Value::unnamed_composite([Value::from_bytes(code_hash)]);
+        self.exec(
+            size,
+            &manager,
+            "PromoService::add_promo_code",
+            vec![
+                code_hash.clone(),
+                none(),
+                Value::unnamed_variant("Percent", [Value::u128(1_000)]),
+                some(Value::u128(100)),
+                some(Value::u128(1)),
+                Value::u128(now() as u128),
+                Value::u128((now() + DAY) as u128),
+            ],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "PromoService::revoke_promo_code",
+            vec![code_hash],
+            0,
+        )
+        .await?;
+        let treasury = self
+            .amount("GetPayment::get_treasury_balance", vec![])
+            .await?;
+        self.exec(
+            size,
+            &manager,
+            "PaymentManagerService::withdraw_treasury",
+            vec![Value::u128(treasury)],
+            0,
+        )
+        .await?;
+        let restaurant = account(&self.new_account("restaurant").await?);
+        self.exec(
+            size,
+            &manager,
+            "OrderingManagerService::add_restaurant",
+            vec![
+                account_value(&restaurant),
+                Value::string("Bench Restaurant"),
+                Value::string("1 Main Street"),
+                Value::string("555-0100"),
+            ],
+            0,
+        )
+        .await?;
+        let processor = account(&self.new_account("processor").await?);
+        let events = self
+            .exec(
+                size,
+                &manager,
+                "PaymentManagerService::add_payment_processor",
+                vec![account_value(&processor), Value::string("Bench Payments")],
+                0,
+            )
+            .await?;
+        let processor_id = event_field(&events, "AddPaymentProcessorEvent", "processor_id")?;
+        self.exec(
+            size,
+            &manager,
+            "PaymentManagerService::set_processor_fee_rate",
+            vec![Value::u128(processor_id as u128), Value::u128(100)],
+            0,
+        )
+        .await?;
+        self.exec(
+            size,
+            &manager,
+            "PaymentManagerService::set_capture_timeout",
+            vec![Value::u128(604_800_000)],
+            0,
+        )
+        .await?;
+        let deliver = account(&self.new_account("deliver").await?);
+        self.exec(
+            size,
+            &manager,
+            "DeliveryManagerService::add_deliver",
+            vec![
+                account_value(&deliver),
+                Value::string("Bench Courier"),
+                Value::string("3 Main Street"),
+                Value::string("555-0102"),
+            ],
+            0,
+        )
+        .await?;
+        self.measure(
+            size,
+            &manager,
+            "ManagerService::change_manager",
+            vec![account_value(&account(&manager))],
+            0,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn add_customer(&mut self, size: u32, customer: &Keypair, record: bool) -> Result<()> {
+        let args = vec![
+            Value::string("Bench Customer"),
+            plain("2 Main Street"),
+            plain("555-0101"),
+        ];
+        if record {
+            self.exec(size, customer, "CustomerService::add_customer", args, 0)
+                .await?;
+        } else {
+            self.call(customer, "CustomerService::add_customer", args, 0)
+                .await?;
+        }
+        self.customers += 1;
+        Ok(())
+    }
+
+    async fn submit_order(
+        &mut self,
+        size: u32,
+        customer: &Keypair,
+        food_id: u64,
+        delivery_address: Value,
+        handoff_hash: Option<[u8; 32]>,
+        record: bool,
+    ) -> Result<u64> {
+        let args = vec![
+            Value::u128(food_id as u128),
+            Value::u128(self.restaurant_id as u128),
+            delivery_address,
+            plain("555-0101"),
+            none(),
+            Value::u128(0),
+            none(),
+            match handoff_hash {
+                Some(hash) => some(Value::unnamed_composite([Value::from_bytes(hash)])),
+                None => none(),
+            },
+        ];
+        let message = "CustomerService::submit_order";
+        let events = if record {
+            self.exec(size, customer, message, args, PRICE + DELIVERY_FEE)
+                .await?
+        } else {
+            self.call(customer, message, args, PRICE + DELIVERY_FEE)
+                .await?
+        };
+        self.orders += 1;
+        event_field(&events, "SubmitOrderEvent", "order_id")
+    }
+
+    /// A fresh account funded by the manager.
+    async fn new_account(&mut self, role: &str) -> Result<Keypair> {
+        self.accounts += 1;
+        let uri = SecretUri::from_str(&format!("//bench//{role}//{}", self.accounts))
+            .map_err(|e| anyhow!("invalid uri: {e:?}"))?;
+        let keypair = Keypair::from_uri(&uri).map_err(|e| anyhow!("invalid keypair: {e:?}"))?;
+        let tx = subxt::dynamic::tx(
+            "Balances",
+            "transfer_keep_alive",
+            vec![
+                (
+                    "dest",
+                    Value::unnamed_variant("Id", [account_value(&account(&keypair))]),
+                ),
+                ("value", Value::u128(FUNDING)),
+            ],
+        );
+        self.client
+            .api()
+            .tx()
+            .sign_and_submit_then_watch_default(&tx, &self.manager)
+            .await?
+            .wait_for_finalized_success()
+            .await
+            .context("funding bench account")?;
+        Ok(keypair)
+    }
+
+    /// Dry-runs `message` and records its cost without changing state.
+    async fn measure(
+        &mut self,
+        size: u32,
+        signer: &Keypair,
+        message: &str,
+        args: Vec<Value>,
+        value: u128,
+    ) -> Result<DryRun> {
+        let dry_run = self
+            .client
+            .dry_run(&account(signer), message, args, value)
+            .await
+            .with_context(|| format!("measuring {message} at size {size}"))?;
+        self.report.record(Measurement::new(
+            message,
+            size,
+            dry_run.gas_consumed,
+            dry_run.gas_required,
+            &dry_run.storage_deposit,
+        ));
+        Ok(dry_run)
+    }
+
+    /// Records the cost of `message` and then submits it.
+    async fn exec(
+        &mut self,
+        size: u32,
+        signer: &Keypair,
+        message: &str,
+        args: Vec<Value>,
+        value: u128,
+    ) -> Result<Vec<DecodedEvent>> {
+        self.measure(size, signer, message, args.clone(), value)
+            .await?;
+        self.call(signer, message, args, value).await
+    }
+
+    async fn call(
+        &self,
+        signer: &Keypair,
+        message: &str,
+        args: Vec<Value>,
+        value: u128,
+    ) -> Result<Vec<DecodedEvent>> {
+        self.client.call(signer, message, args, value).await
+    }
+
+    async fn amount(&self, message: &str, args: Vec<Value>) -> Result<u128> {
+        let value = self.client.query(message, args).await?;
+        match &value {
+            serde_json::Value::Number(n) => n.as_u64().map(u128::from),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow!("{message} returned {value}"))
+    }
+}
+
+fn food_args(index: usize) -> Vec<Value> {
+    vec![
+        Value::string(format!("Bench Food {index}")),
+        Value::string("A realistic description of a dish on the menu"),
+        Value::u128(PRICE),
+        Value::u128(ETA as u128),
+    ]
+}
+
+fn account(keypair: &Keypair) -> AccountId32 {
+    AccountId32(keypair.public_key().0)
+}
+
+fn account_value(account: &AccountId32) -> Value {
+    Value::unnamed_composite([Value::from_bytes(account.0)])
+}
+
+fn hash_value(byte: u8) -> Value {
+    Value::unnamed_composite([Value::from_bytes([byte; 32])])
+}
+
+fn plain(text: &str) -> Value {
+    Value::unnamed_variant("Plain", [Value::string(text)])
+}
+
+fn sealed_box(recipient: [u8; 32]) -> Value {
+    Value::named_composite([
+        ("recipient", Value::from_bytes(recipient)),
+        ("ciphertext", Value::from_bytes([0xab; 96])),
+    ])
+}
+
+fn some(value: Value) -> Value {
+    Value::unnamed_variant("Some", [value])
+}
+
+fn none() -> Value {
+    Value::unnamed_variant("None", [])
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before 1970")
+        .as_millis() as u64
+}
+
+fn event_field(events: &[DecodedEvent], name: &str, field: &str) -> Result<u64> {
+    events
+        .iter()
+        .find(|event| event.name == name)
+        .and_then(|event| event.u64(field))
+        .ok_or_else(|| anyhow!("no {name} with {field} emitted"))
+}
@@ -0,0 +1,599 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(min_specialization)]
+
+//! The delivery business of a split deployment: couriers, their bonds and
+//! the deliveries the ordering contract opens. Orders are read and moved
+//! along through the ordering contract.
+
+pub use self::delivery::{FoodDelivery, FoodDeliveryRef};
+
+#[openbrush::contract]
+pub mod delivery {
+    use ink::codegen::EmitEvent;
+    use ink::codegen::Env;
+    use ink::prelude::{
+        string::String,
+        vec::Vec,
+    };
+    use logics::{
+        impls::{
+            Bonding::BondingEvents,
+            BondService::BondServiceEvents,
+            DeliverService::DeliverServiceEvents,
+            DeliveryManagerService::DeliveryManagerServiceEvents,
+            Ledger::Ledger,
+            ManagerService::ManagerServiceEvents,
+            Ordering::Ordering,
+            Settlement::SettlementEvents,
+            WorkflowLink::WorkflowLinkEvents,
+            types::{Data, DeliveryData, EVENT_SCHEMA_VERSION, Order, OrderId, DeliverId, DeliveryId, PublicKey, DropOffAttestation, EcdsaAddress, BondPolicy},
+        },
+        traits::{
+            BondService::BondService,
+            DeliverService::DeliverService,
+            DeliveryManagerService::DeliveryManagerService,
+            DeliveryWorkflow::DeliveryWorkflow,
+            Get::GetRef,
+            GetDelivery::GetDelivery,
+            GetPayment::GetPaymentRef,
+            ManagerService::ManagerService,
+            OrderWorkflow::OrderWorkflowRef,
+            PaymentLedger::PaymentLedgerRef,
+            WorkflowLink::WorkflowLink,
+        },
+    };
+    use openbrush::traits::Storage;
+
+    #[ink(storage)]
+    #[derive(Storage, Default)]
+    pub struct FoodDelivery {
+        #[storage_field]
+        food_order_data: Data,
+        #[storage_field]
+        delivery_data: DeliveryData,
+    }
+
+    impl BondService for FoodDelivery {}
+
+    impl DeliverService for FoodDelivery {}
+
+    impl DeliveryManagerService for FoodDelivery {}
+
+    impl DeliveryWorkflow for FoodDelivery {}
+
+    impl GetDelivery for FoodDelivery {}
+
+    impl ManagerService for FoodDelivery {}
+
+    impl WorkflowLink for FoodDelivery {}
+
+    impl FoodDelivery {
+        #[ink(constructor, payable)]
+        pub fn new() -> Self {
+            let mut instance = Self::default();
+            instance.food_order_data.manager = Self::env().caller();
+            instance.food_order_data.upgrader = Self::env().caller();
+            Self::env().emit_event(EventSchemaEvent {
+                version: EVENT_SCHEMA_VERSION,
+            });
+            instance
+        }
+
+        /// Replaces the contract code while keeping its storage.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) {
+            assert!(self.env().caller() == self.food_order_data.upgrader, "Only upgrader can set code!");
+            assert!(self.env().set_code_hash(&code_hash).is_ok(), "Code hash does not exist!");
+            self.env().emit_event(SetCodeEvent {
+                code_hash,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_upgrader(&self) -> AccountId {
+            self.food_order_data.upgrader
+        }
+    }
+
+    #[ink(event)]
+    pub struct PickupDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+    }
+
+    #[ink(event)]
+    pub struct HandoffDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+    }
+
+    #[ink(event)]
+    pub struct AddDeliverEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        deliver_name: String,
+        deliver_address: String,
+        phone_number: String,
+    }
+
+    #[ink(event)]
+    pub struct CreditBalanceEvent {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        order_id: OrderId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct ChangeManagerEvent {
+        #[ink(topic)]
+        previous_manager: AccountId,
+        #[ink(topic)]
+        new_manager: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetWorkflowContractsEvent {
+        #[ink(topic)]
+        order_contract: AccountId,
+        #[ink(topic)]
+        delivery_contract: AccountId,
+        #[ink(topic)]
+        payment_contract: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct UpdateDeliverEncryptionKeyEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        encryption_key: Option<PublicKey>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateDeliverAttestationKeyEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        attestation_key: Option<EcdsaAddress>,
+    }
+
+    #[ink(event)]
+    pub struct DropOffDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        timestamp: Timestamp,
+        location_hash: Option<Hash>,
+        photo_hash: Option<Hash>,
+    }
+
+    #[ink(event)]
+    pub struct TimeOutDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+    }
+
+    #[ink(event)]
+    pub struct PostBondEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        amount: u128,
+        bond: u128,
+    }
+
+    #[ink(event)]
+    pub struct DeregisterDeliverEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        unlocks_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct WithdrawBondEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SlashBondEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        #[ink(topic)]
+        order_id: OrderId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetBondPolicyEvent {
+        minimum_bond: u128,
+        lock_period: u64,
+        slash_rate: u16,
+        delivery_timeout: u64,
+    }
+
+    #[ink(event)]
+    pub struct SetCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct EventSchemaEvent {
+        version: u16,
+    }
+
+    impl DeliverServiceEvents for FoodDelivery {
+        fn emit_pickup_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            self.env().emit_event(PickupDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+            })
+        }
+
+        fn emit_handoff_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            self.env().emit_event(HandoffDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+            })
+        }
+
+        fn emit_update_deliver_encryption_key_event(
+            &self,
+            deliver_id: DeliverId,
+            encryption_key: Option<PublicKey>,
+        ) {
+            self.env().emit_event(UpdateDeliverEncryptionKeyEvent {
+                deliver_id,
+                encryption_key,
+            })
+        }
+
+        fn emit_drop_off_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+            attestation: DropOffAttestation,
+        ) {
+            self.env().emit_event(DropOffDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+                timestamp: attestation.timestamp,
+                location_hash: attestation.location_hash,
+                photo_hash: attestation.photo_hash,
+            })
+        }
+
+        fn emit_update_deliver_attestation_key_event(
+            &self,
+            deliver_id: DeliverId,
+            attestation_key: Option<EcdsaAddress>,
+        ) {
+            self.env().emit_event(UpdateDeliverAttestationKeyEvent {
+                deliver_id,
+                attestation_key,
+            })
+        }
+    }
+
+    impl BondServiceEvents for FoodDelivery {
+        fn emit_post_bond_event(
+            &self,
+            deliver_id: DeliverId,
+            amount: u128,
+            bond: u128,
+        ) {
+            self.env().emit_event(PostBondEvent {
+                deliver_id,
+                amount,
+                bond,
+            })
+        }
+
+        fn emit_deregister_deliver_event(
+            &self,
+            deliver_id: DeliverId,
+            unlocks_at: Timestamp,
+        ) {
+            self.env().emit_event(DeregisterDeliverEvent {
+                deliver_id,
+                unlocks_at,
+            })
+        }
+
+        fn emit_withdraw_bond_event(
+            &self,
+            deliver_id: DeliverId,
+            amount: u128,
+        ) {
+            self.env().emit_event(WithdrawBondEvent {
+                deliver_id,
+                amount,
+            })
+        }
+
+        fn emit_time_out_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            self.env().emit_event(TimeOutDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+            })
+        }
+
+        fn emit_set_bond_policy_event(
+            &self,
+            policy: BondPolicy,
+        ) {
+            self.env().emit_event(SetBondPolicyEvent {
+                minimum_bond: policy.minimum_bond,
+                lock_period: policy.lock_period,
+                slash_rate: policy.slash_rate,
+                delivery_timeout: policy.delivery_timeout,
+            })
+        }
+    }
+
+    impl BondingEvents for FoodDelivery {
+        fn emit_slash_bond_event(
+            &self,
+            deliver_id: DeliverId,
+            order_id: OrderId,
+            amount: u128,
+        ) {
+            self.env().emit_event(SlashBondEvent {
+                deliver_id,
+                order_id,
+                amount,
+            })
+        }
+    }
+
+    impl ManagerServiceEvents for FoodDelivery {
+        fn emit_change_manager_event(
+            &self,
+            previous_manager: AccountId,
+            new_manager: AccountId,
+        ) {
+            self.env().emit_event(ChangeManagerEvent {
+                previous_manager,
+                new_manager,
+            })
+        }
+    }
+
+    impl DeliveryManagerServiceEvents for FoodDelivery {
+        fn emit_add_deliver_event(
+            &self,
+            deliver_id: DeliverId,
+            deliver_name: String,
+            deliver_address: String,
+            phone_number: String,
+        ) {
+            self.env().emit_event(AddDeliverEvent {
+                deliver_id,
+                deliver_name,
+                deliver_address,
+                phone_number,
+            })
+        }
+    }
+
+    impl WorkflowLinkEvents for FoodDelivery {
+        fn emit_set_workflow_contracts_event(
+            &self,
+            order_contract: AccountId,
+            delivery_contract: AccountId,
+            payment_contract: AccountId,
+        ) {
+            self.env().emit_event(SetWorkflowContractsEvent {
+                order_contract,
+                delivery_contract,
+                payment_contract,
+            })
+        }
+    }
+
+    impl SettlementEvents for FoodDelivery {
+        fn emit_credit_balance_event(
+            &self,
+            account: AccountId,
+            order_id: OrderId,
+            amount: u128,
+        ) {
+            self.env().emit_event(CreditBalanceEvent {
+                account,
+                order_id,
+                amount,
+            })
+        }
+    }
+
+    // Orders are kept by the ordering contract.
+    impl Ordering for FoodDelivery {
+        fn _order(
+            &self,
+            order_id: OrderId,
+        ) -> Order {
+            GetRef::get_order_from_id(&self.food_order_data.order_contract, order_id)
+        }
+
+        fn _pick_up_order(
+            &mut self,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            let order_contract = self.food_order_data.order_contract;
+            let deliver = self.food_order_data.delivers.get(&deliver_id).unwrap();
+            OrderWorkflowRef::pick_up_order(&order_contract, order_id, deliver_id, deliver);
+        }
+
+        fn _accept_delivery(
+            &mut self,
+            order_id: OrderId,
+        ) {
+            let order_contract = self.food_order_data.order_contract;
+            OrderWorkflowRef::accept_delivery(&order_contract, order_id);
+        }
+
+        fn _time_out_order(
+            &mut self,
+            order_id: OrderId,
+        ) -> Vec<(AccountId, u128)> {
+            let order_contract = self.food_order_data.order_contract;
+            OrderWorkflowRef::time_out_order(&order_contract, order_id)
+        }
+    }
+
+    // Slashed bonds are paid out by the payment contract, which also keeps
+    // the treasury.
+    impl Ledger for FoodDelivery {
+        fn _add_balance(
+            &mut self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            if amount > 0 {
+                let payment_contract = self.food_order_data.payment_contract;
+                PaymentLedgerRef::credit_builder(&payment_contract, account)
+                    .transferred_value(amount)
+                    .invoke();
+            }
+        }
+
+        fn _add_treasury(
+            &mut self,
+            amount: u128,
+        ) {
+            if amount > 0 {
+                let payment_contract = self.food_order_data.payment_contract;
+                PaymentLedgerRef::credit_treasury_builder(&payment_contract)
+                    .transferred_value(amount)
+                    .invoke();
+            }
+        }
+
+        fn _take_treasury(
+            &mut self,
+            amount: u128,
+        ) {
+            let payment_contract = self.food_order_data.payment_contract;
+            PaymentLedgerRef::draw_treasury(&payment_contract, amount);
+        }
+
+        fn _treasury(
+            &self,
+        ) -> u128 {
+            GetPaymentRef::get_treasury_balance(&self.food_order_data.payment_contract)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+        use logics::impls::types::{
+            DeliveryStatus,
+            PersonalData,
+        };
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller::<DefaultEnvironment>(account);
+        }
+
+        // Alice manages the contract, charlie's account stands in for the
+        // ordering contract and eve's for the payment contract.
+        fn setup() -> FoodDelivery {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut delivery = FoodDelivery::new();
+            delivery.set_workflow_contracts(accounts.charlie, accounts.alice, accounts.eve);
+            delivery
+        }
+
+        #[ink::test]
+        fn ordering_contract_dispatches_delivery() {
+            let accounts = accounts();
+            let mut delivery = setup();
+            set_caller(accounts.charlie);
+            let delivery_id = delivery.dispatch_delivery(1, 1, 1, PersonalData::Plain(String::from("Main Street 1")));
+            assert_eq!(delivery.get_delivery_from_id(delivery_id).status, DeliveryStatus::Waiting);
+            assert_eq!(delivery.get_delivery_from_id(delivery_id).order_id, 1);
+            let delivery_id = delivery.update_delivery_address(1, PersonalData::Plain(String::from("Main Street 2")));
+            assert_eq!(delivery.get_delivery_from_id(delivery_id).delivery_address, PersonalData::Plain(String::from("Main Street 2")));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery is cancelled!")]
+        fn cancelled_delivery_cannot_be_picked_up() {
+            let accounts = accounts();
+            let mut delivery = setup();
+            set_caller(accounts.charlie);
+            let delivery_id = delivery.dispatch_delivery(1, 1, 1, PersonalData::Plain(String::from("Main Street 1")));
+            delivery.cancel_delivery(1);
+            assert_eq!(delivery.get_delivery_from_id(delivery_id).status, DeliveryStatus::Cancelled);
+            set_caller(accounts.alice);
+            delivery.add_deliver(accounts.django, String::new(), String::new(), String::new());
+            set_caller(accounts.django);
+            delivery.confirm_delivery(delivery_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only order contract can dispatch delivery!")]
+        fn only_ordering_contract_can_dispatch_delivery() {
+            let accounts = accounts();
+            let mut delivery = setup();
+            set_caller(accounts.bob);
+            delivery.dispatch_delivery(1, 1, 1, PersonalData::Plain(String::from("Main Street 1")));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only order contract can slash deliver!")]
+        fn only_ordering_contract_can_slash_deliver() {
+            let accounts = accounts();
+            let mut delivery = setup();
+            set_caller(accounts.bob);
+            delivery.slash_deliver(1, 1, Vec::new());
+        }
+    }
+}
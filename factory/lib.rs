@@ -0,0 +1,260 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Deploys a `FoodOrder` market per city from a stored code hash, keeps a
+//! registry of the markets by region and rolls new code out to them region
+//! by region.
+
+#[ink::contract]
+mod factory {
+    use contract::FoodOrderRef;
+    use ink::env::call::FromAccountId;
+    use ink::prelude::{
+        string::String,
+        vec::Vec,
+    };
+    use ink::storage::Mapping;
+    use ink::ToAccountId;
+    use logics::impls::types::MarketConfig;
+
+    #[ink(storage)]
+    pub struct FoodOrderFactory {
+        owner: AccountId,
+        // Code new markets are instantiated from.
+        code_hash: Hash,
+        markets: Mapping<String, AccountId>,
+        regions: Vec<String>,
+        // Code each market runs, so a staged roll-out can be followed.
+        market_code: Mapping<String, Hash>,
+    }
+
+    #[ink(event)]
+    pub struct DeployMarketEvent {
+        region: String,
+        #[ink(topic)]
+        market: AccountId,
+        #[ink(topic)]
+        manager: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RollOutCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+        regions: Vec<String>,
+    }
+
+    #[ink(event)]
+    pub struct ChangeOwnerEvent {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    impl FoodOrderFactory {
+        #[ink(constructor)]
+        pub fn new(code_hash: Hash) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                code_hash,
+                markets: Mapping::default(),
+                regions: Vec::new(),
+                market_code: Mapping::default(),
+            }
+        }
+
+        /// Instantiates the market of a region, the transferred value is its
+        /// endowment. The factory stays the upgrader of the market.
+        #[ink(message, payable)]
+        pub fn deploy_market(
+            &mut self,
+            region: String,
+            manager: AccountId,
+            config: MarketConfig,
+        ) -> AccountId {
+            assert!(self.env().caller() == self.owner, "Only owner can deploy market!");
+            assert!(!self.markets.contains(&region), "Region already has a market!");
+            let market = FoodOrderRef::new_market(manager, config)
+                .code_hash(self.code_hash)
+                .endowment(self.env().transferred_value())
+                .salt_bytes(region.as_bytes())
+                .instantiate();
+            let market = market.to_account_id();
+            self.markets.insert(&region, &market);
+            self.market_code.insert(&region, &self.code_hash);
+            self.regions.push(region.clone());
+            self.env().emit_event(DeployMarketEvent {
+                region,
+                market,
+                manager,
+            });
+            market
+        }
+
+        /// Deploys new markets from `code_hash` and upgrades the markets of
+        /// the given regions to it, so code can be rolled out in stages.
+        #[ink(message)]
+        pub fn roll_out_code(&mut self, code_hash: Hash, regions: Vec<String>) {
+            assert!(self.env().caller() == self.owner, "Only owner can roll out code!");
+            self.code_hash = code_hash;
+            for region in regions.iter() {
+                let market = self.markets.get(region).expect("Region has no market!");
+                FoodOrderRef::from_account_id(market).set_code(code_hash);
+                self.market_code.insert(region, &code_hash);
+            }
+            self.env().emit_event(RollOutCodeEvent {
+                code_hash,
+                regions,
+            });
+        }
+
+        #[ink(message)]
+        pub fn change_owner(&mut self, new_owner: AccountId) {
+            let caller = self.env().caller();
+            assert!(caller == self.owner, "Only owner can change owner!");
+            self.owner = new_owner;
+            self.env().emit_event(ChangeOwnerEvent {
+                previous_owner: caller,
+                new_owner,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_market(&self, region: String) -> Option<AccountId> {
+            self.markets.get(&region)
+        }
+
+        #[ink(message)]
+        pub fn get_markets(&self) -> Vec<(String, AccountId)> {
+            self.regions
+                .iter()
+                .map(|region| (region.clone(), self.markets.get(region).unwrap()))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_market_code(&self, region: String) -> Option<Hash> {
+            self.market_code.get(&region)
+        }
+
+        #[ink(message)]
+        pub fn get_code_hash(&self) -> Hash {
+            self.code_hash
+        }
+
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        #[ink::test]
+        fn roll_out_replaces_code_hash() {
+            set_caller(accounts().alice);
+            let mut factory = FoodOrderFactory::new(Hash::from([1; 32]));
+            factory.roll_out_code(Hash::from([2; 32]), Vec::new());
+            assert_eq!(factory.get_code_hash(), Hash::from([2; 32]));
+            assert_eq!(factory.get_markets(), Vec::new());
+            assert_eq!(factory.get_market(String::from("Berlin")), None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only owner can deploy market!")]
+        fn only_owner_can_deploy_market() {
+            set_caller(accounts().alice);
+            let mut factory = FoodOrderFactory::new(Hash::from([1; 32]));
+            set_caller(accounts().bob);
+            factory.deploy_market(String::from("Berlin"), accounts().bob, MarketConfig::default());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only owner can roll out code!")]
+        fn only_owner_can_roll_out_code() {
+            set_caller(accounts().alice);
+            let mut factory = FoodOrderFactory::new(Hash::from([1; 32]));
+            factory.change_owner(accounts().bob);
+            assert_eq!(factory.get_owner(), accounts().bob);
+            factory.roll_out_code(Hash::from([2; 32]), Vec::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Region has no market!")]
+        fn roll_out_to_unknown_region_fails() {
+            set_caller(accounts().alice);
+            let mut factory = FoodOrderFactory::new(Hash::from([1; 32]));
+            factory.roll_out_code(Hash::from([2; 32]), vec![String::from("Berlin")]);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use contract::FoodOrderRef;
+        use ink_e2e::build_message;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test(additional_contracts = "../Cargo.toml")]
+        async fn rolls_code_out_to_deployed_market(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let code_hash = client
+                .upload("contract", &ink_e2e::alice(), None)
+                .await
+                .expect("upload failed")
+                .code_hash;
+            let factory = client
+                .instantiate("factory", &ink_e2e::alice(), FoodOrderFactoryRef::new(code_hash), 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let deploy_market = build_message::<FoodOrderFactoryRef>(factory.clone()).call(|factory| {
+                factory.deploy_market(String::from("Berlin"), ink_e2e::account_id(ink_e2e::AccountKeyring::Bob), MarketConfig::default())
+            });
+            let market = client
+                .call(&ink_e2e::alice(), deploy_market, 1_000_000_000, None)
+                .await
+                .expect("deploy_market failed")
+                .return_value();
+            let get_upgrader = build_message::<FoodOrderRef>(market.clone()).call(|market| market.get_upgrader());
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &get_upgrader, 0, None).await.return_value(), factory);
+
+            // Only one build of the market contract is at hand, so the
+            // market is rolled out to the code it already runs.
+            let roll_out_code = build_message::<FoodOrderFactoryRef>(factory.clone())
+                .call(|factory| factory.roll_out_code(code_hash, vec![String::from("Berlin")]));
+            let result = client
+                .call(&ink_e2e::alice(), roll_out_code, 0, None)
+                .await
+                .expect("roll_out_code failed");
+            // The market emits its SetCodeEvent, the factory its RollOutCodeEvent.
+            let emitted = result
+                .events
+                .iter()
+                .filter_map(|event| event.ok())
+                .filter(|event| event.pallet_name() == "Contracts" && event.variant_name() == "ContractEmitted")
+                .count();
+            assert_eq!(emitted, 2);
+            let get_market_code = build_message::<FoodOrderFactoryRef>(factory.clone())
+                .call(|factory| factory.get_market_code(String::from("Berlin")));
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &get_market_code, 0, None).await.return_value(), Some(code_hash));
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &get_upgrader, 0, None).await.return_value(), factory);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// A CloudEvents 1.0 event in structured JSON form. Extension attributes such
+/// as the `orderid` correlation attribute end up in `extensions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CloudEvent {
+    pub specversion: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub source: String,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    #[serde(default)]
+    pub data: Value,
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+/// One entry of the workflow's `orderevents.yml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventDefinition {
+    pub name: &'static str,
+    pub source: &'static str,
+    pub event_type: &'static str,
+    pub correlation: &'static [&'static str],
+}
+
+pub const FOOD_ORDER_EVENT: &str = "Food Order Event";
+pub const ETA_DEADLINE_EVENT: &str = "ETA Deadline Event";
+pub const ORDER_PICKED_UP_EVENT: &str = "Order Picked Up Event";
+pub const ORDER_DELIVERED_EVENT: &str = "Order Delievered Event";
+
+pub const EVENT_DEFINITIONS: &[EventDefinition] = &[
+    EventDefinition {
+        name: FOOD_ORDER_EVENT,
+        source: "/orders/",
+        event_type: "org.orders",
+        correlation: &["orderid"],
+    },
+    EventDefinition {
+        name: ETA_DEADLINE_EVENT,
+        source: "/ordersETA",
+        event_type: "org.orders.eta",
+        correlation: &["orderid"],
+    },
+    EventDefinition {
+        name: ORDER_PICKED_UP_EVENT,
+        source: "/ordersPickup",
+        event_type: "org.orders.delivery",
+        correlation: &["orderid"],
+    },
+    EventDefinition {
+        name: ORDER_DELIVERED_EVENT,
+        source: "/orderDelivery",
+        event_type: "org.orders.delivery",
+        correlation: &["orderid"],
+    },
+];
+
+impl CloudEvent {
+    /// Builds an event from binary content mode: attributes in `ce-` headers
+    /// and the body as `data`.
+    pub fn from_binary<'a>(
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+        body: &[u8],
+    ) -> Result<Self> {
+        let mut attributes = Map::new();
+        for (name, value) in headers {
+            if let Some(attribute) = name.to_ascii_lowercase().strip_prefix("ce-") {
+                attributes.insert(attribute.to_string(), Value::String(value.to_string()));
+            }
+        }
+        let data = if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(body)?
+        };
+        attributes.insert("data".to_string(), data);
+        Ok(serde_json::from_value(Value::Object(attributes))?)
+    }
+
+    pub fn extension(&self, name: &str) -> Option<&str> {
+        self.extensions.get(name).and_then(Value::as_str)
+    }
+
+    /// Checks the required context attributes and finds the workflow event
+    /// definition this event belongs to.
+    pub fn validate(&self) -> Result<&'static EventDefinition> {
+        if self.specversion != "1.0" {
+            bail!("unsupported specversion {}", self.specversion);
+        }
+        if self.id.is_empty() || self.source.is_empty() || self.event_type.is_empty() {
+            bail!("id, source and type are required");
+        }
+        let definition = EVENT_DEFINITIONS
+            .iter()
+            .find(|definition| {
+                definition.event_type == self.event_type
+                    && definition.source.trim_end_matches('/') == self.source.trim_end_matches('/')
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "no event definition for type {} from source {}",
+                    self.event_type,
+                    self.source
+                )
+            })?;
+        for attribute in definition.correlation {
+            if self.extension(attribute).is_none_or(str::is_empty) {
+                bail!(
+                    "{} requires correlation attribute {attribute}",
+                    definition.name
+                );
+            }
+        }
+        Ok(definition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(source: &str, event_type: &str, orderid: Option<&str>) -> CloudEvent {
+        let mut event = json!({
+            "specversion": "1.0",
+            "type": event_type,
+            "source": source,
+            "id": "evt-1",
+            "data": {},
+        });
+        if let Some(orderid) = orderid {
+            event["orderid"] = json!(orderid);
+        }
+        serde_json::from_value(event).unwrap()
+    }
+
+    #[test]
+    fn validate_finds_definition_by_type_and_source() {
+        let food_order = event("/orders", "org.orders", Some("o-1"));
+        assert_eq!(food_order.validate().unwrap().name, FOOD_ORDER_EVENT);
+        let picked_up = event("/ordersPickup", "org.orders.delivery", Some("o-1"));
+        assert_eq!(picked_up.validate().unwrap().name, ORDER_PICKED_UP_EVENT);
+        let delivered = event("/orderDelivery/", "org.orders.delivery", Some("o-1"));
+        assert_eq!(delivered.validate().unwrap().name, ORDER_DELIVERED_EVENT);
+        assert_eq!(food_order.extension("orderid"), Some("o-1"));
+    }
+
+    #[test]
+    fn validate_rejects_unsupported_specversion() {
+        let mut event = event("/orders", "org.orders", Some("o-1"));
+        event.specversion = "0.3".to_string();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_missing_id() {
+        let mut event = event("/orders", "org.orders", Some("o-1"));
+        event.id = String::new();
+        assert!(event.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_type_or_source() {
+        assert!(event("/orders", "org.orders.unknown", Some("o-1"))
+            .validate()
+            .is_err());
+        assert!(event("/elsewhere", "org.orders", Some("o-1"))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn validate_requires_correlation_attribute() {
+        assert!(event("/orders", "org.orders", None).validate().is_err());
+        assert!(event("/orders", "org.orders", Some("")).validate().is_err());
+    }
+
+    #[test]
+    fn from_binary_reads_ce_headers_and_body() {
+        let event = CloudEvent::from_binary(
+            [
+                ("ce-specversion", "1.0"),
+                ("CE-Type", "org.orders.eta"),
+                ("ce-source", "/ordersETA"),
+                ("ce-id", "evt-2"),
+                ("ce-orderid", "o-2"),
+                ("content-type", "application/json"),
+            ],
+            br#"{"eta": 15}"#,
+        )
+        .unwrap();
+        assert_eq!(event.validate().unwrap().name, ETA_DEADLINE_EVENT);
+        assert_eq!(event.extension("orderid"), Some("o-2"));
+        assert_eq!(event.data, json!({ "eta": 15 }));
+        assert!(!event.extensions.contains_key("content-type"));
+    }
+}
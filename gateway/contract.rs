@@ -0,0 +1,397 @@
+use anyhow::{anyhow, bail, Context, Result};
+use indexer::{
+    decode::to_json, decode_event, metadata::MessageSpec, ContractMetadata, DecodedEvent,
+};
+use scale::{Decode, Encode};
+use scale_value::{Composite, Value};
+use serde_json::Value as Json;
+use subxt::{utils::AccountId32, OnlineClient, SubstrateConfig};
+use subxt_signer::sr25519::Keypair;
+
+/// Mirrors `sp_weights::Weight`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+pub struct Weight {
+    #[codec(compact)]
+    pub ref_time: u64,
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+pub enum StorageDeposit {
+    Refund(u128),
+    Charge(u128),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+struct ExecReturnValue {
+    flags: u32,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+struct InstantiateReturnValue {
+    result: ExecReturnValue,
+    account_id: [u8; 32],
+}
+
+/// The leading fields of `pallet_contracts_primitives::ContractResult`; later
+/// runtimes append more fields, which are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+struct ContractResult<R> {
+    gas_consumed: Weight,
+    gas_required: Weight,
+    storage_deposit: StorageDeposit,
+    debug_message: Vec<u8>,
+    result: Result<R, DispatchErrorBytes>,
+}
+
+impl<R> ContractResult<R> {
+    fn output(self, label: &str) -> Result<(R, Self)>
+    where
+        R: Clone,
+    {
+        match &self.result {
+            Ok(output) => Ok((output.clone(), self)),
+            Err(DispatchErrorBytes(index)) => bail!(
+                "{label} failed with dispatch error {index}: {}",
+                String::from_utf8_lossy(&self.debug_message)
+            ),
+        }
+    }
+}
+
+fn check_revert(label: &str, output: &ExecReturnValue, debug_message: &[u8]) -> Result<()> {
+    if output.flags & REVERT_FLAG != 0 {
+        bail!(
+            "{label} reverted: {}",
+            String::from_utf8_lossy(debug_message)
+        );
+    }
+    Ok(())
+}
+
+/// `DispatchError` is kept opaque; its first byte is the variant index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DispatchErrorBytes(u8);
+
+impl Decode for DispatchErrorBytes {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Ok(DispatchErrorBytes(input.read_byte()?))
+    }
+}
+
+const REVERT_FLAG: u32 = 1;
+
+/// Result of a dry run through the `ContractsApi_call` runtime API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRun {
+    pub gas_consumed: Weight,
+    pub gas_required: Weight,
+    pub storage_deposit: StorageDeposit,
+    /// The decoded return value of the message, with ink!'s `Ok` wrapper
+    /// removed.
+    pub value: Json,
+}
+
+/// Cost of instantiating a contract, measured by a dry run through the
+/// `ContractsApi_instantiate` runtime API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instantiated {
+    pub gas_consumed: Weight,
+    pub gas_required: Weight,
+    pub storage_deposit: StorageDeposit,
+}
+
+/// Calls `FoodOrder` messages by label, encoding arguments and decoding
+/// results with the contract metadata.
+pub struct ContractClient {
+    api: OnlineClient<SubstrateConfig>,
+    contract: AccountId32,
+    metadata: ContractMetadata,
+}
+
+impl ContractClient {
+    pub async fn connect(
+        url: &str,
+        contract: [u8; 32],
+        metadata: ContractMetadata,
+    ) -> Result<Self> {
+        let api = OnlineClient::<SubstrateConfig>::from_url(url)
+            .await
+            .with_context(|| format!("connecting to {url}"))?;
+        Ok(ContractClient {
+            api,
+            contract: AccountId32(contract),
+            metadata,
+        })
+    }
+
+    /// Uploads `code` and instantiates it with `constructor`, signed by
+    /// `signer`.
+    pub async fn instantiate(
+        url: &str,
+        signer: &Keypair,
+        code: Vec<u8>,
+        metadata: ContractMetadata,
+        constructor: &str,
+        args: Vec<Value>,
+        value: u128,
+    ) -> Result<(Self, Instantiated)> {
+        let api = OnlineClient::<SubstrateConfig>::from_url(url)
+            .await
+            .with_context(|| format!("connecting to {url}"))?;
+        let spec = metadata
+            .constructor(constructor)
+            .ok_or_else(|| anyhow!("contract has no constructor {constructor}"))?;
+        let data = encode_args(&metadata, constructor, spec, args)?;
+        let salt = Vec::<u8>::new();
+        let mut params = Vec::new();
+        signer.public_key().0.encode_to(&mut params);
+        value.encode_to(&mut params);
+        None::<Weight>.encode_to(&mut params);
+        None::<u128>.encode_to(&mut params);
+        // `Code::Upload(code)`
+        0u8.encode_to(&mut params);
+        code.encode_to(&mut params);
+        data.encode_to(&mut params);
+        salt.encode_to(&mut params);
+        let bytes: Vec<u8> = api
+            .runtime_api()
+            .at_latest()
+            .await?
+            .call_raw("ContractsApi_instantiate", Some(&params))
+            .await?;
+        let (output, result) = ContractResult::<InstantiateReturnValue>::decode(&mut &bytes[..])?
+            .output(constructor)?;
+        check_revert(constructor, &output.result, &result.debug_message)?;
+        let gas_limit = with_margin(result.gas_required);
+        let tx = subxt::dynamic::tx(
+            "Contracts",
+            "instantiate_with_code",
+            vec![
+                ("value", Value::u128(value)),
+                ("gas_limit", weight_value(gas_limit)),
+                ("storage_deposit_limit", Value::unnamed_variant("None", [])),
+                ("code", Value::from_bytes(code)),
+                ("data", Value::from_bytes(data)),
+                ("salt", Value::from_bytes(salt)),
+            ],
+        );
+        api.tx()
+            .sign_and_submit_then_watch_default(&tx, signer)
+            .await?
+            .wait_for_finalized_success()
+            .await
+            .with_context(|| format!("instantiating with {constructor}"))?;
+        let client = ContractClient {
+            api,
+            contract: AccountId32(output.account_id),
+            metadata,
+        };
+        Ok((
+            client,
+            Instantiated {
+                gas_consumed: result.gas_consumed,
+                gas_required: result.gas_required,
+                storage_deposit: result.storage_deposit,
+            },
+        ))
+    }
+
+    pub fn contract(&self) -> &AccountId32 {
+        &self.contract
+    }
+
+    pub fn api(&self) -> &OnlineClient<SubstrateConfig> {
+        &self.api
+    }
+
+    pub fn metadata(&self) -> &ContractMetadata {
+        &self.metadata
+    }
+
+    /// Encodes selector and arguments of `message`, e.g.
+    /// `CustomerService::submit_order`.
+    pub fn encode_call(&self, message: &str, args: Vec<Value>) -> Result<Vec<u8>> {
+        let spec = self
+            .metadata
+            .message(message)
+            .ok_or_else(|| anyhow!("contract has no message {message}"))?;
+        encode_args(&self.metadata, message, spec, args)
+    }
+
+    pub async fn dry_run(
+        &self,
+        origin: &AccountId32,
+        message: &str,
+        args: Vec<Value>,
+        value: u128,
+    ) -> Result<DryRun> {
+        let data = self.encode_call(message, args)?;
+        let mut params = Vec::new();
+        origin.0.encode_to(&mut params);
+        self.contract.0.encode_to(&mut params);
+        value.encode_to(&mut params);
+        None::<Weight>.encode_to(&mut params);
+        None::<u128>.encode_to(&mut params);
+        data.encode_to(&mut params);
+        let bytes: Vec<u8> = self
+            .api
+            .runtime_api()
+            .at_latest()
+            .await?
+            .call_raw("ContractsApi_call", Some(&params))
+            .await?;
+        let (output, result) =
+            ContractResult::<ExecReturnValue>::decode(&mut &bytes[..])?.output(message)?;
+        check_revert(message, &output, &result.debug_message)?;
+        let spec = self
+            .metadata
+            .message(message)
+            .expect("checked by encode_call");
+        let value = match spec.return_type {
+            Some(type_id) => {
+                let decoded = scale_value::scale::decode_as_type(
+                    &mut &output.data[..],
+                    type_id,
+                    &self.metadata.registry,
+                )
+                .map_err(|e| anyhow!("decoding {message} result: {e:?}"))?;
+                unwrap_ok(to_json(&decoded, &self.metadata.registry))?
+            }
+            None => Json::Null,
+        };
+        Ok(DryRun {
+            gas_consumed: result.gas_consumed,
+            gas_required: result.gas_required,
+            storage_deposit: result.storage_deposit,
+            value,
+        })
+    }
+
+    /// Reads a value through a dry run, using the contract itself as origin.
+    pub async fn query(&self, message: &str, args: Vec<Value>) -> Result<Json> {
+        let origin = self.contract.clone();
+        Ok(self.dry_run(&origin, message, args, 0).await?.value)
+    }
+
+    /// Submits `message` signed by `signer` and waits until it is finalized.
+    /// Returns the contract events it emitted.
+    pub async fn call(
+        &self,
+        signer: &Keypair,
+        message: &str,
+        args: Vec<Value>,
+        value: u128,
+    ) -> Result<Vec<DecodedEvent>> {
+        let origin = AccountId32(signer.public_key().0);
+        let dry_run = self.dry_run(&origin, message, args.clone(), value).await?;
+        let data = self.encode_call(message, args)?;
+        let gas_limit = with_margin(dry_run.gas_required);
+        let tx = subxt::dynamic::tx(
+            "Contracts",
+            "call",
+            vec![
+                (
+                    "dest",
+                    Value::unnamed_variant(
+                        "Id",
+                        [Value::unnamed_composite([Value::from_bytes(
+                            self.contract.0,
+                        )])],
+                    ),
+                ),
+                ("value", Value::u128(value)),
+                ("gas_limit", weight_value(gas_limit)),
+                ("storage_deposit_limit", Value::unnamed_variant("None", [])),
+                ("data", Value::from_bytes(data)),
+            ],
+        );
+        let events = self
+            .api
+            .tx()
+            .sign_and_submit_then_watch_default(&tx, signer)
+            .await?
+            .wait_for_finalized_success()
+            .await
+            .with_context(|| format!("submitting {message}"))?;
+        let metadata = self.api.metadata();
+        let registry = metadata.types();
+        let mut decoded = Vec::new();
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                continue;
+            }
+            let Composite::Named(fields) = event.field_values()? else {
+                continue;
+            };
+            let field = |name: &str| {
+                fields
+                    .iter()
+                    .find(|(field, _)| field == name)
+                    .and_then(|(_, value)| indexer::decode::to_bytes(value, registry))
+            };
+            if field("contract").as_deref() != Some(&self.contract.0[..]) {
+                continue;
+            }
+            decoded.push(decode_event(
+                &self.metadata,
+                &field("data").unwrap_or_default(),
+            )?);
+        }
+        Ok(decoded)
+    }
+}
+
+fn encode_args(
+    metadata: &ContractMetadata,
+    label: &str,
+    spec: &MessageSpec,
+    args: Vec<Value>,
+) -> Result<Vec<u8>> {
+    if spec.args.len() != args.len() {
+        bail!(
+            "{label} takes {} arguments, got {}",
+            spec.args.len(),
+            args.len()
+        );
+    }
+    let mut data = spec.selector.to_vec();
+    for (arg, value) in spec.args.iter().zip(args) {
+        scale_value::scale::encode_as_type(&value, arg.type_id, &metadata.registry, &mut data)
+            .map_err(|e| anyhow!("encoding {label}.{}: {e:?}", arg.label))?;
+    }
+    Ok(data)
+}
+
+/// Dry runs are executed against the current state; leave 10% headroom for
+/// state that changes before the extrinsic is included.
+fn with_margin(weight: Weight) -> Weight {
+    Weight {
+        ref_time: weight.ref_time / 10 * 11,
+        proof_size: weight.proof_size / 10 * 11,
+    }
+}
+
+fn weight_value(weight: Weight) -> Value {
+    Value::named_composite([
+        ("ref_time", Value::u128(weight.ref_time as u128)),
+        ("proof_size", Value::u128(weight.proof_size as u128)),
+    ])
+}
+
+/// Removes the `Result<T, LangError>` wrapper ink! 4 puts around message
+/// return values.
+fn unwrap_ok(value: Json) -> Result<Json> {
+    match value {
+        Json::Object(mut map) if map.len() == 1 && map.contains_key("Ok") => {
+            Ok(map.remove("Ok").unwrap())
+        }
+        Json::Object(map) if map.len() == 1 && map.contains_key("Err") => {
+            Err(anyhow!("message returned an error: {}", map["Err"]))
+        }
+        value => Ok(value),
+    }
+}
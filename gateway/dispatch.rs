@@ -0,0 +1,430 @@
+use crate::{
+    cloudevents::{
+        CloudEvent, ETA_DEADLINE_EVENT, FOOD_ORDER_EVENT, ORDER_DELIVERED_EVENT,
+        ORDER_PICKED_UP_EVENT,
+    },
+    contract::ContractClient,
+    mapping::{Correlation, Correlations, Mapping},
+};
+use anyhow::{anyhow, Context, Result};
+use scale_value::Value;
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::str::FromStr;
+use subxt_signer::{sr25519::Keypair, SecretUri};
+use tokio::sync::Mutex;
+
+/// What the gateway did with an accepted event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Outcome {
+    pub definition: &'static str,
+    pub orderid: String,
+    pub order_ids: Vec<u64>,
+    /// Names of the contract events emitted by the submitted calls.
+    pub events: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum GatewayError {
+    /// The CloudEvent is malformed or refers to unknown ids.
+    Invalid(anyhow::Error),
+    /// The node rejected or failed to execute the call.
+    Chain(anyhow::Error),
+}
+
+impl std::fmt::Display for GatewayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GatewayError::Invalid(e) => write!(f, "invalid event: {e:#}"),
+            GatewayError::Chain(e) => write!(f, "chain error: {e:#}"),
+        }
+    }
+}
+
+fn invalid(e: anyhow::Error) -> GatewayError {
+    GatewayError::Invalid(e)
+}
+
+fn chain(e: anyhow::Error) -> GatewayError {
+    GatewayError::Chain(e)
+}
+
+pub struct Dispatcher {
+    client: ContractClient,
+    mapping: Mapping,
+    correlations: Mutex<Correlations>,
+}
+
+impl Dispatcher {
+    pub fn new(client: ContractClient, mapping: Mapping, correlations: Correlations) -> Self {
+        Dispatcher {
+            client,
+            mapping,
+            correlations: Mutex::new(correlations),
+        }
+    }
+
+    pub async fn handle(&self, event: CloudEvent) -> Result<Outcome, GatewayError> {
+        let definition = event.validate().map_err(invalid)?;
+        let orderid = event
+            .extension("orderid")
+            .expect("validated correlation attribute")
+            .to_string();
+        match definition.name {
+            FOOD_ORDER_EVENT => self.submit_order(&orderid, &event.data).await,
+            ORDER_PICKED_UP_EVENT => self.pickup(&orderid, &event.data).await,
+            ORDER_DELIVERED_EVENT => self.confirm_delivery(&orderid).await,
+            ETA_DEADLINE_EVENT => {
+                let correlation = self.correlation(&orderid).await?;
+                Ok(Outcome {
+                    definition: ETA_DEADLINE_EVENT,
+                    orderid,
+                    order_ids: correlation.order_ids,
+                    events: Vec::new(),
+                })
+            }
+            name => Err(invalid(anyhow!("{name} has no contract mapping"))),
+        }
+    }
+
+    async fn correlation(&self, orderid: &str) -> Result<Correlation, GatewayError> {
+        self.correlations
+            .lock()
+            .await
+            .get(orderid)
+            .cloned()
+            .ok_or_else(|| invalid(anyhow!("unknown orderid {orderid}")))
+    }
+
+    async fn submit_order(&self, orderid: &str, data: &Json) -> Result<Outcome, GatewayError> {
+        let mut correlations = self.correlations.lock().await;
+        if let Some(correlation) = correlations.get(orderid).filter(|c| c.is_complete()) {
+            // Redelivered event: the order is already on chain.
+            return Ok(Outcome {
+                definition: FOOD_ORDER_EVENT,
+                orderid: orderid.to_string(),
+                order_ids: correlation.order_ids.clone(),
+                events: Vec::new(),
+            });
+        }
+        let request = OrderRequest::parse(&self.mapping, data).map_err(invalid)?;
+        let signer = keypair(
+            self.mapping
+                .customer_signer(request.customer_id)
+                .map_err(invalid)?,
+        )
+        .map_err(invalid)?;
+        // A retried event resumes after the items that already landed.
+        let mut correlation = correlations
+            .get(orderid)
+            .cloned()
+            .unwrap_or_else(|| Correlation {
+                customer_id: request.customer_id.to_string(),
+                order_ids: Vec::new(),
+                items: request.foods.len(),
+            });
+
+        let delivery_fee = amount(
+            &self
+                .client
+                .query("Get::get_delivery_fee", vec![])
+                .await
+                .map_err(chain)?,
+        )
+        .map_err(chain)?;
+        let mut events = Vec::new();
+        for &food_id in request.foods.iter().skip(correlation.order_ids.len()) {
+            // Foods priced in fiat are converted at the current oracle rate.
+            let price = amount(
+                &self
+                    .client
+                    .query("Get::get_food_price", vec![Value::u128(food_id as u128)])
+                    .await
+                    .map_err(chain)?,
+            )
+            .map_err(chain)?;
+            let emitted = self
+                .client
+                .call(
+                    &signer,
+                    "CustomerService::submit_order",
+                    request.submit_order_args(food_id),
+                    price + delivery_fee,
+                )
+                .await
+                .map_err(chain)?;
+            let order_id = emitted
+                .iter()
+                .find(|event| event.name == "SubmitOrderEvent")
+                .and_then(|event| event.u64("order_id"))
+                .ok_or_else(|| chain(anyhow!("submit_order emitted no SubmitOrderEvent")))?;
+            // Persisted per item so a failure halfway does not resubmit the
+            // orders that already landed.
+            correlation.order_ids.push(order_id);
+            correlations
+                .insert(orderid, correlation.clone())
+                .map_err(chain)?;
+            events.extend(emitted.into_iter().map(|event| event.name));
+        }
+        Ok(Outcome {
+            definition: FOOD_ORDER_EVENT,
+            orderid: orderid.to_string(),
+            order_ids: correlation.order_ids,
+            events,
+        })
+    }
+
+    async fn pickup(&self, orderid: &str, data: &Json) -> Result<Outcome, GatewayError> {
+        let correlation = self.correlation(orderid).await?;
+        let courier_id = str_field(data, &["courierId"]).map_err(invalid)?;
+        let signer =
+            keypair(self.mapping.deliver_signer(courier_id).map_err(invalid)?).map_err(invalid)?;
+        let mut events = Vec::new();
+        for order_id in &correlation.order_ids {
+            let delivery_id = self
+                .client
+                .query(
+                    "GetDelivery::get_delivery_from_order",
+                    vec![Value::u128(*order_id as u128)],
+                )
+                .await
+                .map_err(chain)?
+                .as_u64()
+                .ok_or_else(|| chain(anyhow!("order {order_id} has no delivery yet")))?;
+            let emitted = self
+                .client
+                .call(
+                    &signer,
+                    "DeliverService::confirm_delivery",
+                    vec![Value::u128(delivery_id as u128)],
+                    0,
+                )
+                .await
+                .map_err(chain)?;
+            events.extend(emitted.into_iter().map(|event| event.name));
+        }
+        Ok(Outcome {
+            definition: ORDER_PICKED_UP_EVENT,
+            orderid: orderid.to_string(),
+            order_ids: correlation.order_ids,
+            events,
+        })
+    }
+
+    async fn confirm_delivery(&self, orderid: &str) -> Result<Outcome, GatewayError> {
+        let correlation = self.correlation(orderid).await?;
+        let signer = keypair(
+            self.mapping
+                .customer_signer(&correlation.customer_id)
+                .map_err(invalid)?,
+        )
+        .map_err(invalid)?;
+        let mut events = Vec::new();
+        for order_id in &correlation.order_ids {
+            let emitted = self
+                .client
+                .call(
+                    &signer,
+                    "CustomerService::confrim_delivery",
+                    vec![Value::u128(*order_id as u128)],
+                    0,
+                )
+                .await
+                .map_err(chain)?;
+            events.extend(emitted.into_iter().map(|event| event.name));
+        }
+        Ok(Outcome {
+            definition: ORDER_DELIVERED_EVENT,
+            orderid: orderid.to_string(),
+            order_ids: correlation.order_ids,
+            events,
+        })
+    }
+}
+
+/// The on-chain orders a `Food Order Event` asks for. The contract takes one
+/// food per order, so an item with `amount` n becomes n orders.
+#[derive(Debug, Clone, PartialEq)]
+struct OrderRequest<'a> {
+    customer_id: &'a str,
+    restaurant_id: u64,
+    address: &'a str,
+    requested_time: Option<u64>,
+    foods: Vec<u64>,
+}
+
+impl<'a> OrderRequest<'a> {
+    fn parse(mapping: &Mapping, data: &'a Json) -> Result<Self> {
+        let customer_id = str_field(data, &["customerId"])?;
+        let restaurant_id = mapping.restaurant(str_field(data, &["order", "restaurantId"])?)?;
+        let address = str_field(data, &["delivery", "address"])?;
+        let requested_time = requested_time(data)?;
+        let items = data["order"]["items"]
+            .as_array()
+            .filter(|items| !items.is_empty())
+            .ok_or_else(|| anyhow!("order.items must be a non-empty array"))?;
+        let mut foods = Vec::new();
+        for item in items {
+            let food_id = mapping.food(str_field(item, &["itemId"])?)?;
+            let amount = item["amount"].as_u64().unwrap_or(1);
+            foods.extend(std::iter::repeat_n(food_id, amount as usize));
+        }
+        Ok(OrderRequest {
+            customer_id,
+            restaurant_id,
+            address,
+            requested_time,
+            foods,
+        })
+    }
+
+    /// Arguments of `CustomerService::submit_order` for one of the foods.
+    fn submit_order_args(&self, food_id: u64) -> Vec<Value> {
+        vec![
+            Value::u128(food_id as u128),
+            Value::u128(self.restaurant_id as u128),
+            Value::unnamed_variant("Plain", [Value::string(self.address)]),
+            Value::unnamed_variant("Plain", [Value::string("")]),
+            match self.requested_time {
+                Some(timestamp) => Value::unnamed_variant("Some", [Value::u128(timestamp as u128)]),
+                None => Value::unnamed_variant("None", []),
+            },
+            // Gateway orders redeem no loyalty points or promo code and
+            // are confirmed by the customer rather than a handoff code.
+            Value::u128(0),
+            Value::unnamed_variant("None", []),
+            Value::unnamed_variant("None", []),
+        ]
+    }
+}
+
+fn keypair(uri: &str) -> Result<Keypair> {
+    let uri = SecretUri::from_str(uri).map_err(|e| anyhow!("invalid signer uri: {e:?}"))?;
+    Keypair::from_uri(&uri).map_err(|e| anyhow!("invalid signer: {e:?}"))
+}
+
+fn str_field<'a>(data: &'a Json, path: &[&str]) -> Result<&'a str> {
+    path.iter()
+        .try_fold(data, |value, key| value.get(key))
+        .and_then(Json::as_str)
+        .ok_or_else(|| anyhow!("data.{} is required", path.join(".")))
+}
+
+/// `delivery.requestedTime` is either `ASAP` (or absent) or an RFC 3339
+/// timestamp, converted to the contract's millisecond timestamps.
+fn requested_time(data: &Json) -> Result<Option<u64>> {
+    match data["delivery"]["requestedTime"].as_str() {
+        None | Some("") | Some("ASAP") => Ok(None),
+        Some(time) => {
+            let time = chrono::DateTime::parse_from_rfc3339(time)
+                .with_context(|| format!("invalid delivery.requestedTime {time}"))?;
+            Ok(Some(time.timestamp_millis() as u64))
+        }
+    }
+}
+
+/// Balances above `u64::MAX` are rendered as strings by the decoder.
+fn amount(value: &Json) -> Result<u128> {
+    match value {
+        Json::Number(n) => n.as_u64().map(u128::from),
+        Json::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("expected an amount, got {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn mapping() -> Mapping {
+        Mapping {
+            restaurants: [("r-1".to_string(), 1)].into(),
+            foods: [("pizza".to_string(), 1), ("soda".to_string(), 2)].into(),
+            customers: [("c-1".to_string(), "//Charlie".to_string())].into(),
+            delivers: Default::default(),
+        }
+    }
+
+    fn data(requested_time: &str) -> Json {
+        json!({
+            "customerId": "c-1",
+            "order": {
+                "restaurantId": "r-1",
+                "items": [
+                    { "itemId": "pizza" },
+                    { "itemId": "soda", "amount": 2 },
+                ],
+            },
+            "delivery": { "address": "Main Street 1", "requestedTime": requested_time },
+        })
+    }
+
+    #[test]
+    fn order_request_expands_items_into_foods() {
+        let data = data("ASAP");
+        let request = OrderRequest::parse(&mapping(), &data).unwrap();
+        assert_eq!(
+            request,
+            OrderRequest {
+                customer_id: "c-1",
+                restaurant_id: 1,
+                address: "Main Street 1",
+                requested_time: None,
+                foods: vec![1, 2, 2],
+            }
+        );
+    }
+
+    #[test]
+    fn order_request_converts_requested_time_to_millis() {
+        let data = data("2023-11-14T22:13:20Z");
+        let request = OrderRequest::parse(&mapping(), &data).unwrap();
+        assert_eq!(request.requested_time, Some(1_700_000_000_000));
+    }
+
+    #[test]
+    fn order_request_rejects_unknown_ids_and_empty_items() {
+        let mut data = data("ASAP");
+        data["order"]["items"][0]["itemId"] = json!("burger");
+        assert!(OrderRequest::parse(&mapping(), &data).is_err());
+        data["order"]["items"] = json!([]);
+        assert!(OrderRequest::parse(&mapping(), &data).is_err());
+        data["order"]["restaurantId"] = json!("r-2");
+        assert!(OrderRequest::parse(&mapping(), &data).is_err());
+        let mut data = self::data("tomorrow");
+        assert!(OrderRequest::parse(&mapping(), &data).is_err());
+        data["delivery"] = json!({});
+        assert!(OrderRequest::parse(&mapping(), &data).is_err());
+    }
+
+    #[test]
+    fn submit_order_args_follow_message_signature() {
+        let data = data("2023-11-14T22:13:20Z");
+        let request = OrderRequest::parse(&mapping(), &data).unwrap();
+        assert_eq!(
+            request.submit_order_args(2),
+            vec![
+                Value::u128(2),
+                Value::u128(1),
+                Value::unnamed_variant("Plain", [Value::string("Main Street 1")]),
+                Value::unnamed_variant("Plain", [Value::string("")]),
+                Value::unnamed_variant("Some", [Value::u128(1_700_000_000_000)]),
+                Value::u128(0),
+                Value::unnamed_variant("None", []),
+                Value::unnamed_variant("None", []),
+            ]
+        );
+    }
+
+    #[test]
+    fn amount_accepts_numbers_and_strings() {
+        assert_eq!(amount(&json!(100)).unwrap(), 100);
+        assert_eq!(
+            amount(&json!("340282366920938463463374607431768211455")).unwrap(),
+            u128::MAX
+        );
+        assert!(amount(&json!(null)).is_err());
+    }
+}
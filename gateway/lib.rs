@@ -0,0 +1,16 @@
+//! CloudEvents ingestion gateway for the food order contract.
+//!
+//! Incoming CloudEvents are validated against the workflow event definitions
+//! from Requirements.md, their `orderid` correlation attribute is mapped to
+//! on-chain ids and the matching `CustomerService` / `DeliverService` message
+//! is submitted to the node.
+
+pub mod cloudevents;
+pub mod contract;
+pub mod dispatch;
+pub mod mapping;
+
+pub use cloudevents::{CloudEvent, EventDefinition, EVENT_DEFINITIONS};
+pub use contract::ContractClient;
+pub use dispatch::{Dispatcher, Outcome};
+pub use mapping::{Correlations, Mapping};
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use clap::Parser;
+use gateway::{
+    dispatch::GatewayError, CloudEvent, ContractClient, Correlations, Dispatcher, Mapping,
+};
+use indexer::{source::parse_address, ContractMetadata};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+#[command(about = "Accept workflow CloudEvents and submit them to the food order contract")]
+struct Cli {
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+    /// Address of the FoodOrder contract, SS58 or hex.
+    #[arg(long)]
+    contract: String,
+    /// Contract metadata produced by `cargo contract build`.
+    #[arg(long)]
+    metadata: PathBuf,
+    /// JSON file mapping business ids to on-chain ids and signers.
+    #[arg(long)]
+    mapping: PathBuf,
+    /// Where to persist `orderid` correlations.
+    #[arg(long)]
+    correlations: Option<PathBuf>,
+}
+
+async fn ingest(
+    State(dispatcher): State<Arc<Dispatcher>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let structured = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/cloudevents+json"));
+    let event = if structured {
+        serde_json::from_slice::<CloudEvent>(&body).map_err(anyhow::Error::from)
+    } else {
+        CloudEvent::from_binary(
+            headers
+                .iter()
+                .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+            &body,
+        )
+    };
+    let result = match event {
+        Ok(event) => dispatcher.handle(event).await,
+        Err(e) => Err(GatewayError::Invalid(e)),
+    };
+    match result {
+        Ok(outcome) => (StatusCode::ACCEPTED, Json(outcome)).into_response(),
+        Err(error) => {
+            let status = match error {
+                GatewayError::Invalid(_) => StatusCode::BAD_REQUEST,
+                GatewayError::Chain(_) => StatusCode::BAD_GATEWAY,
+            };
+            (
+                status,
+                Json(serde_json::json!({ "error": error.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let metadata = ContractMetadata::load(&cli.metadata)?;
+    let client = ContractClient::connect(&cli.url, parse_address(&cli.contract)?, metadata).await?;
+    let dispatcher = Dispatcher::new(
+        client,
+        Mapping::load(&cli.mapping)?,
+        Correlations::open(cli.correlations)?,
+    );
+    let app = Router::new()
+        .route("/events", post(ingest))
+        .with_state(Arc::new(dispatcher));
+    println!("listening on {}", cli.listen);
+    axum::Server::bind(&cli.listen)
+        .serve(app.into_make_service())
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Static mapping from the business ids used in CloudEvents to on-chain ids
+/// and signing keys. Signers are secret URIs such as `//Alice`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Mapping {
+    #[serde(default)]
+    pub restaurants: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub foods: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub customers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub delivers: BTreeMap<String, String>,
+}
+
+impl Mapping {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("reading mapping {}", path.display()))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn restaurant(&self, id: &str) -> Result<u64> {
+        self.restaurants
+            .get(id)
+            .copied()
+            .ok_or_else(|| anyhow!("unknown restaurant {id}"))
+    }
+
+    pub fn food(&self, id: &str) -> Result<u64> {
+        self.foods
+            .get(id)
+            .copied()
+            .ok_or_else(|| anyhow!("unknown item {id}"))
+    }
+
+    pub fn customer_signer(&self, id: &str) -> Result<&str> {
+        self.customers
+            .get(id)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("unknown customer {id}"))
+    }
+
+    pub fn deliver_signer(&self, id: &str) -> Result<&str> {
+        self.delivers
+            .get(id)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("unknown deliver {id}"))
+    }
+}
+
+/// What the gateway learned when an `orderid` was first submitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Correlation {
+    pub customer_id: String,
+    /// The contract takes one item per order, so one workflow order can map
+    /// to several on-chain orders.
+    pub order_ids: Vec<u64>,
+    /// How many on-chain orders the workflow order asked for, more than
+    /// `order_ids` while a submission is incomplete.
+    #[serde(default)]
+    pub items: usize,
+}
+
+impl Correlation {
+    pub fn is_complete(&self) -> bool {
+        self.order_ids.len() >= self.items
+    }
+}
+
+/// `orderid` correlation values mapped to on-chain order ids, optionally
+/// persisted to a JSON file so the gateway can restart.
+#[derive(Debug, Default)]
+pub struct Correlations {
+    entries: BTreeMap<String, Correlation>,
+    path: Option<PathBuf>,
+}
+
+impl Correlations {
+    pub fn open(path: Option<PathBuf>) -> Result<Self> {
+        let entries = match &path {
+            Some(path) if path.exists() => serde_json::from_str(&fs::read_to_string(path)?)?,
+            _ => BTreeMap::new(),
+        };
+        Ok(Correlations { entries, path })
+    }
+
+    pub fn get(&self, orderid: &str) -> Option<&Correlation> {
+        self.entries.get(orderid)
+    }
+
+    pub fn insert(&mut self, orderid: &str, correlation: Correlation) -> Result<()> {
+        self.entries.insert(orderid.to_string(), correlation);
+        if let Some(path) = &self.path {
+            fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correlations_persist_partial_submissions() {
+        let path = std::env::temp_dir().join(format!("correlations-{}.json", std::process::id()));
+        let mut correlations = Correlations::open(Some(path.clone())).unwrap();
+        let mut correlation = Correlation {
+            customer_id: "c-1".to_string(),
+            order_ids: vec![7],
+            items: 2,
+        };
+        correlations.insert("o-1", correlation.clone()).unwrap();
+
+        let reopened = Correlations::open(Some(path.clone())).unwrap();
+        assert_eq!(reopened.get("o-1"), Some(&correlation));
+        assert!(!reopened.get("o-1").unwrap().is_complete());
+
+        correlation.order_ids.push(8);
+        correlations.insert("o-1", correlation.clone()).unwrap();
+        let reopened = Correlations::open(Some(path.clone())).unwrap();
+        assert!(reopened.get("o-1").unwrap().is_complete());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn correlations_without_items_are_complete() {
+        let correlation: Correlation =
+            serde_json::from_str(r#"{"customer_id": "c-1", "order_ids": [7]}"#).unwrap();
+        assert!(correlation.is_complete());
+    }
+}
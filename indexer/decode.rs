@@ -0,0 +1,143 @@
+use crate::metadata::ContractMetadata;
+use anyhow::{anyhow, bail, Result};
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef, TypeDefPrimitive};
+use scale_value::{Composite, Primitive, Value, ValueDef};
+use serde_json::{Map, Value as Json};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    pub name: String,
+    pub fields: Map<String, Json>,
+}
+
+impl DecodedEvent {
+    pub fn u64(&self, field: &str) -> Option<u64> {
+        self.fields.get(field).and_then(Json::as_u64)
+    }
+
+    pub fn str(&self, field: &str) -> Option<&str> {
+        self.fields.get(field).and_then(Json::as_str)
+    }
+}
+
+/// Decodes the `data` of a `Contracts::ContractEmitted` event. ink! encodes
+/// events as one enum over every event of the contract, so the first byte
+/// selects the event and its fields follow in declaration order.
+pub fn decode_event(metadata: &ContractMetadata, data: &[u8]) -> Result<DecodedEvent> {
+    let (&index, mut input) = data
+        .split_first()
+        .ok_or_else(|| anyhow!("empty event data"))?;
+    let spec = metadata
+        .event(index)
+        .ok_or_else(|| anyhow!("unknown event index {index}"))?;
+    let mut fields = Map::new();
+    for arg in &spec.args {
+        let value = scale_value::scale::decode_as_type(&mut input, arg.type_id, &metadata.registry)
+            .map_err(|e| anyhow!("decoding {}.{}: {e:?}", spec.label, arg.label))?;
+        fields.insert(arg.label.clone(), to_json(&value, &metadata.registry));
+    }
+    if !input.is_empty() {
+        bail!("{} trailing bytes after {}", input.len(), spec.label);
+    }
+    Ok(DecodedEvent {
+        name: spec.label.clone(),
+        fields,
+    })
+}
+
+/// Converts a decoded value into JSON. Byte sequences (account ids, hashes,
+/// `Vec<u8>`) become `0x` hex strings, single-field wrappers are flattened,
+/// `Option` becomes `null` or its inner value and unit enum variants become
+/// their name.
+pub fn to_json(value: &Value<u32>, registry: &PortableRegistry) -> Json {
+    if let Some(bytes) = to_bytes(value, registry) {
+        return Json::String(format!("0x{}", hex::encode(bytes)));
+    }
+    match &value.value {
+        ValueDef::Composite(Composite::Named(fields)) if fields.len() != 1 => Json::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), to_json(value, registry)))
+                .collect(),
+        ),
+        ValueDef::Composite(composite) => match composite.values().collect::<Vec<_>>()[..] {
+            [] => Json::Null,
+            [single] => to_json(single, registry),
+            ref values => Json::Array(values.iter().map(|v| to_json(v, registry)).collect()),
+        },
+        ValueDef::Variant(variant) => {
+            let inner = variant.values.values().collect::<Vec<_>>();
+            match (variant.name.as_str(), &inner[..]) {
+                ("None", []) => Json::Null,
+                ("Some", [single]) => to_json(single, registry),
+                (name, []) => Json::String(name.to_string()),
+                (name, _) => {
+                    let value = Value {
+                        value: ValueDef::Composite(variant.values.clone()),
+                        context: value.context,
+                    };
+                    let mut map = Map::new();
+                    map.insert(name.to_string(), to_json(&value, registry));
+                    Json::Object(map)
+                }
+            }
+        }
+        ValueDef::Primitive(primitive) => primitive_to_json(primitive),
+        ValueDef::BitSequence(bits) => Json::Array(bits.iter().map(Json::Bool).collect()),
+    }
+}
+
+/// Extracts raw bytes from a value decoded from `[u8; N]`, `Vec<u8>` or a
+/// newtype around either (such as `AccountId` or `Hash`).
+pub fn to_bytes(value: &Value<u32>, registry: &PortableRegistry) -> Option<Vec<u8>> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+    let values = composite.values().collect::<Vec<_>>();
+    if let [single] = values[..] {
+        if let Some(bytes) = to_bytes(single, registry) {
+            return Some(bytes);
+        }
+    }
+    if !is_byte_sequence(value.context, registry) {
+        return None;
+    }
+    values
+        .iter()
+        .map(|v| match v.value {
+            ValueDef::Primitive(Primitive::U128(byte)) => u8::try_from(byte).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_byte_sequence(type_id: u32, registry: &PortableRegistry) -> bool {
+    let element = match registry.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Sequence(seq)) => seq.type_param.id,
+        Some(TypeDef::Array(array)) => array.type_param.id,
+        _ => return false,
+    };
+    matches!(
+        registry.resolve(element).map(|ty| &ty.type_def),
+        Some(TypeDef::<PortableForm>::Primitive(TypeDefPrimitive::U8))
+    )
+}
+
+fn primitive_to_json(primitive: &Primitive) -> Json {
+    match primitive {
+        Primitive::Bool(b) => Json::Bool(*b),
+        Primitive::Char(c) => Json::String(c.to_string()),
+        Primitive::String(s) => Json::String(s.clone()),
+        Primitive::U128(n) => match u64::try_from(*n) {
+            Ok(n) => Json::from(n),
+            Err(_) => Json::String(n.to_string()),
+        },
+        Primitive::I128(n) => match i64::try_from(*n) {
+            Ok(n) => Json::from(n),
+            Err(_) => Json::String(n.to_string()),
+        },
+        Primitive::U256(bytes) | Primitive::I256(bytes) => {
+            Json::String(format!("0x{}", hex::encode(bytes)))
+        }
+    }
+}
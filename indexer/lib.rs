@@ -0,0 +1,18 @@
+//! Off-chain indexer for the food order contract.
+//!
+//! Contract events are decoded with the ink! metadata produced by
+//! `cargo contract build` and projected into an SQLite database with
+//! `orders`, `deliveries` and `status_changes` tables, from which the
+//! workflow output document of each order can be rendered.
+
+pub mod decode;
+pub mod metadata;
+pub mod source;
+pub mod store;
+pub mod workflow;
+
+pub use decode::{decode_event, DecodedEvent};
+pub use metadata::ContractMetadata;
+pub use source::RawEvent;
+pub use store::Store;
+pub use workflow::WorkflowOutput;
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use indexer::{decode_event, source, ContractMetadata, RawEvent, Store, WorkflowOutput};
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Parser)]
+#[command(about = "Index food order contract events into SQLite")]
+struct Cli {
+    /// Contract metadata produced by `cargo contract build`, required for
+    /// indexing.
+    #[arg(long)]
+    metadata: Option<PathBuf>,
+    /// SQLite database to write to.
+    #[arg(long, default_value = "food_order.sqlite")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index a recorded event dump (JSON lines of `RawEvent`).
+    Dump { path: PathBuf },
+    /// Index events from a running substrate-contracts-node.
+    Node {
+        #[arg(long, default_value = "ws://127.0.0.1:9944")]
+        url: String,
+        /// Address of the FoodOrder contract, SS58 or hex.
+        #[arg(long)]
+        contract: String,
+        /// First block to index when the database has no cursor yet.
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+        /// Keep polling for new finalized blocks.
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Print the workflow output JSON of an indexed order.
+    Workflow {
+        /// Contract order ids making up the workflow order.
+        #[arg(required = true)]
+        order_ids: Vec<u64>,
+        /// Workflow `orderid` to report, defaults to the first order id.
+        #[arg(long)]
+        orderid: Option<String>,
+    },
+}
+
+fn index(store: &mut Store, metadata: &ContractMetadata, events: &[RawEvent]) -> Result<usize> {
+    let mut indexed = 0;
+    for raw in events {
+        match decode_event(metadata, &raw.data_bytes()?) {
+            Ok(event) => {
+                if store.apply(raw.block_number, raw.event_index, &event)? {
+                    indexed += 1;
+                }
+            }
+            Err(e) => eprintln!(
+                "skipping event {}:{}: {e:#}",
+                raw.block_number, raw.event_index
+            ),
+        }
+    }
+    Ok(indexed)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+    let metadata = || -> Result<ContractMetadata> {
+        ContractMetadata::load(
+            cli.metadata
+                .as_ref()
+                .context("--metadata is required for indexing")?,
+        )
+    };
+    match cli.command {
+        Command::Dump { path } => {
+            let metadata = metadata()?;
+            let events = source::read_dump(&path)?;
+            let indexed = index(&mut store, &metadata, &events)?;
+            println!("indexed {indexed} of {} events", events.len());
+        }
+        Command::Node {
+            url,
+            contract,
+            from_block,
+            follow,
+        } => {
+            let metadata = metadata()?;
+            let node = source::NodeSource::connect(&url, source::parse_address(&contract)?).await?;
+            let mut next = store.cursor()?.map_or(from_block, |block| block + 1);
+            loop {
+                let finalized = node.finalized_number().await?;
+                while next <= finalized {
+                    let events = node.block_events(next).await?;
+                    let indexed = index(&mut store, &metadata, &events)?;
+                    if indexed > 0 {
+                        println!("block {next}: indexed {indexed} events");
+                    }
+                    store.set_cursor(next)?;
+                    next += 1;
+                }
+                if !follow {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_secs(6)).await;
+            }
+        }
+        Command::Workflow { order_ids, orderid } => {
+            let orderid = orderid.unwrap_or_else(|| order_ids[0].to_string());
+            let output = WorkflowOutput::from_store(&store, &orderid, &order_ids)?;
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+    Ok(())
+}
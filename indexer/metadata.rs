@@ -0,0 +1,174 @@
+use anyhow::{anyhow, Context, Result};
+use scale_info::PortableRegistry;
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone)]
+pub struct EventArg {
+    pub label: String,
+    pub type_id: u32,
+    pub indexed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventSpec {
+    pub label: String,
+    pub args: Vec<EventArg>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageArg {
+    pub label: String,
+    pub type_id: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageSpec {
+    /// Trait messages are labelled `Trait::message`, e.g.
+    /// `CustomerService::submit_order`.
+    pub label: String,
+    pub selector: [u8; 4],
+    pub args: Vec<MessageArg>,
+    pub return_type: Option<u32>,
+    pub mutates: bool,
+    pub payable: bool,
+}
+
+/// The parts of the ink! contract metadata needed to decode events and to
+/// encode message calls.
+#[derive(Debug, Clone)]
+pub struct ContractMetadata {
+    pub events: Vec<EventSpec>,
+    pub constructors: Vec<MessageSpec>,
+    pub messages: Vec<MessageSpec>,
+    pub registry: PortableRegistry,
+}
+
+impl ContractMetadata {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("reading metadata {}", path.display()))?;
+        Self::from_json(&json)
+    }
+
+    /// Accepts both the `metadata.json` emitted by cargo-contract and the
+    /// metadata embedded in a `.contract` bundle.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let root: Value = serde_json::from_str(json)?;
+        let root = match root.get("V4").or_else(|| root.get("V3")) {
+            Some(versioned) => versioned.clone(),
+            None => root,
+        };
+        let registry: PortableRegistry =
+            serde_json::from_value(json!({ "types": root["types"].clone() }))
+                .context("decoding type registry")?;
+        let events = root["spec"]["events"]
+            .as_array()
+            .ok_or_else(|| anyhow!("metadata has no spec.events"))?
+            .iter()
+            .map(parse_event)
+            .collect::<Result<Vec<_>>>()?;
+        let constructors = parse_messages(&root["spec"]["constructors"])?;
+        let messages = parse_messages(&root["spec"]["messages"])?;
+        Ok(ContractMetadata {
+            events,
+            constructors,
+            messages,
+            registry,
+        })
+    }
+
+    pub fn event(&self, index: u8) -> Option<&EventSpec> {
+        self.events.get(index as usize)
+    }
+
+    pub fn constructor(&self, label: &str) -> Option<&MessageSpec> {
+        self.constructors
+            .iter()
+            .find(|constructor| constructor.label == label)
+    }
+
+    pub fn message(&self, label: &str) -> Option<&MessageSpec> {
+        self.messages.iter().find(|message| message.label == label)
+    }
+}
+
+fn parse_messages(messages: &Value) -> Result<Vec<MessageSpec>> {
+    messages
+        .as_array()
+        .map(|messages| {
+            messages
+                .iter()
+                .map(parse_message)
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn parse_message(message: &Value) -> Result<MessageSpec> {
+    let label = message["label"]
+        .as_str()
+        .ok_or_else(|| anyhow!("message without label"))?
+        .to_string();
+    let selector = message["selector"]
+        .as_str()
+        .ok_or_else(|| anyhow!("message {label} without selector"))?;
+    let selector: [u8; 4] = hex::decode(selector.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| anyhow!("message {label} has an invalid selector"))?;
+    let args = message["args"]
+        .as_array()
+        .ok_or_else(|| anyhow!("message {label} without args"))?
+        .iter()
+        .map(|arg| {
+            let label = arg["label"]
+                .as_str()
+                .ok_or_else(|| anyhow!("message argument without label"))?
+                .to_string();
+            let type_id = arg["type"]["type"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("message argument {label} without type"))?
+                as u32;
+            Ok(MessageArg { label, type_id })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let return_type = message["returnType"]["type"].as_u64().map(|id| id as u32);
+    Ok(MessageSpec {
+        label,
+        selector,
+        args,
+        return_type,
+        mutates: message["mutates"].as_bool().unwrap_or(false),
+        payable: message["payable"].as_bool().unwrap_or(false),
+    })
+}
+
+fn parse_event(event: &Value) -> Result<EventSpec> {
+    let label = event["label"]
+        .as_str()
+        .ok_or_else(|| anyhow!("event without label"))?
+        .to_string();
+    let args = event["args"]
+        .as_array()
+        .ok_or_else(|| anyhow!("event {label} without args"))?
+        .iter()
+        .map(|arg| {
+            let label = arg["label"]
+                .as_str()
+                .ok_or_else(|| anyhow!("event argument without label"))?
+                .to_string();
+            let type_id = arg["type"]["type"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("event argument {label} without type"))?
+                as u32;
+            let indexed = arg["indexed"].as_bool().unwrap_or(false);
+            Ok(EventArg {
+                label,
+                type_id,
+                indexed,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(EventSpec { label, args })
+}
@@ -0,0 +1,110 @@
+use crate::decode::to_bytes;
+use anyhow::{anyhow, Context, Result};
+use scale_value::Composite;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, str::FromStr};
+use subxt::{utils::AccountId32, OnlineClient, SubstrateConfig};
+
+/// A `Contracts::ContractEmitted` event before decoding. This is also the
+/// line format of recorded event dumps (one JSON object per line).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RawEvent {
+    pub block_number: u64,
+    pub event_index: u32,
+    #[serde(default)]
+    pub contract: Option<String>,
+    pub data: String,
+}
+
+impl RawEvent {
+    pub fn data_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(self.data.trim_start_matches("0x")).context("event data is not hex")
+    }
+}
+
+pub fn read_dump(path: &Path) -> Result<Vec<RawEvent>> {
+    let dump = fs::read_to_string(path)
+        .with_context(|| format!("reading event dump {}", path.display()))?;
+    dump.lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(n, line)| {
+            serde_json::from_str(line).with_context(|| format!("line {} of event dump", n + 1))
+        })
+        .collect()
+}
+
+/// Parses a contract address given either as SS58 or as `0x` hex.
+pub fn parse_address(address: &str) -> Result<[u8; 32]> {
+    if let Some(hex_address) = address.strip_prefix("0x") {
+        let bytes = hex::decode(hex_address)?;
+        return bytes
+            .try_into()
+            .map_err(|_| anyhow!("contract address must be 32 bytes"));
+    }
+    AccountId32::from_str(address)
+        .map(|account| account.0)
+        .map_err(|e| anyhow!("invalid contract address {address}: {e:?}"))
+}
+
+/// Reads contract events block by block from a substrate-contracts-node.
+pub struct NodeSource {
+    client: OnlineClient<SubstrateConfig>,
+    contract: [u8; 32],
+}
+
+impl NodeSource {
+    pub async fn connect(url: &str, contract: [u8; 32]) -> Result<Self> {
+        let client = OnlineClient::<SubstrateConfig>::from_url(url)
+            .await
+            .with_context(|| format!("connecting to {url}"))?;
+        Ok(NodeSource { client, contract })
+    }
+
+    pub async fn finalized_number(&self) -> Result<u64> {
+        let block = self.client.blocks().at_latest().await?;
+        Ok(block.number().into())
+    }
+
+    pub async fn block_events(&self, block_number: u64) -> Result<Vec<RawEvent>> {
+        let hash = self
+            .client
+            .rpc()
+            .block_hash(Some(block_number.into()))
+            .await?
+            .ok_or_else(|| anyhow!("block {block_number} not found"))?;
+        let events = self.client.blocks().at(hash).await?.events().await?;
+        let registry = self.client.metadata();
+        let registry = registry.types();
+        let mut raw_events = Vec::new();
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                continue;
+            }
+            let Composite::Named(fields) = event.field_values()? else {
+                continue;
+            };
+            let mut contract = None;
+            let mut data = None;
+            for (name, value) in fields {
+                match name.as_str() {
+                    "contract" => contract = to_bytes(&value, registry),
+                    "data" => data = to_bytes(&value, registry),
+                    _ => {}
+                }
+            }
+            let contract = contract.ok_or_else(|| anyhow!("ContractEmitted without contract"))?;
+            if contract[..] != self.contract[..] {
+                continue;
+            }
+            raw_events.push(RawEvent {
+                block_number,
+                event_index: event.index(),
+                contract: Some(format!("0x{}", hex::encode(&contract))),
+                data: format!("0x{}", hex::encode(data.unwrap_or_default())),
+            });
+        }
+        Ok(raw_events)
+    }
+}
@@ -0,0 +1,644 @@
+use crate::decode::DecodedEvent;
+use anyhow::{bail, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+pub const ORDER_SUBMITTED: &str = "Order Submitted";
+pub const ORDER_CONFIRMED: &str = "Order ETA Received";
+pub const WAITING_DELIVER: &str = "Waiting Deliver";
+pub const ORDER_PICKED_UP: &str = "Order Picked up";
+pub const DELIVERY_ACCEPTED: &str = "Order Delievered";
+pub const ORDER_DISPUTED: &str = "Order Disputed";
+pub const ORDER_REFUNDED: &str = "Order Refunded";
+pub const ORDER_REJECTED: &str = "Order Rejected";
+pub const ORDER_CANCELLED: &str = "Order Cancelled";
+pub const ORDER_SETTLED: &str = "Order Charged";
+
+/// Newest contract `EVENT_SCHEMA_VERSION` whose events this indexer
+/// understands.
+pub const SUPPORTED_EVENT_SCHEMA: u64 = 16;
+
+/// Contract events that change no order or delivery row. They are only
+/// kept in the `events` table.
+const RECORD_ONLY_EVENTS: &[&str] = &[
+    "AddFoodEvent",
+    "UpdateFoodEvent",
+    "UpdateFoodAvailabilityEvent",
+    "UpdateFoodCurrencyEvent",
+    "RemoveFoodEvent",
+    "UpdateOpeningHoursEvent",
+    "UpdateClosuresEvent",
+    "AddDeliverEvent",
+    "AddRestaurantEvent",
+    "RespondDisputeEvent",
+    "SetCommissionEvent",
+    "WithdrawTreasuryEvent",
+    "CreditBalanceEvent",
+    "WithdrawEvent",
+    "AddCustomerEvent",
+    "ChangeManagerEvent",
+    "SetDeliveryFeeEvent",
+    "SetWorkflowContractsEvent",
+    "SetArbiterEvent",
+    "UpdateRestaurantEncryptionKeyEvent",
+    "UpdateDeliverEncryptionKeyEvent",
+    "UpdateDeliverAttestationKeyEvent",
+    "PostBondEvent",
+    "DeregisterDeliverEvent",
+    "WithdrawBondEvent",
+    "SlashBondEvent",
+    "SetBondPolicyEvent",
+    "AddPaymentProcessorEvent",
+    "SetProcessorFeeRateEvent",
+    "SetCaptureTimeoutEvent",
+    "AuthorizeChargeEvent",
+    "SelectPaymentProcessorEvent",
+    "PostExchangeRateEvent",
+    "SetOracleEvent",
+    "SetMaxRateAgeEvent",
+    "CreateScheduleEvent",
+    "TriggerScheduleEvent",
+    "CloseScheduleEvent",
+    "OpenGroupOrderEvent",
+    "AddGroupItemEvent",
+    "FundGroupShareEvent",
+    "SubmitGroupOrderEvent",
+    "CancelGroupOrderEvent",
+    "SetLoyaltyRateEvent",
+    "SetLoyaltyMultiplierEvent",
+    "AddPromoCodeEvent",
+    "RevokePromoCodeEvent",
+    "RedeemPromoCodeEvent",
+    "SetCodeEvent",
+];
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    block_number INTEGER NOT NULL,
+    event_index INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    PRIMARY KEY (block_number, event_index)
+);
+CREATE TABLE IF NOT EXISTS orders (
+    order_id INTEGER PRIMARY KEY,
+    food_id INTEGER,
+    restaurant_id INTEGER,
+    customer_id INTEGER,
+    delivery_id INTEGER,
+    deliver_id INTEGER,
+    delivery_address TEXT,
+    eta INTEGER,
+    status TEXT NOT NULL,
+    created_block INTEGER NOT NULL,
+    updated_block INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS deliveries (
+    delivery_id INTEGER PRIMARY KEY,
+    order_id INTEGER NOT NULL,
+    deliver_id INTEGER,
+    status TEXT NOT NULL,
+    created_block INTEGER NOT NULL,
+    updated_block INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS status_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    order_id INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    event TEXT NOT NULL,
+    block_number INTEGER NOT NULL,
+    event_index INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS status_changes_order ON status_changes (order_id);
+CREATE TABLE IF NOT EXISTS event_schema (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    version INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cursor (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    block_number INTEGER NOT NULL
+);
+";
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// The last block whose events were fully indexed.
+    pub fn cursor(&self) -> Result<Option<u64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT block_number FROM cursor WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    pub fn set_cursor(&self, block_number: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO cursor (id, block_number) VALUES (0, ?1)
+             ON CONFLICT(id) DO UPDATE SET block_number = excluded.block_number",
+            params![block_number],
+        )?;
+        Ok(())
+    }
+
+    /// The event schema version announced by the contract, if seen yet.
+    pub fn event_schema(&self) -> Result<Option<u64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT version FROM event_schema WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Records an event and updates the projections. Events already seen at
+    /// the same position are ignored, so replaying a dump is idempotent.
+    /// Returns whether the event was new.
+    pub fn apply(
+        &mut self,
+        block_number: u64,
+        event_index: u32,
+        event: &DecodedEvent,
+    ) -> Result<bool> {
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO events (block_number, event_index, name, payload) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                block_number,
+                event_index,
+                event.name,
+                serde_json::to_string(&event.fields)?
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+        let at = Position {
+            block_number,
+            event_index,
+            event: &event.name,
+        };
+        match event.name.as_str() {
+            "SubmitOrderEvent" => {
+                let order_id = event.u64("order_id");
+                tx.execute(
+                    "INSERT OR REPLACE INTO orders (order_id, food_id, restaurant_id, customer_id, delivery_address, status, created_block, updated_block)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+                    params![
+                        order_id,
+                        event.u64("food_id"),
+                        event.u64("restaurant_id"),
+                        event.u64("customer_id"),
+                        event.fields.get("delivery_address").map(|v| v.to_string()),
+                        ORDER_SUBMITTED,
+                        block_number
+                    ],
+                )?;
+                record_status(&tx, order_id, ORDER_SUBMITTED, &at)?;
+            }
+            "ConfirmOrderEvent" => {
+                let order_id = event.u64("order_id");
+                tx.execute(
+                    "UPDATE orders SET eta = ?2 WHERE order_id = ?1",
+                    params![order_id, event.u64("eta")],
+                )?;
+                set_order_status(&tx, order_id, ORDER_CONFIRMED, &at)?;
+            }
+            "DeliverOrderEvent" => {
+                let order_id = event.u64("order_id");
+                let delivery_id = event.u64("delivery_id");
+                tx.execute(
+                    "INSERT OR IGNORE INTO deliveries (delivery_id, order_id, status, created_block, updated_block)
+                     VALUES (?1, ?2, 'Waiting', ?3, ?3)",
+                    params![delivery_id, order_id, block_number],
+                )?;
+                tx.execute(
+                    "UPDATE orders SET delivery_id = ?2, delivery_address = ?3 WHERE order_id = ?1",
+                    params![
+                        order_id,
+                        delivery_id,
+                        event.fields.get("delivery_address").map(|v| v.to_string())
+                    ],
+                )?;
+                set_order_status(&tx, order_id, WAITING_DELIVER, &at)?;
+            }
+            "PickupDeliveryEvent" => {
+                let order_id = event.u64("order_id");
+                let deliver_id = event.u64("deliver_id");
+                tx.execute(
+                    "UPDATE deliveries SET deliver_id = ?2, status = 'PickUp', updated_block = ?3 WHERE delivery_id = ?1",
+                    params![event.u64("delivery_id"), deliver_id, block_number],
+                )?;
+                tx.execute(
+                    "UPDATE orders SET deliver_id = ?2 WHERE order_id = ?1",
+                    params![order_id, deliver_id],
+                )?;
+                set_order_status(&tx, order_id, ORDER_PICKED_UP, &at)?;
+            }
+            "ConfirmDeliveryEvent" => {
+                set_order_status(&tx, event.u64("order_id"), DELIVERY_ACCEPTED, &at)?;
+            }
+            "HandoffDeliveryEvent" => {
+                tx.execute(
+                    "UPDATE deliveries SET status = 'HandedOff', updated_block = ?2 WHERE delivery_id = ?1",
+                    params![event.u64("delivery_id"), block_number],
+                )?;
+                set_order_status(&tx, event.u64("order_id"), DELIVERY_ACCEPTED, &at)?;
+            }
+            "DropOffDeliveryEvent" => {
+                tx.execute(
+                    "UPDATE deliveries SET status = 'DroppedOff', updated_block = ?2 WHERE delivery_id = ?1",
+                    params![event.u64("delivery_id"), block_number],
+                )?;
+            }
+            "TimeOutDeliveryEvent" => {
+                tx.execute(
+                    "UPDATE deliveries SET status = 'TimedOut', updated_block = ?2 WHERE delivery_id = ?1",
+                    params![event.u64("delivery_id"), block_number],
+                )?;
+                set_order_status(&tx, event.u64("order_id"), ORDER_REFUNDED, &at)?;
+            }
+            "OpenDisputeEvent" => {
+                set_order_status(&tx, event.u64("order_id"), ORDER_DISPUTED, &at)?;
+            }
+            "ResolveDisputeEvent" => {
+                let status = match event.str("resolution") {
+                    Some("Release") => DELIVERY_ACCEPTED,
+                    _ => ORDER_REFUNDED,
+                };
+                let order_id = event.u64("order_id");
+                tx.execute(
+                    "UPDATE deliveries SET status = 'Cancelled', updated_block = ?2 WHERE order_id = ?1 AND status = 'Waiting'",
+                    params![order_id, block_number],
+                )?;
+                set_order_status(&tx, order_id, status, &at)?;
+            }
+            "RejectOrderEvent" => {
+                set_order_status(&tx, event.u64("order_id"), ORDER_REJECTED, &at)?;
+            }
+            "CancelOrderEvent" => {
+                set_order_status(&tx, event.u64("order_id"), ORDER_CANCELLED, &at)?;
+            }
+            "SettleOrderEvent" => {
+                // A settlement that refunds everything charged nobody. One
+                // that holds the restaurant share for a payment processor
+                // charges when the processor captures or the charge is voided.
+                let charged = ["restaurant_amount", "deliver_amount", "platform_amount"]
+                    .iter()
+                    .any(|field| event.u64(field) != Some(0));
+                let held = event.u64("held_amount").unwrap_or(0) != 0;
+                if charged && !held {
+                    record_status(&tx, event.u64("order_id"), ORDER_SETTLED, &at)?;
+                }
+            }
+            "CaptureChargeEvent" => {
+                let order_id = event.u64("order_id");
+                tx.execute(
+                    "UPDATE orders SET status = ?2, updated_block = ?3 WHERE order_id = ?1",
+                    params![order_id, ORDER_SETTLED, block_number],
+                )?;
+                record_status(&tx, order_id, ORDER_SETTLED, &at)?;
+            }
+            "VoidChargeEvent" => {
+                record_status(&tx, event.u64("order_id"), ORDER_SETTLED, &at)?;
+            }
+            "EventSchemaEvent" => {
+                if let Some(version) = event.u64("version") {
+                    if version > SUPPORTED_EVENT_SCHEMA {
+                        bail!(
+                            "contract emits event schema {version} but this indexer supports up to {SUPPORTED_EVENT_SCHEMA}"
+                        );
+                    }
+                    tx.execute(
+                        "INSERT INTO event_schema (id, version) VALUES (0, ?1)
+                         ON CONFLICT(id) DO UPDATE SET version = excluded.version",
+                        params![version],
+                    )?;
+                }
+            }
+            name if RECORD_ONLY_EVENTS.contains(&name) => {}
+            name => {
+                eprintln!(
+                    "unknown event {name} at {block_number}:{event_index} recorded without projection"
+                );
+            }
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Whether any event for the order has been indexed.
+    pub fn has_order(&self, order_id: u64) -> Result<bool> {
+        let found = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM status_changes WHERE order_id = ?1 LIMIT 1",
+                params![order_id],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    /// The ordered status history of an order.
+    pub fn order_history(&self, order_id: u64) -> Result<Vec<String>> {
+        let mut statement = self.conn.prepare(
+            "SELECT status FROM status_changes WHERE order_id = ?1 ORDER BY block_number, event_index, id",
+        )?;
+        let statuses = statement
+            .query_map(params![order_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(statuses)
+    }
+}
+
+struct Position<'a> {
+    block_number: u64,
+    event_index: u32,
+    event: &'a str,
+}
+
+fn record_status(
+    conn: &Connection,
+    order_id: Option<u64>,
+    status: &str,
+    at: &Position,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO status_changes (order_id, status, event, block_number, event_index) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![order_id, status, at.event, at.block_number, at.event_index],
+    )?;
+    Ok(())
+}
+
+fn set_order_status(
+    conn: &Connection,
+    order_id: Option<u64>,
+    status: &str,
+    at: &Position,
+) -> Result<()> {
+    let current: Option<String> = conn
+        .query_row(
+            "SELECT status FROM orders WHERE order_id = ?1",
+            params![order_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if current.as_deref() == Some(status) {
+        return Ok(());
+    }
+    conn.execute(
+        "UPDATE orders SET status = ?2, updated_block = ?3 WHERE order_id = ?1",
+        params![order_id, status, at.block_number],
+    )?;
+    record_status(conn, order_id, status, at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_event, ContractMetadata};
+    use serde_json::json;
+
+    /// The events of a delivered and charged order, trimmed down to the
+    /// fields the projections read. Type 0 is `u64`, 1 is `u128` and 2 is
+    /// `Vec<u8>`.
+    fn metadata() -> ContractMetadata {
+        let event = |label: &str, args: &[(&str, u32)]| {
+            json!({
+                "label": label,
+                "args": args
+                    .iter()
+                    .map(|(label, ty)| json!({ "label": label, "type": { "type": ty }, "indexed": false }))
+                    .collect::<Vec<_>>(),
+            })
+        };
+        let metadata = json!({
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u64" } } },
+                { "id": 1, "type": { "def": { "primitive": "u128" } } },
+                { "id": 2, "type": { "def": { "sequence": { "type": 3 } } } },
+                { "id": 3, "type": { "def": { "primitive": "u8" } } },
+            ],
+            "spec": {
+                "events": [
+                    event(
+                        "SubmitOrderEvent",
+                        &[
+                            ("order_id", 0),
+                            ("food_id", 0),
+                            ("restaurant_id", 0),
+                            ("customer_id", 0),
+                            ("delivery_address", 2),
+                            ("phone_number", 2),
+                        ],
+                    ),
+                    event("ConfirmOrderEvent", &[("order_id", 0), ("eta", 0)]),
+                    event(
+                        "DeliverOrderEvent",
+                        &[
+                            ("order_id", 0),
+                            ("delivery_id", 0),
+                            ("restaurant_id", 0),
+                            ("customer_id", 0),
+                            ("delivery_address", 2),
+                        ],
+                    ),
+                    event(
+                        "PickupDeliveryEvent",
+                        &[("delivery_id", 0), ("order_id", 0), ("deliver_id", 0)],
+                    ),
+                    event(
+                        "HandoffDeliveryEvent",
+                        &[("delivery_id", 0), ("order_id", 0), ("deliver_id", 0)],
+                    ),
+                    event(
+                        "SettleOrderEvent",
+                        &[
+                            ("order_id", 0),
+                            ("refund", 1),
+                            ("restaurant_amount", 1),
+                            ("deliver_amount", 1),
+                            ("held_amount", 1),
+                            ("platform_amount", 1),
+                        ],
+                    ),
+                    event(
+                        "CaptureChargeEvent",
+                        &[
+                            ("order_id", 0),
+                            ("processor_id", 0),
+                            ("restaurant_amount", 1),
+                            ("processor_amount", 1),
+                        ],
+                    ),
+                ],
+            },
+        });
+        ContractMetadata::from_json(&metadata.to_string()).unwrap()
+    }
+
+    enum Arg<'a> {
+        U64(u64),
+        U128(u128),
+        Bytes(&'a [u8]),
+    }
+
+    fn encode(index: u8, args: &[Arg]) -> Vec<u8> {
+        let mut data = vec![index];
+        for arg in args {
+            match arg {
+                Arg::U64(n) => data.extend(n.to_le_bytes()),
+                Arg::U128(n) => data.extend(n.to_le_bytes()),
+                Arg::Bytes(bytes) => {
+                    // Single-byte compact length, enough for short fixtures.
+                    data.push((bytes.len() as u8) << 2);
+                    data.extend(*bytes);
+                }
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn projects_a_delivered_and_charged_order() {
+        use Arg::*;
+        let metadata = metadata();
+        let events = [
+            encode(
+                0,
+                &[
+                    U64(7),
+                    U64(3),
+                    U64(1),
+                    U64(2),
+                    Bytes(&[0xaa]),
+                    Bytes(&[0xbb]),
+                ],
+            ),
+            encode(1, &[U64(7), U64(1_800_000)]),
+            encode(2, &[U64(7), U64(4), U64(1), U64(2), Bytes(&[0xcc])]),
+            encode(3, &[U64(4), U64(7), U64(9)]),
+            encode(4, &[U64(4), U64(7), U64(9)]),
+            encode(5, &[U64(7), U128(0), U128(0), U128(8), U128(90), U128(7)]),
+            encode(6, &[U64(7), U64(1), U128(85), U128(5)]),
+        ];
+        let mut store = Store::in_memory().unwrap();
+        for (block, data) in events.iter().enumerate() {
+            let event = decode_event(&metadata, data).unwrap();
+            assert!(store.apply(block as u64 + 1, 0, &event).unwrap());
+        }
+
+        let order = store
+            .connection()
+            .query_row(
+                "SELECT food_id, restaurant_id, customer_id, delivery_id, deliver_id, delivery_address, eta, status, created_block, updated_block
+                 FROM orders WHERE order_id = 7",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, u64>(0)?,
+                        row.get::<_, u64>(1)?,
+                        row.get::<_, u64>(2)?,
+                        row.get::<_, u64>(3)?,
+                        row.get::<_, u64>(4)?,
+                        row.get::<_, String>(5)?,
+                        row.get::<_, u64>(6)?,
+                        row.get::<_, String>(7)?,
+                        row.get::<_, u64>(8)?,
+                        row.get::<_, u64>(9)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            order,
+            (
+                3,
+                1,
+                2,
+                4,
+                9,
+                "\"0xcc\"".to_string(),
+                1_800_000,
+                ORDER_SETTLED.to_string(),
+                1,
+                7
+            )
+        );
+
+        let delivery = store
+            .connection()
+            .query_row(
+                "SELECT order_id, deliver_id, status, created_block, updated_block FROM deliveries WHERE delivery_id = 4",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, u64>(0)?,
+                        row.get::<_, u64>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, u64>(3)?,
+                        row.get::<_, u64>(4)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(delivery, (7, 9, "HandedOff".to_string(), 3, 5));
+
+        assert_eq!(
+            store.order_history(7).unwrap(),
+            [
+                ORDER_SUBMITTED,
+                ORDER_CONFIRMED,
+                WAITING_DELIVER,
+                ORDER_PICKED_UP,
+                DELIVERY_ACCEPTED,
+                ORDER_SETTLED,
+            ]
+        );
+    }
+
+    #[test]
+    fn replaying_an_event_is_ignored() {
+        let metadata = metadata();
+        let event = decode_event(
+            &metadata,
+            &encode(
+                0,
+                &[
+                    Arg::U64(7),
+                    Arg::U64(3),
+                    Arg::U64(1),
+                    Arg::U64(2),
+                    Arg::Bytes(&[]),
+                    Arg::Bytes(&[]),
+                ],
+            ),
+        )
+        .unwrap();
+        let mut store = Store::in_memory().unwrap();
+        assert!(store.apply(1, 0, &event).unwrap());
+        assert!(!store.apply(1, 0, &event).unwrap());
+        assert_eq!(store.order_history(7).unwrap(), [ORDER_SUBMITTED]);
+    }
+}
@@ -0,0 +1,291 @@
+//! Renders the workflow output document of Requirements.md from the status
+//! history recorded by the store:
+//!
+//! ```json
+//! { "orderid": "ORDER-100", "orderstatus": ["Order Submitted", "...", "Order Completed"] }
+//! ```
+
+use crate::store::{Store, DELIVERY_ACCEPTED, ORDER_SETTLED, WAITING_DELIVER};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+pub const ORDER_COMPLETED: &str = "Order Completed";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowOutput {
+    pub orderid: String,
+    pub orderstatus: Vec<String>,
+}
+
+impl WorkflowOutput {
+    /// Builds the output for `orderid` from the on-chain history of one or
+    /// more contract orders (a workflow order may span several contract
+    /// orders, one per item).
+    pub fn from_store(store: &Store, orderid: &str, order_ids: &[u64]) -> Result<Self> {
+        let mut orderstatus: Vec<String> = Vec::new();
+        for &order_id in order_ids {
+            if !store.has_order(order_id)? {
+                bail!("order {order_id} has not been indexed");
+            }
+            for status in workflow_statuses(&store.order_history(order_id)?) {
+                if !orderstatus.contains(&status) {
+                    orderstatus.push(status);
+                }
+            }
+        }
+        Ok(WorkflowOutput {
+            orderid: orderid.to_string(),
+            orderstatus,
+        })
+    }
+}
+
+/// Maps a contract status history onto workflow states.
+///
+/// The contract settles an order before emitting the event that closes it,
+/// so the charge is moved after the closing state. `Waiting Deliver` is an
+/// internal hand-over state the workflow does not report. An order that was
+/// delivered and charged is completed; rejected, cancelled and refunded
+/// orders end in their own state.
+pub fn workflow_statuses(history: &[String]) -> Vec<String> {
+    let mut statuses: Vec<String> = Vec::new();
+    let mut charged = false;
+    for status in history {
+        match status.as_str() {
+            WAITING_DELIVER => {}
+            ORDER_SETTLED => charged = true,
+            _ => {
+                if statuses.last() != Some(status) {
+                    statuses.push(status.clone());
+                }
+                if charged {
+                    statuses.push(ORDER_SETTLED.to_string());
+                    charged = false;
+                }
+            }
+        }
+    }
+    if charged {
+        statuses.push(ORDER_SETTLED.to_string());
+    }
+    let closed = statuses
+        .iter()
+        .rev()
+        .find(|status| status.as_str() != ORDER_SETTLED)
+        .map(String::as_str);
+    let settled = statuses.iter().any(|status| status == ORDER_SETTLED);
+    if settled && closed == Some(DELIVERY_ACCEPTED) {
+        statuses.push(ORDER_COMPLETED.to_string());
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::DecodedEvent;
+    use crate::store::{
+        ORDER_CANCELLED, ORDER_CONFIRMED, ORDER_DISPUTED, ORDER_PICKED_UP, ORDER_REFUNDED,
+        ORDER_REJECTED, ORDER_SUBMITTED,
+    };
+    use serde_json::{json, Value};
+
+    const ORDER: u64 = 7;
+    const DELIVERY: u64 = 4;
+
+    fn event(name: &str, fields: Value) -> DecodedEvent {
+        DecodedEvent {
+            name: name.to_string(),
+            fields: fields.as_object().unwrap().clone(),
+        }
+    }
+
+    fn submitted() -> Vec<DecodedEvent> {
+        vec![event(
+            "SubmitOrderEvent",
+            json!({ "order_id": ORDER, "food_id": 3, "restaurant_id": 1, "customer_id": 2 }),
+        )]
+    }
+
+    fn picked_up() -> Vec<DecodedEvent> {
+        let mut events = submitted();
+        events.extend([
+            event(
+                "ConfirmOrderEvent",
+                json!({ "order_id": ORDER, "eta": 1_800_000 }),
+            ),
+            event(
+                "DeliverOrderEvent",
+                json!({ "order_id": ORDER, "delivery_id": DELIVERY }),
+            ),
+            event(
+                "PickupDeliveryEvent",
+                json!({ "order_id": ORDER, "delivery_id": DELIVERY, "deliver_id": 9 }),
+            ),
+        ]);
+        events
+    }
+
+    fn settled(refund: u64, restaurant_amount: u64) -> DecodedEvent {
+        settled_holding(refund, restaurant_amount, 0)
+    }
+
+    fn settled_holding(refund: u64, restaurant_amount: u64, held_amount: u64) -> DecodedEvent {
+        event(
+            "SettleOrderEvent",
+            json!({
+                "order_id": ORDER,
+                "refund": refund,
+                "restaurant_amount": restaurant_amount,
+                "deliver_amount": 0,
+                "held_amount": held_amount,
+                "platform_amount": 0,
+            }),
+        )
+    }
+
+    fn statuses(events: &[DecodedEvent]) -> Vec<String> {
+        let mut store = Store::in_memory().unwrap();
+        for (block, event) in events.iter().enumerate() {
+            store.apply(block as u64 + 1, 0, event).unwrap();
+        }
+        WorkflowOutput::from_store(&store, "ORDER-100", &[ORDER])
+            .unwrap()
+            .orderstatus
+    }
+
+    #[test]
+    fn delivered_and_charged_order_completes() {
+        let mut events = picked_up();
+        events.extend([
+            settled(0, 100),
+            event("ConfirmDeliveryEvent", json!({ "order_id": ORDER })),
+        ]);
+        assert_eq!(
+            statuses(&events),
+            [
+                ORDER_SUBMITTED,
+                ORDER_CONFIRMED,
+                ORDER_PICKED_UP,
+                DELIVERY_ACCEPTED,
+                ORDER_SETTLED,
+                ORDER_COMPLETED,
+            ]
+        );
+    }
+
+    #[test]
+    fn order_held_for_capture_is_charged_on_capture() {
+        let mut events = picked_up();
+        events.extend([
+            settled_holding(0, 0, 90),
+            event("ConfirmDeliveryEvent", json!({ "order_id": ORDER })),
+        ]);
+        assert_eq!(
+            statuses(&events),
+            [
+                ORDER_SUBMITTED,
+                ORDER_CONFIRMED,
+                ORDER_PICKED_UP,
+                DELIVERY_ACCEPTED,
+            ]
+        );
+        events.push(event(
+            "CaptureChargeEvent",
+            json!({ "order_id": ORDER, "processor_id": 1, "restaurant_amount": 85, "processor_amount": 5 }),
+        ));
+        assert_eq!(
+            statuses(&events),
+            [
+                ORDER_SUBMITTED,
+                ORDER_CONFIRMED,
+                ORDER_PICKED_UP,
+                DELIVERY_ACCEPTED,
+                ORDER_SETTLED,
+                ORDER_COMPLETED,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejected_order_ends_rejected() {
+        let mut events = submitted();
+        events.extend([
+            settled(110, 0),
+            event("RejectOrderEvent", json!({ "order_id": ORDER })),
+        ]);
+        assert_eq!(statuses(&events), [ORDER_SUBMITTED, ORDER_REJECTED]);
+    }
+
+    #[test]
+    fn cancelled_order_ends_cancelled() {
+        let mut events = submitted();
+        events.extend([
+            event(
+                "ConfirmOrderEvent",
+                json!({ "order_id": ORDER, "eta": 1_800_000 }),
+            ),
+            settled(110, 0),
+            event("CancelOrderEvent", json!({ "order_id": ORDER })),
+        ]);
+        assert_eq!(
+            statuses(&events),
+            [ORDER_SUBMITTED, ORDER_CONFIRMED, ORDER_CANCELLED]
+        );
+    }
+
+    #[test]
+    fn disputed_order_refunded_in_part_is_charged_but_not_completed() {
+        let mut events = picked_up();
+        events.extend([
+            event("OpenDisputeEvent", json!({ "order_id": ORDER })),
+            settled(60, 50),
+            event(
+                "ResolveDisputeEvent",
+                json!({ "order_id": ORDER, "resolution": { "PartialRefund": 60 } }),
+            ),
+        ]);
+        assert_eq!(
+            statuses(&events),
+            [
+                ORDER_SUBMITTED,
+                ORDER_CONFIRMED,
+                ORDER_PICKED_UP,
+                ORDER_DISPUTED,
+                ORDER_REFUNDED,
+                ORDER_SETTLED,
+            ]
+        );
+    }
+
+    #[test]
+    fn disputed_order_released_completes() {
+        let mut events = picked_up();
+        events.extend([
+            event("OpenDisputeEvent", json!({ "order_id": ORDER })),
+            settled(0, 100),
+            event(
+                "ResolveDisputeEvent",
+                json!({ "order_id": ORDER, "resolution": "Release" }),
+            ),
+        ]);
+        assert_eq!(
+            statuses(&events),
+            [
+                ORDER_SUBMITTED,
+                ORDER_CONFIRMED,
+                ORDER_PICKED_UP,
+                ORDER_DISPUTED,
+                DELIVERY_ACCEPTED,
+                ORDER_SETTLED,
+                ORDER_COMPLETED,
+            ]
+        );
+    }
+
+    #[test]
+    fn unindexed_order_fails() {
+        let store = Store::in_memory().unwrap();
+        assert!(WorkflowOutput::from_store(&store, "ORDER-100", &[ORDER]).is_err());
+    }
+}
@@ -1,28 +1,63 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![feature(min_specialization)]
 
+pub use self::contract::{FoodOrder, FoodOrderRef};
+
 #[openbrush::contract]
 
-mod contract {
+pub mod contract {
     use ink::codegen::EmitEvent;
     use ink::codegen::Env;
-    use ink::prelude::string::String;
+    use ink::prelude::{
+        string::String,
+        vec::Vec,
+    };
     use logics::{
         impls::{
+            BalanceService::BalanceServiceEvents,
+            Bonding::BondingEvents,
+            BondService::BondServiceEvents,
             CustomerService::CustomerServiceEvents,
             DeliverService::DeliverServiceEvents,
+            DeliveryManagerService::DeliveryManagerServiceEvents,
+            DisputeService::DisputeServiceEvents,
+            GroupOrderService::GroupOrderServiceEvents,
+            Loyalty::Loyalty,
+            LoyaltyService::LoyaltyServiceEvents,
             ManagerService::ManagerServiceEvents,
+            OracleService::OracleServiceEvents,
+            OrderingManagerService::OrderingManagerServiceEvents,
+            PaymentManagerService::PaymentManagerServiceEvents,
+            PaymentService::PaymentServiceEvents,
+            PromoService::PromoServiceEvents,
             RestaurantService::RestaurantServiceEvents,
-            types::{Data, OrderId, FoodId, RestaurantId, CustomerId, DeliverId, DeliveryId},
+            ScheduleService::ScheduleServiceEvents,
+            Settlement::SettlementEvents,
+            types::{Data, DeliveryData, OrderingData, PaymentData, EVENT_SCHEMA_VERSION, OrderId, FoodId, RestaurantId, CustomerId, DeliverId, DeliveryId, DisputeId, DisputeResolution, OpeningSlot, Closure, PersonalData, PublicKey, PaymentProcessorId, CurrencyCode, ScheduleId, GroupId, DropOffAttestation, EcdsaAddress, BondPolicy, MarketConfig, BASIS_POINTS},
         },
         traits::{
+            BalanceService::BalanceService,
+            BondService::BondService,
             CustomerService::CustomerService,
             DeliverService::DeliverService,
+            DeliveryManagerService::DeliveryManagerService,
+            DisputeService::DisputeService,
             Get::Get,
+            GetDelivery::GetDelivery,
+            GetPayment::GetPayment,
+            GroupOrderService::GroupOrderService,
+            LoyaltyService::LoyaltyService,
             ManagerService::ManagerService,
+            OracleService::OracleService,
+            OrderingManagerService::OrderingManagerService,
+            PaymentManagerService::PaymentManagerService,
+            PaymentService::PaymentService,
+            PromoService::PromoService,
             RestaurantService::RestaurantService,
+            ScheduleService::ScheduleService,
         },
     };
+    use openbrush::contracts::psp22::{self, PSP22};
     use openbrush::traits::Storage;
 
     #[ink(storage)]
@@ -30,49 +65,138 @@ mod contract {
     pub struct FoodOrder {
         #[storage_field]
         food_order_data: Data,
+        #[storage_field]
+        ordering_data: OrderingData,
+        #[storage_field]
+        delivery_data: DeliveryData,
+        #[storage_field]
+        payment_data: PaymentData,
+        // Balances of the loyalty points token.
+        #[storage_field]
+        psp22: psp22::Data,
     }
 
+    impl BalanceService for FoodOrder {}
+
+    impl BondService for FoodOrder {}
+
     impl CustomerService for FoodOrder {}
 
     impl DeliverService for FoodOrder {}
 
+    impl DeliveryManagerService for FoodOrder {}
+
+    impl DisputeService for FoodOrder {}
+
     impl Get for FoodOrder {}
 
+    impl GetDelivery for FoodOrder {}
+
+    impl GetPayment for FoodOrder {}
+
+    impl GroupOrderService for FoodOrder {}
+
+    impl LoyaltyService for FoodOrder {}
+
     impl ManagerService for FoodOrder {}
+
+    impl OracleService for FoodOrder {}
+
+    impl OrderingManagerService for FoodOrder {}
+
+    impl PaymentManagerService for FoodOrder {}
+
+    impl PaymentService for FoodOrder {}
+
+    impl PromoService for FoodOrder {}
+
+    impl PSP22 for FoodOrder {}
     
     impl RestaurantService for FoodOrder {}
 
+    impl ScheduleService for FoodOrder {}
+
     impl FoodOrder {
         #[ink(constructor, payable)]
         pub fn new() -> Self {
             let mut instance = Self::default();
-            instance.manager = Self::env().caller();
+            instance.food_order_data.manager = Self::env().caller();
+            instance.food_order_data.upgrader = Self::env().caller();
+            Self::env().emit_event(EventSchemaEvent {
+                version: EVENT_SCHEMA_VERSION,
+            });
+            instance
+        }
+
+        /// Creates the market of a city on behalf of `manager`. The caller,
+        /// usually the factory, stays the upgrader of the contract code.
+        #[ink(constructor, payable)]
+        pub fn new_market(manager: AccountId, config: MarketConfig) -> Self {
+            assert!(config.food_commission as u128 <= BASIS_POINTS && config.delivery_commission as u128 <= BASIS_POINTS, "Commission exceeds 100%!");
+            assert!(config.loyalty_rate as u128 <= BASIS_POINTS, "Loyalty rate exceeds 100%!");
+            let mut instance = Self::default();
+            instance.food_order_data.manager = manager;
+            instance.food_order_data.upgrader = Self::env().caller();
+            instance.food_order_data.delivery_fee = config.delivery_fee;
+            instance.food_order_data.food_commission = config.food_commission;
+            instance.food_order_data.delivery_commission = config.delivery_commission;
+            instance.ordering_data.loyalty_rate = config.loyalty_rate;
+            Self::env().emit_event(EventSchemaEvent {
+                version: EVENT_SCHEMA_VERSION,
+            });
             instance
         }
+
+        /// Replaces the contract code while keeping its storage.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) {
+            assert!(self.env().caller() == self.food_order_data.upgrader, "Only upgrader can set code!");
+            assert!(self.env().set_code_hash(&code_hash).is_ok(), "Code hash does not exist!");
+            self.env().emit_event(SetCodeEvent {
+                code_hash,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_upgrader(&self) -> AccountId {
+            self.food_order_data.upgrader
+        }
     }
 
     #[ink(event)]
     pub struct SubmitOrderEvent {
+        #[ink(topic)]
         order_id: OrderId,
         food_id: FoodId,
+        #[ink(topic)]
         restaurant_id: RestaurantId,
+        #[ink(topic)]
         customer_id: CustomerId,
-        delivery_address: String,
-        phone_number: String,
+        delivery_address: PersonalData,
+        phone_number: PersonalData,
     }
 
     #[ink(event)]
     pub struct ConfirmDeliveryEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+    }
+
+    #[ink(event)]
+    pub struct CancelOrderEvent {
+        #[ink(topic)]
         order_id: OrderId,
+        #[ink(topic)]
+        customer_id: CustomerId,
     }
 
     #[ink(event)]
     pub struct AddFoodEvent {
+        #[ink(topic)]
         food_id: FoodId,
         food_name: String,
+        #[ink(topic)]
         restaurant_id: RestaurantId,
-        restaurant_address: String,
-        phone_number: String,
         description: String,
         price: u128,
         eta: u64,
@@ -80,6 +204,7 @@ mod contract {
 
     #[ink(event)]
     pub struct UpdateFoodEvent {
+        #[ink(topic)]
         food_id: FoodId,
         food_name: String,
         description: String,
@@ -87,22 +212,93 @@ mod contract {
         eta: u64,
     }
 
+    #[ink(event)]
+    pub struct UpdateFoodAvailabilityEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        available: bool,
+        stock: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateFoodCurrencyEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        currency: Option<CurrencyCode>,
+    }
+
+    #[ink(event)]
+    pub struct RemoveFoodEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+    }
+
+    #[ink(event)]
+    pub struct UpdateOpeningHoursEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        opening_hours: Vec<OpeningSlot>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateClosuresEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        closures: Vec<Closure>,
+    }
+
     #[ink(event)]
     pub struct ConfirmOrderEvent {
+        #[ink(topic)]
         order_id: OrderId,
         eta: u64,
     }
 
+    #[ink(event)]
+    pub struct RejectOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+    }
+
     #[ink(event)]
     pub struct DeliverOrderEvent {
+        #[ink(topic)]
         order_id: OrderId,
+        delivery_id: DeliveryId,
+        #[ink(topic)]
         restaurant_id: RestaurantId,
+        #[ink(topic)]
         customer_id: CustomerId,
-        delivery_address: String,
+        delivery_address: PersonalData,
+    }
+
+    #[ink(event)]
+    pub struct PickupDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+    }
+
+    #[ink(event)]
+    pub struct HandoffDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
     }
 
     #[ink(event)]
     pub struct AddDeliverEvent {
+        #[ink(topic)]
         deliver_id: DeliverId,
         deliver_name: String,
         deliver_address: String,
@@ -111,146 +307,3870 @@ mod contract {
 
     #[ink(event)]
     pub struct AddRestaurantEvent {
+        #[ink(topic)]
         restaurant_id: RestaurantId,
         restaurant_name: String,
         restaurant_address: String,
         phone_number: String,
     }
 
-    impl CustomerServiceEvents for CustomerService {
-        fn emit_submit_order_event(
-            &self,
-            order_id: OrderId,
-            food_id: FoodId,
-            restaurant_id: RestaurantId,
-            customer_id: CustomerId,
-            delivery_address: String,
-            phone_number: String,
-        ) {
-            self.env().emit_event(SubmitOrderEvent {
-                order_id,
-                food_id,
-                restaurant_id,
-                customer_id,
-                delivery_address,
-                phone_number,
-            })
-        }
+    #[ink(event)]
+    pub struct OpenDisputeEvent {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        evidence_hash: Hash,
+    }
 
-        fn emit_confirm_delivery_event(
-            &self,
-            order_id: OrderId,
-        ) {
-            self.env().emit_event(ConfirmDeliveryEvent {
-                order_id,
-            })
-        }
+    #[ink(event)]
+    pub struct RespondDisputeEvent {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        responder: AccountId,
+        response_hash: Hash,
     }
 
-    impl DeliverServiceEvents for FoodOrder {
-        fn emit_pickup_delivery_event(
-            &self,
-            delivery_id: DeliveryId,
-        ) {
-            self.env().emit_event(ConfirmDeliveryEvent {
-                delivery_id,
-            })
-        }
+    #[ink(event)]
+    pub struct ResolveDisputeEvent {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        order_id: OrderId,
+        resolution: DisputeResolution,
     }
 
-    impl ManagerServiceEvents for FoodOrder {
-        fn emit_add_deliver_event(
-            &self,
-            deliver_id: DeliverId,
-            deliver_name: String,
-            deliver_address: String,
-            phone_number: String,
-        ) {
-            self.env().emit_event(AddDeliverEvent {
-                deliver_id,
-                deliver_name,
-                deliver_address,
-                phone_number,
-            })
-        }
-    
-        fn emit_add_restaurant_event(
-            &self,
-            restaurant_id: RestaurantId,
-            restaurant_name: String,
-            restaurant_address: String,
-            phone_number: String,
-        ) {
-            self.env().emit_event(AddRestaurantEvent {
-                restaurant_id,
-                restaurant_name,
-                restaurant_address,
-                phone_number,
-            })
-        }
+    #[ink(event)]
+    pub struct SettleOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        refund: u128,
+        restaurant_amount: u128,
+        deliver_amount: u128,
+        held_amount: u128,
+        platform_amount: u128,
     }
 
-    impl RestaurantServiceEvents for FoodOrder {
-        fn emit_add_food_event(
-            &self,
-            food_id: FoodId,
-            food_name: String,
-            restaurant_id: RestaurantId,
-            description: String,
-            price: u128,
-            eta: u64,
-        ) {
-            self.env().emit_event(AddFoodEvent {
-                food_id,
-                food_name,
-                restaurant_id,
-                description,
-                price,
-                eta,
-            })
-        }
-    
-        fn emit_update_food_event(
-            &self,
-            food_id: FoodId,
-            food_name: String,
-            description: String,
-            price: u128,
-            eta: u64,
-        ) {
-            self.env().emit_evet(UpdateFoodEvent {
-                food_id,
-                food_name,
-                description,
-                price,
-                eta,
-            })
-        }
-    
-        fn emit_confirm_order_event(
-            &self,
-            order_id: OrderId,
-            eta: u64,
-        ) {
-            self.env().emit_event(ConfirmOrderEvent {
-                order_id,
-                eta
-            })
-        }
-    
-        fn emit_deliver_order_event(
-            &self,
-            order_id: OrderId,
-            restaurant_id: RestaurantId,
-            customer_id: CustomerId,
-            delivery_address: String,
-        ) {
-            self.env().emit_event(DeliverOrderEvent {
-                order_id,
-                restaurant_id,
-                customer_id,
-                delivery_address,
-            })
+    #[ink(event)]
+    pub struct SetCommissionEvent {
+        #[ink(topic)]
+        restaurant_id: Option<RestaurantId>,
+        food_commission: Option<u16>,
+        delivery_commission: u16,
+    }
+
+    #[ink(event)]
+    pub struct WithdrawTreasuryEvent {
+        #[ink(topic)]
+        manager: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct CreditBalanceEvent {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        order_id: OrderId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct WithdrawEvent {
+        #[ink(topic)]
+        account: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct AddCustomerEvent {
+        #[ink(topic)]
+        customer_id: CustomerId,
+        #[ink(topic)]
+        customer_account: AccountId,
+        customer_name: String,
+    }
+
+    #[ink(event)]
+    pub struct ChangeManagerEvent {
+        #[ink(topic)]
+        previous_manager: AccountId,
+        #[ink(topic)]
+        new_manager: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetDeliveryFeeEvent {
+        delivery_fee: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetArbiterEvent {
+        #[ink(topic)]
+        arbiter: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct UpdateRestaurantEncryptionKeyEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        encryption_key: Option<PublicKey>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateDeliverEncryptionKeyEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        encryption_key: Option<PublicKey>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateDeliverAttestationKeyEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        attestation_key: Option<EcdsaAddress>,
+    }
+
+    #[ink(event)]
+    pub struct DropOffDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        timestamp: Timestamp,
+        location_hash: Option<Hash>,
+        photo_hash: Option<Hash>,
+    }
+
+    #[ink(event)]
+    pub struct TimeOutDeliveryEvent {
+        #[ink(topic)]
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        deliver_id: DeliverId,
+    }
+
+    #[ink(event)]
+    pub struct PostBondEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        amount: u128,
+        bond: u128,
+    }
+
+    #[ink(event)]
+    pub struct DeregisterDeliverEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        unlocks_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct WithdrawBondEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SlashBondEvent {
+        #[ink(topic)]
+        deliver_id: DeliverId,
+        #[ink(topic)]
+        order_id: OrderId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetBondPolicyEvent {
+        minimum_bond: u128,
+        lock_period: u64,
+        slash_rate: u16,
+        delivery_timeout: u64,
+    }
+
+    #[ink(event)]
+    pub struct AddPaymentProcessorEvent {
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        #[ink(topic)]
+        processor_account: AccountId,
+        processor_name: String,
+    }
+
+    #[ink(event)]
+    pub struct SetProcessorFeeRateEvent {
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        fee_rate: u16,
+    }
+
+    #[ink(event)]
+    pub struct SetCaptureTimeoutEvent {
+        capture_timeout: u64,
+    }
+
+    #[ink(event)]
+    pub struct AuthorizeChargeEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        charge_reference: String,
+        amount: u128,
+        fee: u128,
+    }
+
+    #[ink(event)]
+    pub struct CaptureChargeEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+        processor_amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SelectPaymentProcessorEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+    }
+
+    #[ink(event)]
+    pub struct VoidChargeEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct PostExchangeRateEvent {
+        #[ink(topic)]
+        currency: CurrencyCode,
+        rate: u128,
+        timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct SetOracleEvent {
+        #[ink(topic)]
+        oracle: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetMaxRateAgeEvent {
+        max_rate_age: u64,
+    }
+
+    #[ink(event)]
+    pub struct CreateScheduleEvent {
+        #[ink(topic)]
+        schedule_id: ScheduleId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        food_id: FoodId,
+        next_time: Timestamp,
+        prepaid: u128,
+    }
+
+    #[ink(event)]
+    pub struct TriggerScheduleEvent {
+        #[ink(topic)]
+        schedule_id: ScheduleId,
+        #[ink(topic)]
+        order_id: Option<OrderId>,
+        next_time: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct CloseScheduleEvent {
+        #[ink(topic)]
+        schedule_id: ScheduleId,
+        refund: u128,
+    }
+
+    #[ink(event)]
+    pub struct OpenGroupOrderEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        organizer_id: CustomerId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+    }
+
+    #[ink(event)]
+    pub struct AddGroupItemEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        food_id: FoodId,
+        price: u128,
+    }
+
+    #[ink(event)]
+    pub struct FundGroupShareEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SubmitGroupOrderEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        order_id: OrderId,
+    }
+
+    #[ink(event)]
+    pub struct CancelGroupOrderEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        refund: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetLoyaltyRateEvent {
+        loyalty_rate: u16,
+    }
+
+    #[ink(event)]
+    pub struct SetLoyaltyMultiplierEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        multiplier: Option<u16>,
+    }
+
+    #[ink(event)]
+    pub struct AddPromoCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+        #[ink(topic)]
+        creator: AccountId,
+        #[ink(topic)]
+        restaurant_id: Option<RestaurantId>,
+    }
+
+    #[ink(event)]
+    pub struct RevokePromoCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct RedeemPromoCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        discount: u128,
+    }
+
+    /// PSP22 transfer of loyalty points, `from` is `None` when points are
+    /// minted and `to` is `None` when they are burned.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SetCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    /// Emitted on instantiation with the layout version of every event the
+    /// contract emits, see `get_event_schema_version`.
+    #[ink(event)]
+    pub struct EventSchemaEvent {
+        version: u16,
+    }
+
+    impl BalanceServiceEvents for FoodOrder {
+        fn emit_withdraw_event(
+            &self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            self.env().emit_event(WithdrawEvent {
+                account,
+                amount,
+            })
+        }
+    }
+
+    impl CustomerServiceEvents for FoodOrder {
+        fn emit_add_customer_event(
+            &self,
+            customer_id: CustomerId,
+            customer_account: AccountId,
+            customer_name: String,
+        ) {
+            self.env().emit_event(AddCustomerEvent {
+                customer_id,
+                customer_account,
+                customer_name,
+            })
+        }
+
+        fn emit_submit_order_event(
+            &self,
+            order_id: OrderId,
+            food_id: FoodId,
+            restaurant_id: RestaurantId,
+            customer_id: CustomerId,
+            delivery_address: PersonalData,
+            phone_number: PersonalData,
+        ) {
+            self.env().emit_event(SubmitOrderEvent {
+                order_id,
+                food_id,
+                restaurant_id,
+                customer_id,
+                delivery_address,
+                phone_number,
+            })
+        }
+
+        fn emit_confirm_delivery_event(
+            &self,
+            order_id: OrderId,
+        ) {
+            self.env().emit_event(ConfirmDeliveryEvent {
+                order_id,
+            })
+        }
+
+        fn emit_cancel_order_event(
+            &self,
+            order_id: OrderId,
+            customer_id: CustomerId,
+        ) {
+            self.env().emit_event(CancelOrderEvent {
+                order_id,
+                customer_id,
+            })
+        }
+    }
+
+    impl DeliverServiceEvents for FoodOrder {
+        fn emit_pickup_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            self.env().emit_event(PickupDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+            })
+        }
+
+        fn emit_handoff_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            self.env().emit_event(HandoffDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+            })
+        }
+
+        fn emit_update_deliver_encryption_key_event(
+            &self,
+            deliver_id: DeliverId,
+            encryption_key: Option<PublicKey>,
+        ) {
+            self.env().emit_event(UpdateDeliverEncryptionKeyEvent {
+                deliver_id,
+                encryption_key,
+            })
+        }
+
+        fn emit_drop_off_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+            attestation: DropOffAttestation,
+        ) {
+            self.env().emit_event(DropOffDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+                timestamp: attestation.timestamp,
+                location_hash: attestation.location_hash,
+                photo_hash: attestation.photo_hash,
+            })
+        }
+
+        fn emit_update_deliver_attestation_key_event(
+            &self,
+            deliver_id: DeliverId,
+            attestation_key: Option<EcdsaAddress>,
+        ) {
+            self.env().emit_event(UpdateDeliverAttestationKeyEvent {
+                deliver_id,
+                attestation_key,
+            })
+        }
+    }
+
+    impl DisputeServiceEvents for FoodOrder {
+        fn emit_open_dispute_event(
+            &self,
+            dispute_id: DisputeId,
+            order_id: OrderId,
+            customer_id: CustomerId,
+            evidence_hash: Hash,
+        ) {
+            self.env().emit_event(OpenDisputeEvent {
+                dispute_id,
+                order_id,
+                customer_id,
+                evidence_hash,
+            })
+        }
+
+        fn emit_respond_dispute_event(
+            &self,
+            dispute_id: DisputeId,
+            responder: AccountId,
+            response_hash: Hash,
+        ) {
+            self.env().emit_event(RespondDisputeEvent {
+                dispute_id,
+                responder,
+                response_hash,
+            })
+        }
+
+        fn emit_resolve_dispute_event(
+            &self,
+            dispute_id: DisputeId,
+            order_id: OrderId,
+            resolution: DisputeResolution,
+        ) {
+            self.env().emit_event(ResolveDisputeEvent {
+                dispute_id,
+                order_id,
+                resolution,
+            })
+        }
+
+        fn emit_set_arbiter_event(
+            &self,
+            arbiter: AccountId,
+        ) {
+            self.env().emit_event(SetArbiterEvent {
+                arbiter,
+            })
+        }
+    }
+
+    impl GroupOrderServiceEvents for FoodOrder {
+        fn emit_open_group_order_event(
+            &self,
+            group_id: GroupId,
+            organizer_id: CustomerId,
+            restaurant_id: RestaurantId,
+        ) {
+            self.env().emit_event(OpenGroupOrderEvent {
+                group_id,
+                organizer_id,
+                restaurant_id,
+            })
+        }
+
+        fn emit_add_group_item_event(
+            &self,
+            group_id: GroupId,
+            customer_id: CustomerId,
+            food_id: FoodId,
+            price: u128,
+        ) {
+            self.env().emit_event(AddGroupItemEvent {
+                group_id,
+                customer_id,
+                food_id,
+                price,
+            })
+        }
+
+        fn emit_fund_group_share_event(
+            &self,
+            group_id: GroupId,
+            customer_id: CustomerId,
+            amount: u128,
+        ) {
+            self.env().emit_event(FundGroupShareEvent {
+                group_id,
+                customer_id,
+                amount,
+            })
+        }
+
+        fn emit_submit_group_order_event(
+            &self,
+            group_id: GroupId,
+            order_id: OrderId,
+        ) {
+            self.env().emit_event(SubmitGroupOrderEvent {
+                group_id,
+                order_id,
+            })
+        }
+
+        fn emit_cancel_group_order_event(
+            &self,
+            group_id: GroupId,
+            refund: u128,
+        ) {
+            self.env().emit_event(CancelGroupOrderEvent {
+                group_id,
+                refund,
+            })
+        }
+    }
+
+    impl BondServiceEvents for FoodOrder {
+        fn emit_post_bond_event(
+            &self,
+            deliver_id: DeliverId,
+            amount: u128,
+            bond: u128,
+        ) {
+            self.env().emit_event(PostBondEvent {
+                deliver_id,
+                amount,
+                bond,
+            })
+        }
+
+        fn emit_deregister_deliver_event(
+            &self,
+            deliver_id: DeliverId,
+            unlocks_at: Timestamp,
+        ) {
+            self.env().emit_event(DeregisterDeliverEvent {
+                deliver_id,
+                unlocks_at,
+            })
+        }
+
+        fn emit_withdraw_bond_event(
+            &self,
+            deliver_id: DeliverId,
+            amount: u128,
+        ) {
+            self.env().emit_event(WithdrawBondEvent {
+                deliver_id,
+                amount,
+            })
+        }
+
+        fn emit_time_out_delivery_event(
+            &self,
+            delivery_id: DeliveryId,
+            order_id: OrderId,
+            deliver_id: DeliverId,
+        ) {
+            self.env().emit_event(TimeOutDeliveryEvent {
+                delivery_id,
+                order_id,
+                deliver_id,
+            })
+        }
+
+        fn emit_set_bond_policy_event(
+            &self,
+            policy: BondPolicy,
+        ) {
+            self.env().emit_event(SetBondPolicyEvent {
+                minimum_bond: policy.minimum_bond,
+                lock_period: policy.lock_period,
+                slash_rate: policy.slash_rate,
+                delivery_timeout: policy.delivery_timeout,
+            })
+        }
+    }
+
+    impl BondingEvents for FoodOrder {
+        fn emit_slash_bond_event(
+            &self,
+            deliver_id: DeliverId,
+            order_id: OrderId,
+            amount: u128,
+        ) {
+            self.env().emit_event(SlashBondEvent {
+                deliver_id,
+                order_id,
+                amount,
+            })
+        }
+    }
+
+    impl LoyaltyServiceEvents for FoodOrder {
+        fn emit_set_loyalty_rate_event(
+            &self,
+            loyalty_rate: u16,
+        ) {
+            self.env().emit_event(SetLoyaltyRateEvent {
+                loyalty_rate,
+            })
+        }
+
+        fn emit_set_loyalty_multiplier_event(
+            &self,
+            restaurant_id: RestaurantId,
+            multiplier: Option<u16>,
+        ) {
+            self.env().emit_event(SetLoyaltyMultiplierEvent {
+                restaurant_id,
+                multiplier,
+            })
+        }
+    }
+
+    impl PromoServiceEvents for FoodOrder {
+        fn emit_add_promo_code_event(
+            &self,
+            code_hash: Hash,
+            creator: AccountId,
+            restaurant_id: Option<RestaurantId>,
+        ) {
+            self.env().emit_event(AddPromoCodeEvent {
+                code_hash,
+                creator,
+                restaurant_id,
+            })
+        }
+
+        fn emit_revoke_promo_code_event(
+            &self,
+            code_hash: Hash,
+        ) {
+            self.env().emit_event(RevokePromoCodeEvent {
+                code_hash,
+            })
+        }
+
+        fn emit_redeem_promo_code_event(
+            &self,
+            code_hash: Hash,
+            order_id: OrderId,
+            customer_id: CustomerId,
+            discount: u128,
+        ) {
+            self.env().emit_event(RedeemPromoCodeEvent {
+                code_hash,
+                order_id,
+                customer_id,
+                discount,
+            })
+        }
+    }
+
+    impl psp22::Internal for FoodOrder {
+        fn _emit_transfer_event(
+            &self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            amount: Balance,
+        ) {
+            self.env().emit_event(Transfer {
+                from,
+                to,
+                value: amount,
+            })
+        }
+
+        fn _emit_approval_event(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+            amount: Balance,
+        ) {
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: amount,
+            })
+        }
+    }
+
+    // Loyalty points are the contract's PSP22 token.
+    impl Loyalty for FoodOrder {
+        fn _mint_points(
+            &mut self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            assert!(psp22::Internal::_mint_to(self, account, amount).is_ok(), "Minting loyalty points failed!");
+        }
+
+        fn _burn_points(
+            &mut self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            assert!(psp22::Internal::_burn_from(self, account, amount).is_ok(), "Burning loyalty points failed!");
+        }
+
+        fn _points_of(
+            &self,
+            account: AccountId,
+        ) -> u128 {
+            psp22::Internal::_balance_of(self, &account)
+        }
+    }
+
+    impl ManagerServiceEvents for FoodOrder {
+        fn emit_change_manager_event(
+            &self,
+            previous_manager: AccountId,
+            new_manager: AccountId,
+        ) {
+            self.env().emit_event(ChangeManagerEvent {
+                previous_manager,
+                new_manager,
+            })
+        }
+    }
+
+    impl OrderingManagerServiceEvents for FoodOrder {
+        fn emit_add_restaurant_event(
+            &self,
+            restaurant_id: RestaurantId,
+            restaurant_name: String,
+            restaurant_address: String,
+            phone_number: String,
+        ) {
+            self.env().emit_event(AddRestaurantEvent {
+                restaurant_id,
+                restaurant_name,
+                restaurant_address,
+                phone_number,
+            })
+        }
+
+        fn emit_set_delivery_fee_event(
+            &self,
+            delivery_fee: u128,
+        ) {
+            self.env().emit_event(SetDeliveryFeeEvent {
+                delivery_fee,
+            })
+        }
+
+        fn emit_set_commission_event(
+            &self,
+            restaurant_id: Option<RestaurantId>,
+            food_commission: Option<u16>,
+            delivery_commission: u16,
+        ) {
+            self.env().emit_event(SetCommissionEvent {
+                restaurant_id,
+                food_commission,
+                delivery_commission,
+            })
+        }
+    }
+
+    impl DeliveryManagerServiceEvents for FoodOrder {
+        fn emit_add_deliver_event(
+            &self,
+            deliver_id: DeliverId,
+            deliver_name: String,
+            deliver_address: String,
+            phone_number: String,
+        ) {
+            self.env().emit_event(AddDeliverEvent {
+                deliver_id,
+                deliver_name,
+                deliver_address,
+                phone_number,
+            })
+        }
+    }
+
+    impl PaymentManagerServiceEvents for FoodOrder {
+        fn emit_add_payment_processor_event(
+            &self,
+            processor_id: PaymentProcessorId,
+            processor_account: AccountId,
+            processor_name: String,
+        ) {
+            self.env().emit_event(AddPaymentProcessorEvent {
+                processor_id,
+                processor_account,
+                processor_name,
+            })
+        }
+
+        fn emit_set_processor_fee_rate_event(
+            &self,
+            processor_id: PaymentProcessorId,
+            fee_rate: u16,
+        ) {
+            self.env().emit_event(SetProcessorFeeRateEvent {
+                processor_id,
+                fee_rate,
+            })
+        }
+
+        fn emit_set_capture_timeout_event(
+            &self,
+            capture_timeout: u64,
+        ) {
+            self.env().emit_event(SetCaptureTimeoutEvent {
+                capture_timeout,
+            })
+        }
+
+        fn emit_withdraw_treasury_event(
+            &self,
+            manager: AccountId,
+            amount: u128,
+        ) {
+            self.env().emit_event(WithdrawTreasuryEvent {
+                manager,
+                amount,
+            })
+        }
+    }
+
+    impl OracleServiceEvents for FoodOrder {
+        fn emit_post_exchange_rate_event(
+            &self,
+            currency: CurrencyCode,
+            rate: u128,
+            timestamp: Timestamp,
+        ) {
+            self.env().emit_event(PostExchangeRateEvent {
+                currency,
+                rate,
+                timestamp,
+            })
+        }
+
+        fn emit_set_oracle_event(
+            &self,
+            oracle: AccountId,
+        ) {
+            self.env().emit_event(SetOracleEvent {
+                oracle,
+            })
+        }
+
+        fn emit_set_max_rate_age_event(
+            &self,
+            max_rate_age: u64,
+        ) {
+            self.env().emit_event(SetMaxRateAgeEvent {
+                max_rate_age,
+            })
+        }
+    }
+
+    impl PaymentServiceEvents for FoodOrder {
+        fn emit_authorize_charge_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+            charge_reference: String,
+            amount: u128,
+            fee: u128,
+        ) {
+            self.env().emit_event(AuthorizeChargeEvent {
+                order_id,
+                processor_id,
+                charge_reference,
+                amount,
+                fee,
+            })
+        }
+
+        fn emit_capture_charge_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+            restaurant_amount: u128,
+            processor_amount: u128,
+        ) {
+            self.env().emit_event(CaptureChargeEvent {
+                order_id,
+                processor_id,
+                restaurant_amount,
+                processor_amount,
+            })
+        }
+
+        fn emit_select_payment_processor_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+        ) {
+            self.env().emit_event(SelectPaymentProcessorEvent {
+                order_id,
+                processor_id,
+            })
+        }
+
+        fn emit_void_charge_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+            restaurant_amount: u128,
+        ) {
+            self.env().emit_event(VoidChargeEvent {
+                order_id,
+                processor_id,
+                restaurant_amount,
+            })
+        }
+    }
+
+    impl RestaurantServiceEvents for FoodOrder {
+        fn emit_add_food_event(
+            &self,
+            food_id: FoodId,
+            food_name: String,
+            restaurant_id: RestaurantId,
+            description: String,
+            price: u128,
+            eta: u64,
+        ) {
+            self.env().emit_event(AddFoodEvent {
+                food_id,
+                food_name,
+                restaurant_id,
+                description,
+                price,
+                eta,
+            })
+        }
+    
+        fn emit_update_food_event(
+            &self,
+            food_id: FoodId,
+            food_name: String,
+            description: String,
+            price: u128,
+            eta: u64,
+        ) {
+            self.env().emit_event(UpdateFoodEvent {
+                food_id,
+                food_name,
+                description,
+                price,
+                eta,
+            })
+        }
+    
+        fn emit_update_food_availability_event(
+            &self,
+            food_id: FoodId,
+            available: bool,
+            stock: Option<u32>,
+        ) {
+            self.env().emit_event(UpdateFoodAvailabilityEvent {
+                food_id,
+                available,
+                stock,
+            })
+        }
+
+        fn emit_update_food_currency_event(
+            &self,
+            food_id: FoodId,
+            currency: Option<CurrencyCode>,
+        ) {
+            self.env().emit_event(UpdateFoodCurrencyEvent {
+                food_id,
+                currency,
+            })
+        }
+
+        fn emit_remove_food_event(
+            &self,
+            food_id: FoodId,
+            restaurant_id: RestaurantId,
+        ) {
+            self.env().emit_event(RemoveFoodEvent {
+                food_id,
+                restaurant_id,
+            })
+        }
+    
+        fn emit_update_opening_hours_event(
+            &self,
+            restaurant_id: RestaurantId,
+            opening_hours: Vec<OpeningSlot>,
+        ) {
+            self.env().emit_event(UpdateOpeningHoursEvent {
+                restaurant_id,
+                opening_hours,
+            })
+        }
+
+        fn emit_update_closures_event(
+            &self,
+            restaurant_id: RestaurantId,
+            closures: Vec<Closure>,
+        ) {
+            self.env().emit_event(UpdateClosuresEvent {
+                restaurant_id,
+                closures,
+            })
+        }
+    
+        fn emit_confirm_order_event(
+            &self,
+            order_id: OrderId,
+            eta: u64,
+        ) {
+            self.env().emit_event(ConfirmOrderEvent {
+                order_id,
+                eta
+            })
+        }
+
+        fn emit_reject_order_event(
+            &self,
+            order_id: OrderId,
+            restaurant_id: RestaurantId,
+        ) {
+            self.env().emit_event(RejectOrderEvent {
+                order_id,
+                restaurant_id,
+            })
+        }
+    
+        fn emit_deliver_order_event(
+            &self,
+            order_id: OrderId,
+            delivery_id: DeliveryId,
+            restaurant_id: RestaurantId,
+            customer_id: CustomerId,
+            delivery_address: PersonalData,
+        ) {
+            self.env().emit_event(DeliverOrderEvent {
+                order_id,
+                delivery_id,
+                restaurant_id,
+                customer_id,
+                delivery_address,
+            })
+        }
+
+        fn emit_update_restaurant_encryption_key_event(
+            &self,
+            restaurant_id: RestaurantId,
+            encryption_key: Option<PublicKey>,
+        ) {
+            self.env().emit_event(UpdateRestaurantEncryptionKeyEvent {
+                restaurant_id,
+                encryption_key,
+            })
+        }
+    }
+
+    impl ScheduleServiceEvents for FoodOrder {
+        fn emit_create_schedule_event(
+            &self,
+            schedule_id: ScheduleId,
+            customer_id: CustomerId,
+            food_id: FoodId,
+            next_time: Timestamp,
+            prepaid: u128,
+        ) {
+            self.env().emit_event(CreateScheduleEvent {
+                schedule_id,
+                customer_id,
+                food_id,
+                next_time,
+                prepaid,
+            })
+        }
+
+        fn emit_trigger_schedule_event(
+            &self,
+            schedule_id: ScheduleId,
+            order_id: Option<OrderId>,
+            next_time: Timestamp,
+        ) {
+            self.env().emit_event(TriggerScheduleEvent {
+                schedule_id,
+                order_id,
+                next_time,
+            })
+        }
+
+        fn emit_close_schedule_event(
+            &self,
+            schedule_id: ScheduleId,
+            refund: u128,
+        ) {
+            self.env().emit_event(CloseScheduleEvent {
+                schedule_id,
+                refund,
+            })
+        }
+    }
+
+    impl SettlementEvents for FoodOrder {
+        fn emit_settle_order_event(
+            &self,
+            order_id: OrderId,
+            refund: u128,
+            restaurant_amount: u128,
+            deliver_amount: u128,
+            held_amount: u128,
+            platform_amount: u128,
+        ) {
+            self.env().emit_event(SettleOrderEvent {
+                order_id,
+                refund,
+                restaurant_amount,
+                deliver_amount,
+                held_amount,
+                platform_amount,
+            })
+        }
+
+        fn emit_credit_balance_event(
+            &self,
+            account: AccountId,
+            order_id: OrderId,
+            amount: u128,
+        ) {
+            self.env().emit_event(CreditBalanceEvent {
+                account,
+                order_id,
+                amount,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+        use logics::impls::types::{
+            ChargeStatus,
+            DeliveryStatus,
+            DisputeStatus,
+            GroupStatus,
+            OrderStatus,
+            PromoDiscount,
+            SealedBox,
+            hash_secret,
+            RATE_PRECISION,
+        };
+
+        const PRICE: u128 = 100;
+        const DELIVERY_FEE: u128 = 10;
+        const FOOD_ETA: u64 = 600_000;
+        const START: u64 = 1_700_000_000_000;
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller::<DefaultEnvironment>(account);
+        }
+
+        fn set_value(value: u128) {
+            test::set_value_transferred::<DefaultEnvironment>(value);
+        }
+
+        fn set_timestamp(timestamp: u64) {
+            test::set_block_timestamp::<DefaultEnvironment>(timestamp);
+        }
+
+        fn plain(value: &str) -> PersonalData {
+            PersonalData::Plain(String::from(value))
+        }
+
+        /// Alice manages the contract, Bob runs restaurant 1 serving food 1,
+        /// Django is deliver 1 and Charlie is customer 1.
+        fn setup() -> FoodOrder {
+            let accounts = accounts();
+            #[cfg(feature = "chain-extension")]
+            logics::impls::Bookkeeping::mock::register();
+            set_timestamp(START);
+            set_caller(accounts.alice);
+            let mut contract = FoodOrder::new();
+            contract.add_restaurant(accounts.bob, String::from("Bob's"), String::from("1 Main St"), String::from("555-0100"));
+            contract.add_deliver(accounts.django, String::from("Django"), String::from("2 Main St"), String::from("555-0101"));
+            contract.set_delivery_fee(DELIVERY_FEE);
+            contract.set_commission(1_000, 2_000);
+            set_caller(accounts.bob);
+            contract.add_food(String::from("Pizza"), String::from("Margherita"), PRICE, FOOD_ETA);
+            set_caller(accounts.charlie);
+            contract.add_customer(String::from("Charlie"), plain("3 Main St"), plain("555-0102"));
+            contract
+        }
+
+        fn submit(contract: &mut FoodOrder) -> OrderId {
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 0, None, None);
+            set_value(0);
+            contract.get_order_from_customer(1).last().copied().unwrap()
+        }
+
+        fn deliver(contract: &mut FoodOrder, order_id: OrderId) -> DeliveryId {
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            set_caller(accounts().django);
+            contract.confirm_delivery(delivery_id);
+            delivery_id
+        }
+
+        fn statuses(contract: &FoodOrder, order_id: OrderId) -> Vec<OrderStatus> {
+            contract
+                .get_order_status_history(order_id)
+                .into_iter()
+                .map(|change| change.status)
+                .collect()
+        }
+
+        #[ink::test]
+        fn full_order_lifecycle_settles_all_parties() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::OrderSubmitted);
+            assert_eq!(contract.get_order_from_restaurant(1), vec![order_id]);
+
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            assert_eq!(contract.get_order_from_id(order_id).eta, FOOD_ETA);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            assert_eq!(contract.get_delivery_from_id(delivery_id).status, DeliveryStatus::Waiting);
+
+            set_caller(accounts.django);
+            contract.confirm_delivery(delivery_id);
+            assert_eq!(contract.get_delivery_from_id(delivery_id).status, DeliveryStatus::PickUp);
+            assert_eq!(contract.get_delivery_from_deliver(1), vec![delivery_id]);
+            assert_eq!(contract.get_order_from_id(order_id).deliver_id, 1);
+
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            assert_eq!(
+                statuses(&contract, order_id),
+                vec![
+                    OrderStatus::OrderSubmitted,
+                    OrderStatus::OrderConfirmed,
+                    OrderStatus::WaitingDeliver,
+                    OrderStatus::OrderDelivered,
+                    OrderStatus::DeliveryAcceptted,
+                ]
+            );
+            assert_eq!(contract.get_balance(accounts.bob), 90);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+            assert_eq!(contract.get_treasury_balance(), 12);
+            assert_eq!(contract.get_balance(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn balances_and_treasury_can_be_withdrawn() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            test::set_account_balance::<DefaultEnvironment>(test::callee::<DefaultEnvironment>(), 1_000_000);
+
+            set_caller(accounts.bob);
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            contract.withdraw(90);
+            assert_eq!(contract.get_balance(accounts.bob), 0);
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap(), before + 90);
+
+            set_caller(accounts.alice);
+            contract.withdraw_treasury(12);
+            assert_eq!(contract.get_treasury_balance(), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Withdraw amount must be positive!")]
+        fn withdraw_zero_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.withdraw(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Insufficient balance!")]
+        fn withdraw_more_than_balance_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.withdraw(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can withdraw treasury!")]
+        fn withdraw_treasury_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.withdraw_treasury(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Insufficient treasury balance!")]
+        fn withdraw_treasury_more_than_treasury_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.withdraw_treasury(1);
+        }
+
+        #[ink::test]
+        fn manager_registers_restaurants_and_delivers() {
+            let accounts = accounts();
+            let contract = setup();
+            let restaurant = contract.get_restaurant_from_id(1);
+            assert_eq!(restaurant.restaurant_account, accounts.bob);
+            assert_eq!(restaurant.restaurant_name, "Bob's");
+            let deliver = contract.get_deliver_from_id(1);
+            assert_eq!(deliver.deliver_account, accounts.django);
+            assert_eq!(contract.get_delivery_fee(), DELIVERY_FEE);
+            assert_eq!(contract.get_food_commission(1), 1_000);
+            assert_eq!(contract.get_delivery_commission(), 2_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can add restaurant!")]
+        fn add_restaurant_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.add_restaurant(accounts().eve, String::new(), String::new(), String::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "already exist restaurant!")]
+        fn add_restaurant_twice_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().bob, String::new(), String::new(), String::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can add deliver!")]
+        fn add_deliver_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.add_deliver(accounts().eve, String::new(), String::new(), String::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "already exist deliver!")]
+        fn add_deliver_twice_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.add_deliver(accounts().django, String::new(), String::new(), String::new());
+        }
+
+        #[ink::test]
+        fn change_manager_hands_over_manager_rights() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.change_manager(accounts.frank);
+            set_caller(accounts.frank);
+            contract.add_restaurant(accounts.eve, String::from("Eve's"), String::new(), String::new());
+            assert_eq!(contract.get_restaurant_from_id(2).restaurant_account, accounts.eve);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can add restaurant!")]
+        fn previous_manager_loses_manager_rights() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.change_manager(accounts.frank);
+            contract.add_restaurant(accounts.eve, String::new(), String::new(), String::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can change manager!")]
+        fn change_manager_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.change_manager(accounts().eve);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set delivery fee!")]
+        fn set_delivery_fee_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_delivery_fee(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set commission!")]
+        fn set_commission_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_commission(0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Commission exceeds 100%!")]
+        fn set_commission_above_basis_points_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_commission(10_001, 0);
+        }
+
+        #[ink::test]
+        fn restaurant_commission_overrides_default() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_restaurant_commission(1, Some(500));
+            assert_eq!(contract.get_food_commission(1), 500);
+            contract.set_restaurant_commission(1, None);
+            assert_eq!(contract.get_food_commission(1), 1_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set commission!")]
+        fn set_restaurant_commission_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_restaurant_commission(1, Some(500));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Restaurant does not exist!")]
+        fn set_restaurant_commission_for_unknown_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_restaurant_commission(2, Some(500));
+        }
+
+        #[ink::test]
+        fn restaurant_manages_its_menu() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.update_food(1, String::from("Pizza"), String::from("Marinara"), 120, 300_000);
+            let food = contract.get_food_from_id(1);
+            assert_eq!(food.description, "Marinara");
+            assert_eq!(food.price, 120);
+            assert_eq!(food.eta, 300_000);
+            contract.set_food_availability(1, false);
+            assert!(!contract.get_food_from_id(1).available);
+            contract.set_food_stock(1, Some(3));
+            assert_eq!(contract.get_food_from_id(1).stock, Some(3));
+            contract.add_food(String::from("Pasta"), String::new(), 80, FOOD_ETA);
+            assert_eq!(contract.get_food_from_restaurant(1), vec![1, 2]);
+            contract.remove_food(1);
+            assert_eq!(contract.get_food_from_restaurant(1), vec![2]);
+            assert_eq!(contract.get_food_all(1, 3).len(), 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can add food!")]
+        fn add_food_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.add_food(String::new(), String::new(), 0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can update food!")]
+        fn update_food_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.update_food(1, String::new(), String::new(), 0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not owner of this food!")]
+        fn update_food_of_other_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().eve, String::new(), String::new(), String::new());
+            set_caller(accounts().eve);
+            contract.update_food(1, String::new(), String::new(), 0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Food not exist!")]
+        fn update_unknown_food_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.update_food(2, String::new(), String::new(), 0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can update food!")]
+        fn set_food_availability_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_food_availability(1, false);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not owner of this food!")]
+        fn set_food_stock_of_other_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().eve, String::new(), String::new(), String::new());
+            set_caller(accounts().eve);
+            contract.set_food_stock(1, Some(1));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can remove food!")]
+        fn remove_food_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.remove_food(1);
+        }
+
+        #[ink::test]
+        fn opening_hours_and_closures_gate_orders() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.add_closure(START, START + 1_000);
+            assert!(!contract.is_restaurant_open(1, START));
+            assert!(contract.is_restaurant_open(1, START + 1_000));
+            contract.remove_closure(START);
+            assert!(contract.is_restaurant_open(1, START));
+            // START falls on a Tuesday
+            contract.set_opening_hours(vec![OpeningSlot { weekday: 1, open: 0, close: 1_440 }]);
+            assert_eq!(contract.get_restaurant_from_id(1).opening_hours.len(), 1);
+            assert!(contract.is_restaurant_open(1, START));
+            assert!(!contract.is_restaurant_open(1, START + 86_400_000));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can set opening hours!")]
+        fn set_opening_hours_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_opening_hours(Vec::new());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid opening slot!")]
+        fn set_invalid_opening_slot_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_opening_hours(vec![OpeningSlot { weekday: 0, open: 600, close: 600 }]);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can add closure!")]
+        fn add_closure_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.add_closure(START, START + 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid closure window!")]
+        fn add_empty_closure_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.add_closure(START, START);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Closure not exist!")]
+        fn remove_unknown_closure_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.remove_closure(START);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can remove closure!")]
+        fn remove_closure_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.remove_closure(START);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Restaurant is closed!")]
+        fn submit_order_while_closed_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.add_closure(START, START + 1_000);
+            submit(&mut contract);
+        }
+
+        #[ink::test]
+        fn sealed_delivery_address_is_shared_with_the_deliver() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.bob);
+            contract.set_restaurant_encryption_key(Some([1; 32]));
+            set_caller(accounts.django);
+            contract.set_deliver_encryption_key(Some([2; 32]));
+            assert_eq!(contract.get_deliver_from_id(1).encryption_key, Some([2; 32]));
+
+            set_caller(accounts.charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            let sealed = PersonalData::Sealed(vec![SealedBox { recipient: [1; 32], ciphertext: vec![1] }]);
+            contract.submit_order(1, 1, sealed, plain("555-0102"), None, 0, None, None);
+            let delivery_id = deliver(&mut contract, 1);
+
+            set_caller(accounts.bob);
+            contract.share_delivery_address(1, SealedBox { recipient: [2; 32], ciphertext: vec![2] });
+            let delivery_address = contract.get_delivery_from_id(delivery_id).delivery_address;
+            assert!(delivery_address.is_sealed_for(&Some([2; 32])));
+            assert_eq!(contract.get_order_from_id(1).delivery_address, delivery_address);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery address must be sealed for the restaurant!")]
+        fn submit_order_sealed_for_another_key_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_restaurant_encryption_key(Some([1; 32]));
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            let sealed = PersonalData::Sealed(vec![SealedBox { recipient: [9; 32], ciphertext: vec![1] }]);
+            contract.submit_order(1, 1, sealed, plain("555-0102"), None, 0, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can set encryption key!")]
+        fn set_restaurant_encryption_key_by_non_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_restaurant_encryption_key(None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "only deliver can set encryption key")]
+        fn set_deliver_encryption_key_by_non_deliver_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_deliver_encryption_key(None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "No deliver assigned to this order!")]
+        fn share_delivery_address_before_pickup_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.share_delivery_address(order_id, SealedBox { recipient: [2; 32], ciphertext: vec![2] });
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can share delivery address!")]
+        fn share_delivery_address_by_non_restaurant_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().eve);
+            contract.share_delivery_address(order_id, SealedBox { recipient: [2; 32], ciphertext: vec![2] });
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "alread exist customer!")]
+        fn add_customer_twice_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            contract.add_customer(String::new(), plain(""), plain(""));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "only customer can submit order!")]
+        fn submit_order_by_non_customer_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.submit_order(1, 1, plain(""), plain(""), None, 0, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "you must pay same of price and delivery fee!")]
+        fn submit_order_with_wrong_payment_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            set_value(PRICE);
+            contract.submit_order(1, 1, plain(""), plain(""), None, 0, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Food not served by this restaurant!")]
+        fn submit_order_to_wrong_restaurant_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().eve, String::new(), String::new(), String::new());
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.submit_order(1, 2, plain(""), plain(""), None, 0, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Food is not available!")]
+        fn submit_order_for_unavailable_food_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_food_availability(1, false);
+            submit(&mut contract);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Food is out of stock!")]
+        fn submit_order_beyond_stock_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_food_stock(1, Some(1));
+            submit(&mut contract);
+            assert_eq!(contract.get_food_from_id(1).stock, Some(0));
+            submit(&mut contract);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Requested time must be in the future!")]
+        fn submit_order_requested_in_the_past_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.submit_order(1, 1, plain(""), plain(""), Some(START - 1), 0, None, None);
+        }
+
+        #[ink::test]
+        fn cancel_order_refunds_and_restores_stock() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.bob);
+            contract.set_food_stock(1, Some(1));
+            let order_id = submit(&mut contract);
+            set_caller(accounts.charlie);
+            contract.cancel_order(order_id);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::OrderCancelled);
+            assert_eq!(contract.get_food_from_id(1).stock, Some(1));
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+            assert_eq!(contract.get_treasury_balance(), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is already confirmed!")]
+        fn cancel_confirmed_order_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            set_caller(accounts().charlie);
+            contract.cancel_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "not customer of this order!")]
+        fn cancel_order_of_other_customer_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().eve);
+            contract.add_customer(String::new(), plain(""), plain(""));
+            contract.cancel_order(order_id);
+        }
+
+        #[ink::test]
+        fn reject_order_refunds_customer() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts.bob);
+            contract.reject_order(order_id);
+            assert_eq!(
+                statuses(&contract, order_id),
+                vec![OrderStatus::OrderSubmitted, OrderStatus::OrderRejected]
+            );
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is already confirmed!")]
+        fn reject_confirmed_order_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.reject_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not owner of this order!")]
+        fn reject_order_of_other_restaurant_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().eve, String::new(), String::new(), String::new());
+            set_caller(accounts().eve);
+            contract.reject_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can confirm order!")]
+        fn confirm_order_by_non_restaurant_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().eve);
+            contract.confirm_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not owner of this order!")]
+        fn confirm_order_of_other_restaurant_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().eve, String::new(), String::new(), String::new());
+            set_caller(accounts().eve);
+            contract.confirm_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order not exist!")]
+        fn confirm_unknown_order_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.confirm_order(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant can confirm order!")]
+        fn deliver_order_by_non_restaurant_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().eve);
+            contract.deliver_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is already settled!")]
+        fn deliver_cancelled_order_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.cancel_order(order_id);
+            set_caller(accounts().bob);
+            contract.deliver_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "only deliver can confirm devliery")]
+        fn pickup_by_non_deliver_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            set_caller(accounts().eve);
+            contract.confirm_delivery(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is not confirmed!")]
+        fn deliver_unconfirmed_order_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.deliver_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is not confirmed!")]
+        fn deliver_picked_up_order_again_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts().bob);
+            contract.deliver_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is already sent out!")]
+        fn confirm_picked_up_order_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "this delivery is already picked up!")]
+        fn pickup_twice_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            let delivery_id = deliver(&mut contract, order_id);
+            contract.confirm_delivery(delivery_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "not customer of this order!")]
+        fn confirm_delivery_of_other_customer_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts().eve);
+            contract.add_customer(String::new(), plain(""), plain(""));
+            contract.confrim_delivery(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is already settled!")]
+        fn confirm_delivery_twice_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts().charlie);
+            contract.confrim_delivery(order_id);
+            contract.confrim_delivery(order_id);
+        }
+
+        #[ink::test]
+        fn dispute_is_resolved_by_arbiter() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.alice);
+            contract.set_arbiter(accounts.frank);
+
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            let dispute = contract.get_dispute_from_order(order_id);
+            assert_eq!(dispute.status, DisputeStatus::Open);
+            set_caller(accounts.bob);
+            contract.respond_dispute(1, Hash::from([2; 32]));
+            set_caller(accounts.django);
+            contract.respond_dispute(1, Hash::from([3; 32]));
+            let dispute = contract.get_dispute_from_id(1);
+            assert_eq!(dispute.restaurant_response, Some(Hash::from([2; 32])));
+            assert_eq!(dispute.deliver_response, Some(Hash::from([3; 32])));
+
+            set_caller(accounts.frank);
+            contract.resolve_dispute(1, DisputeResolution::PartialRefund(50), false);
+            assert_eq!(contract.get_dispute_from_id(1).status, DisputeStatus::Resolved);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::OrderRefunded);
+            assert_eq!(contract.get_balance(accounts.charlie), 50);
+            assert_eq!(contract.get_balance(accounts.bob), 45);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+        }
+
+        #[ink::test]
+        fn dispute_release_pays_out_in_full() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::Release, false);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::DeliveryAcceptted);
+            assert_eq!(contract.get_balance(accounts.bob), 90);
+            assert_eq!(contract.get_treasury_balance(), 20);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery is cancelled!")]
+        fn resolved_dispute_cancels_waiting_delivery() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+            assert_eq!(contract.get_delivery_from_id(delivery_id).status, DeliveryStatus::Cancelled);
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+            set_caller(accounts.django);
+            contract.confirm_delivery(delivery_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is under dispute!")]
+        fn disputed_order_cannot_progress() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "only customer can open dispute!")]
+        fn open_dispute_by_non_customer_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().eve);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is already under dispute!")]
+        fn open_dispute_twice_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only restaurant or deliver of this order can respond!")]
+        fn respond_dispute_by_outsider_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts().django);
+            contract.respond_dispute(1, Hash::from([2; 32]));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager or arbiter can resolve dispute!")]
+        fn resolve_dispute_by_outsider_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Dispute is already resolved!")]
+        fn resolve_dispute_twice_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts().alice);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Refund exceeds order price!")]
+        fn partial_refund_above_price_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts().alice);
+            contract.resolve_dispute(1, DisputeResolution::PartialRefund(PRICE + DELIVERY_FEE + 1), false);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set arbiter!")]
+        fn set_arbiter_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_arbiter(accounts().eve);
+        }
+
+        fn add_processor(contract: &mut FoodOrder) {
+            set_caller(accounts().alice);
+            contract.add_payment_processor(accounts().eve, String::from("Eve Pay"));
+            contract.set_processor_fee_rate(1, 500);
+        }
+
+        fn authorize(contract: &mut FoodOrder, order_id: OrderId) {
+            add_processor(contract);
+            set_caller(accounts().charlie);
+            contract.select_payment_processor(order_id, 1);
+            set_caller(accounts().eve);
+            contract.authorize_charge(order_id, String::from("ch_1"), 5);
+        }
+
+        fn accept(contract: &mut FoodOrder, order_id: OrderId) {
+            deliver(contract, order_id);
+            set_caller(accounts().charlie);
+            contract.confrim_delivery(order_id);
+        }
+
+        #[ink::test]
+        fn captured_charge_pays_held_share_to_restaurant_and_processor() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            let charge = contract.get_charge_from_order(order_id);
+            assert_eq!(charge.processor_id, 1);
+            assert_eq!(charge.charge_reference, String::from("ch_1"));
+            assert_eq!(charge.amount, PRICE + DELIVERY_FEE);
+            assert_eq!(charge.status, ChargeStatus::Authorized);
+            assert_eq!(contract.get_payment_processor_from_id(1).processor_account, accounts.eve);
+
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            assert_eq!(contract.get_balance(accounts.bob), 0);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+            assert_eq!(contract.get_treasury_balance(), 12);
+
+            set_caller(accounts.eve);
+            contract.capture_charge(order_id);
+            assert_eq!(contract.get_charge_from_order(order_id).status, ChargeStatus::Captured);
+            assert_eq!(statuses(&contract, order_id).last(), Some(&OrderStatus::OrderCharged));
+            assert_eq!(contract.get_balance(accounts.bob), 85);
+            assert_eq!(contract.get_balance(accounts.eve), 5);
+        }
+
+        #[ink::test]
+        fn dispute_release_waits_for_capture() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::Release, false);
+            assert_eq!(contract.get_balance(accounts.bob), 0);
+            set_caller(accounts.eve);
+            contract.capture_charge(order_id);
+            assert_eq!(contract.get_balance(accounts.bob), 85);
+            assert_eq!(contract.get_treasury_balance(), 20);
+        }
+
+        #[ink::test]
+        fn refund_voids_authorized_charge() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.cancel_order(order_id);
+            assert_eq!(contract.get_charge_from_order(order_id).status, ChargeStatus::Voided);
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+            assert_eq!(contract.get_balance(accounts.eve), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can add payment processor!")]
+        fn add_payment_processor_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.add_payment_processor(accounts().eve, String::from("Eve Pay"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only payment processor can authorize charge!")]
+        fn authorize_charge_by_non_processor_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().eve);
+            contract.authorize_charge(order_id, String::from("ch_1"), 5);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Charge already exist!")]
+        fn authorize_charge_twice_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            contract.authorize_charge(order_id, String::from("ch_2"), 5);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Fee exceeds processor fee rate!")]
+        fn authorize_charge_fee_above_rate_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            add_processor(&mut contract);
+            set_caller(accounts().charlie);
+            contract.select_payment_processor(order_id, 1);
+            set_caller(accounts().eve);
+            contract.authorize_charge(order_id, String::from("ch_1"), 6);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Processor was not selected for this order!")]
+        fn authorize_charge_by_unselected_processor_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            add_processor(&mut contract);
+            set_caller(accounts().eve);
+            contract.authorize_charge(order_id, String::from("ch_1"), 5);
+        }
+
+        #[ink::test]
+        fn restaurant_can_select_payment_processor() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            add_processor(&mut contract);
+            set_caller(accounts().bob);
+            contract.select_payment_processor(order_id, 1);
+            set_caller(accounts().eve);
+            contract.authorize_charge(order_id, String::from("ch_1"), 5);
+            assert_eq!(contract.get_charge_from_order(order_id).processor_id, 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only customer or restaurant can select payment processor!")]
+        fn select_payment_processor_by_processor_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            add_processor(&mut contract);
+            set_caller(accounts().eve);
+            contract.select_payment_processor(order_id, 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set processor fee rate!")]
+        fn set_processor_fee_rate_by_non_manager_fails() {
+            let mut contract = setup();
+            add_processor(&mut contract);
+            set_caller(accounts().eve);
+            contract.set_processor_fee_rate(1, BASIS_POINTS as u16);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Fee rate exceeds 100%!")]
+        fn set_processor_fee_rate_above_100_percent_fails() {
+            let mut contract = setup();
+            add_processor(&mut contract);
+            contract.set_processor_fee_rate(1, BASIS_POINTS as u16 + 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is not accepted yet!")]
+        fn capture_charge_before_acceptance_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            contract.capture_charge(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not processor of this charge!")]
+        fn capture_charge_by_other_processor_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            accept(&mut contract, order_id);
+            set_caller(accounts().alice);
+            contract.add_payment_processor(accounts().frank, String::from("Frank Pay"));
+            set_caller(accounts().frank);
+            contract.capture_charge(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Charge is not authorized!")]
+        fn capture_voided_charge_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            set_caller(accounts().charlie);
+            contract.cancel_order(order_id);
+            set_caller(accounts().eve);
+            contract.capture_charge(order_id);
+        }
+
+        #[ink::test]
+        fn void_charge_after_capture_timeout_settles_order() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            set_caller(accounts.alice);
+            contract.set_capture_timeout(DAY);
+            accept(&mut contract, order_id);
+            set_timestamp(START + DAY);
+            set_caller(accounts.frank);
+            contract.void_charge(order_id);
+            assert_eq!(contract.get_charge_from_order(order_id).status, ChargeStatus::Voided);
+            assert_eq!(contract.get_balance(accounts.bob), 90);
+            assert_eq!(contract.get_balance(accounts.eve), 0);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+            assert_eq!(contract.get_treasury_balance(), 12);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Capture deadline has not passed!")]
+        fn void_charge_before_capture_timeout_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            set_caller(accounts().alice);
+            contract.set_capture_timeout(DAY);
+            accept(&mut contract, order_id);
+            set_timestamp(START + DAY - 1);
+            set_caller(accounts().frank);
+            contract.void_charge(order_id);
+        }
+
+        #[ink::test]
+        fn manager_can_void_charge_before_capture_timeout() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            accept(&mut contract, order_id);
+            set_caller(accounts.alice);
+            contract.void_charge(order_id);
+            assert_eq!(contract.get_charge_from_order(order_id).status, ChargeStatus::Voided);
+            assert_eq!(contract.get_balance(accounts.bob), 90);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Charge is not authorized!")]
+        fn capture_charge_after_void_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            authorize(&mut contract, order_id);
+            accept(&mut contract, order_id);
+            set_caller(accounts().alice);
+            contract.void_charge(order_id);
+            set_caller(accounts().eve);
+            contract.capture_charge(order_id);
+        }
+
+        const USD: CurrencyCode = *b"USD";
+
+        fn price_in_usd(contract: &mut FoodOrder, rate: u128) {
+            set_caller(accounts().alice);
+            contract.set_oracle(accounts().frank);
+            set_caller(accounts().frank);
+            contract.post_exchange_rate(USD, rate, START);
+            set_caller(accounts().bob);
+            contract.set_food_currency(1, Some(USD));
+        }
+
+        #[ink::test]
+        fn fiat_price_is_converted_at_submission() {
+            let mut contract = setup();
+            price_in_usd(&mut contract, RATE_PRECISION * 3 / 2);
+            assert_eq!(contract.get_food_price(1), 150);
+
+            set_caller(accounts().charlie);
+            set_value(150 + DELIVERY_FEE);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 0, None, None);
+            let order = contract.get_order_from_id(1);
+            assert_eq!(order.price, 150);
+            let quote = order.fiat_quote.unwrap();
+            assert_eq!(quote.currency, USD);
+            assert_eq!(quote.fiat_price, PRICE);
+            assert_eq!(quote.exchange_rate, contract.get_exchange_rate(USD));
+
+            set_timestamp(START + 1);
+            set_caller(accounts().frank);
+            contract.post_exchange_rate(USD, RATE_PRECISION * 2, START + 1);
+            assert_eq!(contract.get_food_price(1), 200);
+            assert_eq!(contract.get_order_from_id(1).price, 150);
+        }
+
+        #[ink::test]
+        fn fiat_price_rounds_up() {
+            let mut contract = setup();
+            price_in_usd(&mut contract, RATE_PRECISION / 3);
+            assert_eq!(contract.get_food_price(1), 34);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exchange rate is stale!")]
+        fn submit_order_with_stale_rate_fails() {
+            let mut contract = setup();
+            price_in_usd(&mut contract, RATE_PRECISION);
+            set_timestamp(START + 3_600_001);
+            submit(&mut contract);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exchange rate not exist!")]
+        fn submit_order_without_rate_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_food_currency(1, Some(USD));
+            submit(&mut contract);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only oracle can post exchange rate!")]
+        fn post_exchange_rate_by_non_oracle_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.post_exchange_rate(USD, RATE_PRECISION, START);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exchange rate is older than the current one!")]
+        fn post_older_exchange_rate_fails() {
+            let mut contract = setup();
+            price_in_usd(&mut contract, RATE_PRECISION);
+            set_caller(accounts().frank);
+            contract.post_exchange_rate(USD, RATE_PRECISION, START - 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Exchange rate is from the future!")]
+        fn post_future_exchange_rate_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_oracle(accounts().frank);
+            set_caller(accounts().frank);
+            contract.post_exchange_rate(USD, RATE_PRECISION, START + 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set oracle!")]
+        fn set_oracle_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().eve);
+            contract.set_oracle(accounts().eve);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not owner of this food!")]
+        fn set_currency_of_other_restaurant_food_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.add_restaurant(accounts().eve, String::from("Eve's"), String::from("4 Main St"), String::from("555-0103"));
+            set_caller(accounts().eve);
+            contract.set_food_currency(1, Some(USD));
+        }
+
+        const DAY: u64 = 86_400_000;
+        // Lunch time on the first weekday after START, a Tuesday evening.
+        const WEDNESDAY_NOON: u64 = 1_700_049_600_000;
+
+        fn schedule_lunch(contract: &mut FoodOrder, weekdays: Vec<u8>, occurrences: u32, prepaid: u128) {
+            set_caller(accounts().charlie);
+            set_value(prepaid);
+            contract.create_schedule(1, 1, plain("3 Main St"), weekdays, 720, occurrences);
+            set_value(0);
+        }
+
+        #[ink::test]
+        fn recurring_schedule_places_orders_when_due() {
+            let accounts = accounts();
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![0, 1, 2, 3, 4], 2, 2 * (PRICE + DELIVERY_FEE) + 5);
+            assert_eq!(contract.get_schedule_from_customer(1), vec![1]);
+            assert_eq!(contract.get_schedule_from_id(1).next_time, WEDNESDAY_NOON);
+
+            set_timestamp(WEDNESDAY_NOON);
+            set_caller(accounts.eve);
+            contract.trigger_schedule(1);
+            let order = contract.get_order_from_id(1);
+            assert_eq!(order.customer_id, 1);
+            assert_eq!(order.requested_time, WEDNESDAY_NOON);
+            assert_eq!(order.price, PRICE);
+            let schedule = contract.get_schedule_from_id(1);
+            assert_eq!(schedule.remaining, 1);
+            assert_eq!(schedule.prepaid, PRICE + DELIVERY_FEE + 5);
+            assert_eq!(schedule.next_time, WEDNESDAY_NOON + DAY);
+
+            set_timestamp(WEDNESDAY_NOON + DAY);
+            contract.trigger_schedule(1);
+            assert_eq!(contract.get_order_from_customer(1), vec![1, 2]);
+            let schedule = contract.get_schedule_from_id(1);
+            assert!(!schedule.active);
+            assert_eq!(schedule.prepaid, 0);
+            assert_eq!(contract.get_balance(accounts.charlie), 5);
+        }
+
+        #[ink::test]
+        fn schedule_skips_to_next_weekday() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![0, 4], 2, 2 * (PRICE + DELIVERY_FEE));
+            assert_eq!(contract.get_schedule_from_id(1).next_time, WEDNESDAY_NOON + 2 * DAY);
+            set_timestamp(WEDNESDAY_NOON + 2 * DAY + 1);
+            contract.trigger_schedule(1);
+            assert_eq!(contract.get_schedule_from_id(1).next_time, WEDNESDAY_NOON + 5 * DAY);
+        }
+
+        #[ink::test]
+        fn late_trigger_orders_for_now_and_skips_missed_slots() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![0, 1, 2, 3, 4], 2, 2 * (PRICE + DELIVERY_FEE));
+            set_timestamp(WEDNESDAY_NOON + 2 * DAY + 60_000);
+            contract.trigger_schedule(1);
+            assert_eq!(contract.get_order_from_id(1).requested_time, WEDNESDAY_NOON + 2 * DAY + 60_000);
+            let schedule = contract.get_schedule_from_id(1);
+            assert_eq!(schedule.remaining, 1);
+            assert_eq!(schedule.next_time, WEDNESDAY_NOON + 5 * DAY);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Schedule cost overflows!")]
+        fn create_schedule_with_overflowing_cost_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.update_food(1, String::from("Pizza"), String::from("Cheese"), u128::MAX / 2, FOOD_ETA);
+            schedule_lunch(&mut contract, vec![2], 3, PRICE + DELIVERY_FEE);
+        }
+
+        #[ink::test]
+        fn unservable_slot_is_skipped() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![2], 1, PRICE + DELIVERY_FEE);
+            set_caller(accounts().bob);
+            contract.set_food_availability(1, false);
+            set_timestamp(WEDNESDAY_NOON);
+            contract.trigger_schedule(1);
+            assert!(contract.get_order_from_customer(1).is_empty());
+            let schedule = contract.get_schedule_from_id(1);
+            assert!(schedule.active);
+            assert_eq!(schedule.remaining, 1);
+            assert_eq!(schedule.next_time, WEDNESDAY_NOON + 7 * DAY);
+        }
+
+        #[ink::test]
+        fn cancel_schedule_refunds_prepayment() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![2], 3, 3 * (PRICE + DELIVERY_FEE));
+            contract.cancel_schedule(1);
+            assert!(!contract.get_schedule_from_id(1).active);
+            assert_eq!(contract.get_balance(accounts().charlie), 3 * (PRICE + DELIVERY_FEE));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Schedule is not active!")]
+        fn trigger_cancelled_schedule_fails() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![2], 1, PRICE + DELIVERY_FEE);
+            contract.cancel_schedule(1);
+            set_timestamp(WEDNESDAY_NOON);
+            contract.trigger_schedule(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Schedule is not due yet!")]
+        fn trigger_schedule_before_due_fails() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![2], 1, PRICE + DELIVERY_FEE);
+            set_timestamp(WEDNESDAY_NOON - 1);
+            contract.trigger_schedule(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Prepayment does not cover the schedule!")]
+        fn create_underpaid_schedule_fails() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![2], 2, 2 * (PRICE + DELIVERY_FEE) - 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid schedule weekdays!")]
+        fn create_schedule_with_invalid_weekday_fails() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![7], 1, PRICE + DELIVERY_FEE);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "not customer of this schedule!")]
+        fn cancel_schedule_of_other_customer_fails() {
+            let mut contract = setup();
+            schedule_lunch(&mut contract, vec![2], 1, PRICE + DELIVERY_FEE);
+            set_caller(accounts().eve);
+            contract.add_customer(String::from("Eve"), plain("4 Main St"), plain("555-0103"));
+            contract.cancel_schedule(1);
+        }
+
+        /// Charlie organizes group order 1 with one food 1; Eve joins with
+        /// food 2, which Bob sells for 50 and takes longer to prepare.
+        fn open_group(contract: &mut FoodOrder) {
+            set_caller(accounts().bob);
+            contract.add_food(String::from("Salad"), String::from("Caesar"), 50, FOOD_ETA * 2);
+            set_caller(accounts().eve);
+            contract.add_customer(String::from("Eve"), plain("4 Main St"), plain("555-0103"));
+            set_caller(accounts().charlie);
+            contract.open_group_order(1, plain("3 Main St"));
+            contract.add_group_item(1, 1);
+            set_caller(accounts().eve);
+            contract.add_group_item(1, 2);
+        }
+
+        fn fund_group(contract: &mut FoodOrder) {
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.fund_group_share(1);
+            set_caller(accounts().eve);
+            set_value(50);
+            contract.fund_group_share(1);
+            set_value(0);
+        }
+
+        #[ink::test]
+        fn group_order_is_submitted_once_every_share_is_funded() {
+            let accounts = accounts();
+            let mut contract = setup();
+            open_group(&mut contract);
+            fund_group(&mut contract);
+            set_caller(accounts.charlie);
+            contract.submit_group_order(1);
+            let group = contract.get_group_order_from_id(1);
+            assert_eq!(group.shares.len(), 2);
+            let order_id = group.order_id.unwrap();
+            let order = contract.get_order_from_id(order_id);
+            assert_eq!(order.customer_id, 1);
+            assert_eq!(order.food_id, 2);
+            assert_eq!(order.price, PRICE + 50);
+            assert_eq!(order.delivery_fee, DELIVERY_FEE);
+            assert_eq!(order.group_id, Some(1));
+
+            deliver(&mut contract, order_id);
+            assert_eq!(contract.get_order_from_id(order_id).eta, FOOD_ETA * 2);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            assert_eq!(contract.get_balance(accounts.bob), 135);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+            assert_eq!(contract.get_treasury_balance(), 17);
+        }
+
+        #[ink::test]
+        fn cancel_group_order_refunds_each_participant() {
+            let accounts = accounts();
+            let mut contract = setup();
+            open_group(&mut contract);
+            fund_group(&mut contract);
+            set_caller(accounts.charlie);
+            contract.cancel_group_order(1);
+            assert_eq!(contract.get_group_order_from_id(1).status, GroupStatus::Cancelled);
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+            assert_eq!(contract.get_balance(accounts.eve), 50);
+        }
+
+        #[ink::test]
+        fn cancelled_group_order_refunds_payers_and_restocks() {
+            let accounts = accounts();
+            let mut contract = setup();
+            open_group(&mut contract);
+            set_caller(accounts.bob);
+            contract.set_food_stock(2, Some(5));
+            fund_group(&mut contract);
+            set_caller(accounts.charlie);
+            contract.submit_group_order(1);
+            assert_eq!(contract.get_food_from_id(2).stock, Some(4));
+            contract.cancel_order(1);
+            assert_eq!(contract.get_food_from_id(2).stock, Some(5));
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+            assert_eq!(contract.get_balance(accounts.eve), 50);
+        }
+
+        #[ink::test]
+        fn partial_refund_of_group_order_is_split_pro_rata() {
+            let accounts = accounts();
+            let mut contract = setup();
+            open_group(&mut contract);
+            fund_group(&mut contract);
+            set_caller(accounts.charlie);
+            contract.submit_group_order(1);
+            contract.open_dispute(1, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::PartialRefund(80), false);
+            assert_eq!(contract.get_balance(accounts.eve), 25);
+            assert_eq!(contract.get_balance(accounts.charlie), 55);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not all shares are funded!")]
+        fn submit_unfunded_group_order_fails() {
+            let mut contract = setup();
+            open_group(&mut contract);
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.fund_group_share(1);
+            set_value(0);
+            contract.submit_group_order(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only organizer can submit group order!")]
+        fn submit_group_order_by_participant_fails() {
+            let mut contract = setup();
+            open_group(&mut contract);
+            fund_group(&mut contract);
+            set_caller(accounts().eve);
+            contract.submit_group_order(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "you must pay your outstanding share!")]
+        fn fund_group_share_with_wrong_amount_fails() {
+            let mut contract = setup();
+            open_group(&mut contract);
+            set_caller(accounts().eve);
+            set_value(49);
+            contract.fund_group_share(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Group order is not open!")]
+        fn add_item_to_submitted_group_order_fails() {
+            let mut contract = setup();
+            open_group(&mut contract);
+            fund_group(&mut contract);
+            set_caller(accounts().charlie);
+            contract.submit_group_order(1);
+            contract.add_group_item(1, 1);
+        }
+
+        #[ink::test]
+        fn accepted_order_earns_loyalty_points() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.set_loyalty_rate(1_000);
+            let order_id = submit(&mut contract);
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            contract.confirm_order(order_id);
+            assert_eq!(contract.balance_of(accounts.charlie), 11);
+            assert_eq!(contract.get_order_from_id(order_id).points_earned, 11);
+
+            set_caller(accounts.alice);
+            contract.set_restaurant_loyalty_multiplier(1, Some(20_000));
+            assert_eq!(contract.get_loyalty_rate(1), 2_000);
+            let order_id = submit(&mut contract);
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            assert_eq!(contract.balance_of(accounts.charlie), 33);
+            assert_eq!(contract.total_supply(), 33);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Loyalty rate exceeds 100%!")]
+        fn loyalty_multiplier_above_full_rate_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_loyalty_rate(5_000);
+            contract.set_restaurant_loyalty_multiplier(1, Some(20_001));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set loyalty multiplier!")]
+        fn set_loyalty_multiplier_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_restaurant_loyalty_multiplier(1, Some(20_000));
+        }
+
+        #[ink::test]
+        fn raised_loyalty_rate_stays_capped_by_multiplier() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.set_loyalty_rate(5_000);
+            contract.set_restaurant_loyalty_multiplier(1, Some(20_000));
+            contract.set_loyalty_rate(10_000);
+            assert_eq!(contract.get_loyalty_rate(1), 10_000);
+            let order_id = submit(&mut contract);
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            assert_eq!(contract.balance_of(accounts.charlie), PRICE + DELIVERY_FEE);
+        }
+
+        #[ink::test]
+        fn redeemed_points_discount_the_food_price() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.set_loyalty_rate(10_000);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            assert_eq!(contract.balance_of(accounts.charlie), 110);
+            assert_eq!(contract.get_treasury_balance(), 12);
+
+            set_value(PRICE + DELIVERY_FEE - 10);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 10, None, None);
+            set_value(0);
+            let order_id = contract.get_order_from_customer(1).last().copied().unwrap();
+            assert_eq!(contract.get_order_from_id(order_id).points_redeemed, 10);
+            assert_eq!(contract.balance_of(accounts.charlie), 100);
+            assert_eq!(contract.get_treasury_balance(), 2);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            assert_eq!(contract.get_balance(accounts.bob), 180);
+            assert_eq!(contract.balance_of(accounts.charlie), 200);
+        }
+
+        #[ink::test]
+        fn dispute_refund_claws_back_earned_points() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.set_loyalty_rate(10_000);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+
+            set_value(PRICE + DELIVERY_FEE - 10);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 10, None, None);
+            set_value(0);
+            let order_id = contract.get_order_from_customer(1).last().copied().unwrap();
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            assert_eq!(contract.balance_of(accounts.charlie), 200);
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+            assert_eq!(contract.balance_of(accounts.charlie), 110);
+            assert_eq!(contract.get_balance(accounts.charlie), 100);
+            assert_eq!(contract.get_treasury_balance(), 12);
+        }
+
+        #[ink::test]
+        fn clawback_burns_only_points_still_held() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.set_loyalty_rate(10_000);
+            let order_id = submit(&mut contract);
+            set_caller(accounts.bob);
+            contract.confirm_order(order_id);
+            set_caller(accounts.charlie);
+            assert!(contract.transfer(accounts.eve, 100, Vec::new()).is_ok());
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+            assert_eq!(contract.balance_of(accounts.eve), 100);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not enough loyalty points!")]
+        fn redeem_more_points_than_held_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE - 10);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 10, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Redeemed points exceed food price!")]
+        fn redeem_more_points_than_price_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, PRICE + 1, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Treasury cannot fund the discount!")]
+        fn redeem_with_empty_treasury_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_loyalty_rate(10_000);
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE - 10);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 10, None, None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set loyalty rate!")]
+        fn set_loyalty_rate_by_non_manager_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            contract.set_loyalty_rate(1_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Loyalty rate exceeds 100%!")]
+        fn set_loyalty_rate_above_full_fails() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_loyalty_rate(10_001);
+        }
+
+        fn promo_hash(code: &str) -> Hash {
+            hash_secret(&String::from(code))
+        }
+
+        fn add_promo(contract: &mut FoodOrder, discount: PromoDiscount, max_uses: Option<u32>, max_uses_per_customer: Option<u32>) {
+            set_caller(accounts().alice);
+            contract.add_promo_code(promo_hash("SAVE"), None, discount, max_uses, max_uses_per_customer, START, START + DAY);
+        }
+
+        fn submit_with_promo(contract: &mut FoodOrder, discount: u128) {
+            set_value(PRICE + DELIVERY_FEE - discount);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 0, Some(String::from("SAVE")), None);
+            set_value(0);
+        }
+
+        #[ink::test]
+        fn promo_code_discounts_food_price() {
+            let accounts = accounts();
+            let mut contract = setup();
+            add_promo(&mut contract, PromoDiscount::Amount(10), None, None);
+            set_caller(accounts.charlie);
+            submit_with_promo(&mut contract, 10);
+            let order_id = contract.get_order_from_customer(1).last().copied().unwrap();
+            let order = contract.get_order_from_id(order_id);
+            assert_eq!(order.price, PRICE - 10);
+            assert_eq!(order.promo_code, Some(promo_hash("SAVE")));
+            assert_eq!(order.promo_discount, 10);
+            assert_eq!(contract.get_promo_code(promo_hash("SAVE")).uses, 1);
+            assert_eq!(contract.get_promo_code_uses(promo_hash("SAVE"), 1), 1);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(order_id);
+            assert_eq!(contract.get_balance(accounts.bob), 81);
+        }
+
+        #[ink::test]
+        fn restaurant_adds_percent_promo_code() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.bob);
+            contract.add_promo_code(promo_hash("SAVE"), Some(1), PromoDiscount::Percent(2_500), None, None, START, START + DAY);
+            assert_eq!(contract.get_promo_code(promo_hash("SAVE")).creator, accounts.bob);
+            set_caller(accounts.charlie);
+            submit_with_promo(&mut contract, 25);
+            assert_eq!(contract.get_order_from_id(1).price, PRICE - 25);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Promo code already used by customer!")]
+        fn promo_code_over_customer_cap_fails() {
+            let mut contract = setup();
+            add_promo(&mut contract, PromoDiscount::Amount(10), None, Some(1));
+            set_caller(accounts().charlie);
+            submit_with_promo(&mut contract, 10);
+            submit_with_promo(&mut contract, 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Promo code is used up!")]
+        fn promo_code_over_global_cap_fails() {
+            let mut contract = setup();
+            add_promo(&mut contract, PromoDiscount::Amount(10), Some(1), None);
+            set_caller(accounts().charlie);
+            submit_with_promo(&mut contract, 10);
+            set_caller(accounts().eve);
+            contract.add_customer(String::from("Eve"), plain("4 Main St"), plain("555-0103"));
+            submit_with_promo(&mut contract, 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Promo code is not valid now!")]
+        fn expired_promo_code_fails() {
+            let mut contract = setup();
+            add_promo(&mut contract, PromoDiscount::Amount(10), None, None);
+            set_timestamp(START + DAY);
+            set_caller(accounts().charlie);
+            submit_with_promo(&mut contract, 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Promo code is revoked!")]
+        fn revoked_promo_code_fails() {
+            let mut contract = setup();
+            add_promo(&mut contract, PromoDiscount::Amount(10), None, None);
+            contract.revoke_promo_code(promo_hash("SAVE"));
+            set_caller(accounts().charlie);
+            submit_with_promo(&mut contract, 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Promo code not exist!")]
+        fn unknown_promo_code_fails() {
+            let mut contract = setup();
+            set_caller(accounts().charlie);
+            submit_with_promo(&mut contract, 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Promo code not valid at this restaurant!")]
+        fn promo_code_at_other_restaurant_fails() {
+            let accounts = accounts();
+            let mut contract = setup();
+            set_caller(accounts.alice);
+            contract.add_restaurant(accounts.eve, String::from("Eve's"), String::from("4 Main St"), String::from("555-0103"));
+            contract.add_promo_code(promo_hash("SAVE"), Some(2), PromoDiscount::Amount(10), None, None, START, START + DAY);
+            set_caller(accounts.charlie);
+            submit_with_promo(&mut contract, 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Restaurant can only add promo code for itself!")]
+        fn restaurant_adds_global_promo_code_fails() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.add_promo_code(promo_hash("SAVE"), None, PromoDiscount::Amount(10), None, None, START, START + DAY);
+        }
+
+        const HANDOFF_CODE: &str = "4f2a9c1be07d5a38f6c2e91d0b7a4f63";
+
+        fn pick_up_with_handoff(contract: &mut FoodOrder) -> (OrderId, DeliveryId) {
+            set_caller(accounts().charlie);
+            set_value(PRICE + DELIVERY_FEE);
+            contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 0, None, Some(hash_secret(&String::from(HANDOFF_CODE))));
+            set_value(0);
+            let order_id = contract.get_order_from_customer(1).last().copied().unwrap();
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            set_caller(accounts().django);
+            contract.confirm_delivery(delivery_id);
+            (order_id, delivery_id)
+        }
+
+        #[ink::test]
+        fn handoff_code_completes_delivery() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let (order_id, delivery_id) = pick_up_with_handoff(&mut contract);
+            contract.complete_handoff(delivery_id, String::from(HANDOFF_CODE));
+            assert_eq!(contract.get_delivery_from_id(delivery_id).status, DeliveryStatus::HandedOff);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::DeliveryAcceptted);
+            assert_eq!(statuses(&contract, order_id).last(), Some(&OrderStatus::DeliveryAcceptted));
+            assert_eq!(contract.get_balance(accounts.bob), 90);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Handoff code does not match!")]
+        fn wrong_handoff_code_fails() {
+            let mut contract = setup();
+            let (_, delivery_id) = pick_up_with_handoff(&mut contract);
+            contract.complete_handoff(delivery_id, String::from("00000000000000000000000000000000"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Handoff code is too short!")]
+        fn short_handoff_code_fails() {
+            let mut contract = setup();
+            let (_, delivery_id) = pick_up_with_handoff(&mut contract);
+            contract.complete_handoff(delivery_id, String::from("4f2a"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery is not picked up by this deliver!")]
+        fn handoff_code_is_spent_after_use() {
+            let mut contract = setup();
+            let (_, delivery_id) = pick_up_with_handoff(&mut contract);
+            contract.complete_handoff(delivery_id, String::from(HANDOFF_CODE));
+            contract.complete_handoff(delivery_id, String::from(HANDOFF_CODE));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order has no handoff code!")]
+        fn handoff_without_code_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            set_caller(accounts().django);
+            contract.confirm_delivery(delivery_id);
+            contract.complete_handoff(delivery_id, String::from(HANDOFF_CODE));
+        }
+
+        fn courier_key(seed: u8) -> secp256k1::SecretKey {
+            secp256k1::SecretKey::from_slice(&[seed; 32]).unwrap()
+        }
+
+        fn sign_attestation(attestation: &DropOffAttestation, secret: &secp256k1::SecretKey) -> [u8; 65] {
+            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            let message = secp256k1::Message::from_slice(&attestation.signing_hash(&contract)).unwrap();
+            let (recovery_id, compact) = secp256k1::SECP256K1.sign_ecdsa_recoverable(&message, secret).serialize_compact();
+            let mut signature = [0; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            signature
+        }
+
+        fn pick_up_contactless(contract: &mut FoodOrder) -> DropOffAttestation {
+            let order_id = submit(contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            set_caller(accounts().django);
+            contract.confirm_delivery(delivery_id);
+            let public_key = courier_key(7).public_key(secp256k1::SECP256K1).serialize();
+            let mut address = [0; 20];
+            ink::env::ecdsa_to_eth_address(&public_key, &mut address).unwrap();
+            contract.set_deliver_attestation_key(Some(address));
+            set_timestamp(START + FOOD_ETA);
+            DropOffAttestation {
+                delivery_id,
+                order_id,
+                timestamp: START + FOOD_ETA,
+                location_hash: Some(Hash::from([4; 32])),
+                photo_hash: Some(Hash::from([5; 32])),
+            }
+        }
+
+        #[ink::test]
+        fn relayed_attestation_marks_drop_off() {
+            let accounts = accounts();
+            let mut contract = setup();
+            let attestation = pick_up_contactless(&mut contract);
+            let signature = sign_attestation(&attestation, &courier_key(7));
+            set_caller(accounts.eve);
+            contract.submit_attestation(attestation.clone(), signature);
+            let delivery_id = attestation.delivery_id;
+            assert_eq!(contract.get_delivery_from_id(delivery_id).status, DeliveryStatus::DroppedOff);
+            assert_eq!(contract.get_delivery_attestation(delivery_id), attestation);
+            assert_eq!(contract.get_order_from_id(attestation.order_id).status, OrderStatus::OrderDelivered);
+            set_caller(accounts.charlie);
+            contract.confrim_delivery(attestation.order_id);
+            assert_eq!(contract.get_balance(accounts.django), 8);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid attestation signature!")]
+        fn attestation_signed_by_other_key_fails() {
+            let mut contract = setup();
+            let attestation = pick_up_contactless(&mut contract);
+            let signature = sign_attestation(&attestation, &courier_key(8));
+            contract.submit_attestation(attestation, signature);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid attestation signature!")]
+        fn tampered_attestation_fails() {
+            let mut contract = setup();
+            let mut attestation = pick_up_contactless(&mut contract);
+            let signature = sign_attestation(&attestation, &courier_key(7));
+            attestation.photo_hash = None;
+            contract.submit_attestation(attestation, signature);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery is not picked up!")]
+        fn attestation_cannot_be_replayed() {
+            let mut contract = setup();
+            let attestation = pick_up_contactless(&mut contract);
+            let signature = sign_attestation(&attestation, &courier_key(7));
+            contract.submit_attestation(attestation.clone(), signature);
+            contract.submit_attestation(attestation, signature);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Attestation timestamp out of range!")]
+        fn attestation_from_the_future_fails() {
+            let mut contract = setup();
+            let mut attestation = pick_up_contactless(&mut contract);
+            attestation.timestamp = START + FOOD_ETA + 1;
+            let signature = sign_attestation(&attestation, &courier_key(7));
+            contract.submit_attestation(attestation, signature);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Deliver has no attestation key!")]
+        fn attestation_without_registered_key_fails() {
+            let mut contract = setup();
+            let attestation = pick_up_contactless(&mut contract);
+            let signature = sign_attestation(&attestation, &courier_key(7));
+            set_caller(accounts().django);
+            contract.set_deliver_attestation_key(None);
+            contract.submit_attestation(attestation, signature);
+        }
+
+        const BOND: u128 = 100;
+        const DELIVERY_TIMEOUT: u64 = 3_600_000;
+
+        /// Requires a bond of `BOND` locked for a day, slashes 20% of it and
+        /// times deliveries out after an hour. Django posts the bond.
+        fn bond_deliver(contract: &mut FoodOrder) {
+            set_caller(accounts().alice);
+            contract.set_bond_policy(BOND, DAY, 2_000, DELIVERY_TIMEOUT);
+            set_caller(accounts().django);
+            set_value(BOND);
+            contract.post_bond();
+            set_value(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Deliver bond is below minimum!")]
+        fn unbonded_deliver_cannot_pick_up() {
+            let mut contract = setup();
+            set_caller(accounts().alice);
+            contract.set_bond_policy(BOND, DAY, 2_000, DELIVERY_TIMEOUT);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+        }
+
+        #[ink::test]
+        fn bonded_deliver_picks_up() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::OrderDelivered);
+            assert_eq!(contract.get_deliver_bond(1).amount, BOND);
+            assert_eq!(contract.get_bond_policy().minimum_bond, BOND);
+        }
+
+        #[ink::test]
+        fn dispute_against_deliver_slashes_bond() {
+            let accounts = accounts();
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, true);
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE + 20);
+            assert_eq!(contract.get_deliver_bond(1).amount, BOND - 20);
+        }
+
+        #[ink::test]
+        fn dispute_not_against_deliver_keeps_bond() {
+            let accounts = accounts();
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            set_caller(accounts.charlie);
+            contract.open_dispute(order_id, Hash::from([1; 32]));
+            set_caller(accounts.alice);
+            contract.resolve_dispute(1, DisputeResolution::FullRefund, false);
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE);
+            assert_eq!(contract.get_deliver_bond(1).amount, BOND);
+        }
+
+        #[ink::test]
+        fn timed_out_delivery_refunds_and_slashes() {
+            let accounts = accounts();
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            let delivery_id = deliver(&mut contract, order_id);
+            set_timestamp(START + DELIVERY_TIMEOUT);
+            set_caller(accounts.eve);
+            contract.time_out_delivery(delivery_id);
+            assert_eq!(contract.get_delivery_from_id(delivery_id).status, DeliveryStatus::TimedOut);
+            assert_eq!(contract.get_order_from_id(order_id).status, OrderStatus::OrderRefunded);
+            assert_eq!(statuses(&contract, order_id).last(), Some(&OrderStatus::OrderRefunded));
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE + 20);
+            assert_eq!(contract.get_balance(accounts.django), 0);
+            assert_eq!(contract.get_deliver_bond(1).amount, BOND - 20);
+        }
+
+        #[ink::test]
+        fn dropped_off_delivery_still_times_out() {
+            let accounts = accounts();
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let attestation = pick_up_contactless(&mut contract);
+            let signature = sign_attestation(&attestation, &courier_key(7));
+            contract.submit_attestation(attestation.clone(), signature);
+            assert_eq!(contract.get_delivery_from_id(attestation.delivery_id).status, DeliveryStatus::DroppedOff);
+            set_timestamp(START + DELIVERY_TIMEOUT);
+            set_caller(accounts.eve);
+            contract.time_out_delivery(attestation.delivery_id);
+            assert_eq!(contract.get_delivery_from_id(attestation.delivery_id).status, DeliveryStatus::TimedOut);
+            assert_eq!(contract.get_order_from_id(attestation.order_id).status, OrderStatus::OrderRefunded);
+            assert_eq!(contract.get_balance(accounts.charlie), PRICE + DELIVERY_FEE + 20);
+            assert_eq!(contract.get_deliver_bond(1).amount, BOND - 20);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery has not timed out!")]
+        fn delivery_cannot_time_out_early() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            let delivery_id = deliver(&mut contract, order_id);
+            set_timestamp(START + DELIVERY_TIMEOUT - 1);
+            contract.time_out_delivery(delivery_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery has not timed out!")]
+        fn delivery_timeout_counts_from_pickup() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            contract.deliver_order(order_id);
+            let delivery_id = contract.get_delivery_from_order(order_id);
+            set_timestamp(START + 1_000);
+            set_caller(accounts().django);
+            contract.confirm_delivery(delivery_id);
+            assert_eq!(contract.get_delivery_from_id(delivery_id).picked_up_at, START + 1_000);
+            set_timestamp(START + DELIVERY_TIMEOUT);
+            contract.time_out_delivery(delivery_id);
+        }
+
+        #[ink::test]
+        fn bond_is_withdrawn_after_lock_period() {
+            let accounts = accounts();
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            test::set_account_balance::<DefaultEnvironment>(test::callee::<DefaultEnvironment>(), 1_000_000);
+            set_caller(accounts.django);
+            contract.deregister_deliver();
+            assert_eq!(contract.get_deliver_bond(1).unlocks_at, Some(START + DAY));
+            set_timestamp(START + DAY);
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts.django).unwrap();
+            contract.withdraw_bond();
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django).unwrap(), before + BOND);
+            assert_eq!(contract.get_deliver_bond(1).amount, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Bond is still locked!")]
+        fn bond_is_locked_after_deregistering() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            set_caller(accounts().django);
+            contract.deregister_deliver();
+            set_timestamp(START + DAY - 1);
+            contract.withdraw_bond();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Deliver must deregister first!")]
+        fn registered_deliver_cannot_withdraw_bond() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            set_caller(accounts().django);
+            contract.withdraw_bond();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Deliver has open deliveries!")]
+        fn deliver_with_open_delivery_cannot_deregister() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+            contract.deregister_deliver();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "only deliver can confirm devliery")]
+        fn deregistered_deliver_cannot_pick_up() {
+            let mut contract = setup();
+            bond_deliver(&mut contract);
+            set_caller(accounts().django);
+            contract.deregister_deliver();
+            let order_id = submit(&mut contract);
+            deliver(&mut contract, order_id);
+        }
+
+        #[ink::test]
+        fn market_starts_with_manager_and_config() {
+            let accounts = accounts();
+            set_caller(accounts.eve);
+            let mut contract = FoodOrder::new_market(accounts.alice, MarketConfig {
+                delivery_fee: DELIVERY_FEE,
+                food_commission: 1_000,
+                delivery_commission: 2_000,
+                loyalty_rate: 100,
+            });
+            assert_eq!(contract.get_upgrader(), accounts.eve);
+            assert_eq!(contract.get_delivery_fee(), DELIVERY_FEE);
+            assert_eq!(contract.get_food_commission(1), 1_000);
+            assert_eq!(contract.get_delivery_commission(), 2_000);
+            assert_eq!(contract.get_loyalty_rate(1), 100);
+            set_caller(accounts.alice);
+            contract.add_restaurant(accounts.bob, String::from("Bob's"), String::from("1 Main St"), String::from("555-0100"));
+            assert_eq!(contract.get_restaurant_from_id(1).restaurant_account, accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Commission exceeds 100%!")]
+        fn market_commission_cannot_exceed_total() {
+            FoodOrder::new_market(accounts().alice, MarketConfig {
+                food_commission: 10_001,
+                ..MarketConfig::default()
+            });
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only upgrader can set code!")]
+        fn only_upgrader_can_set_code() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.set_code(Hash::from([1; 32]));
+        }
+
+        #[ink::test]
+        fn get_eta_counts_down_and_saturates() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            assert_eq!(contract.get_eta(order_id), 0);
+            set_caller(accounts().bob);
+            contract.confirm_order(order_id);
+            assert_eq!(contract.get_eta(order_id), FOOD_ETA);
+            set_timestamp(START + 100_000);
+            assert_eq!(contract.get_eta(order_id), FOOD_ETA - 100_000);
+            set_timestamp(START + FOOD_ETA);
+            assert_eq!(contract.get_eta(order_id), 0);
+            set_timestamp(START + FOOD_ETA + 1);
+            assert_eq!(contract.get_eta(order_id), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order does not exist!")]
+        fn get_eta_of_unknown_order_fails() {
+            let contract = setup();
+            contract.get_eta(1);
+        }
+
+        #[ink::test]
+        fn get_order_all_clamps_ranges() {
+            let mut contract = setup();
+            assert!(contract.get_order_all(0, 10).is_empty());
+            submit(&mut contract);
+            submit(&mut contract);
+            submit(&mut contract);
+            assert_eq!(contract.get_order_all(0, 10).len(), 3);
+            assert_eq!(contract.get_order_all(1, 3).len(), 2);
+            assert_eq!(contract.get_order_all(1, 4).len(), 3);
+            assert_eq!(contract.get_order_all(3, 3).len(), 0);
+            assert_eq!(contract.get_order_all(3, u64::MAX).len(), 1);
+            assert!(contract.get_order_all(4, 10).is_empty());
+            assert!(contract.get_order_all(3, 1).is_empty());
+        }
+
+        #[ink::test]
+        fn get_food_all_clamps_ranges() {
+            let mut contract = setup();
+            set_caller(accounts().bob);
+            contract.add_food(String::from("Pasta"), String::new(), 80, FOOD_ETA);
+            assert_eq!(contract.get_food_all(0, 10).len(), 2);
+            assert_eq!(contract.get_food_all(1, 2).len(), 1);
+            assert_eq!(contract.get_food_all(2, 2).len(), 0);
+            assert_eq!(contract.get_food_all(2, u64::MAX).len(), 1);
+            assert!(contract.get_food_all(3, 10).is_empty());
+            assert!(contract.get_food_all(2, 1).is_empty());
+        }
+
+        #[ink::test]
+        fn get_delivery_all_clamps_ranges() {
+            let mut contract = setup();
+            assert!(contract.get_delivery_all(0, 10).is_empty());
+            let first = submit(&mut contract);
+            let second = submit(&mut contract);
+            deliver(&mut contract, first);
+            deliver(&mut contract, second);
+            assert_eq!(contract.get_delivery_all(0, 10).len(), 2);
+            assert_eq!(contract.get_delivery_all(1, 2).len(), 1);
+            assert_eq!(contract.get_delivery_all(2, u64::MAX).len(), 1);
+            assert!(contract.get_delivery_all(3, 10).is_empty());
+            assert!(contract.get_delivery_all(2, 1).is_empty());
+        }
+
+        #[ink::test]
+        fn event_schema_version_is_exposed() {
+            let contract = setup();
+            assert_eq!(contract.get_event_schema_version(), EVENT_SCHEMA_VERSION);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Restaurant does not exist!")]
+        fn get_unknown_restaurant_fails() {
+            let contract = setup();
+            contract.get_restaurant_from_id(2);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Deliver does not exist!")]
+        fn get_unknown_deliver_fails() {
+            let contract = setup();
+            contract.get_deliver_from_id(2);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Food does not exist!")]
+        fn get_unknown_food_fails() {
+            let contract = setup();
+            contract.get_food_from_id(2);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Delivery does not exist!")]
+        fn get_delivery_of_undelivered_order_fails() {
+            let mut contract = setup();
+            let order_id = submit(&mut contract);
+            contract.get_delivery_from_order(order_id);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Dispute does not exist!")]
+        fn get_unknown_dispute_fails() {
+            let contract = setup();
+            contract.get_dispute_from_id(1);
+        }
+    }
+
+    /// Runs against a local `substrate-contracts-node`:
+    ///
+    /// ```sh
+    /// CONTRACTS_NODE=substrate-contracts-node cargo test --features e2e-tests
+    /// ```
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::{
+            build_message,
+            subxt::{
+                blocks::ExtrinsicEvents,
+                Config,
+            },
+            AccountKeyring,
+        };
+        use logics::traits::{
+            BalanceService::balance_service_external::BalanceService,
+            CustomerService::customer_service_external::CustomerService,
+            DeliverService::deliver_service_external::DeliverService,
+            DeliveryManagerService::delivery_manager_service_external::DeliveryManagerService,
+            Get::get_external::Get,
+            OrderingManagerService::ordering_manager_service_external::OrderingManagerService,
+            RestaurantService::restaurant_service_external::RestaurantService,
+        };
+        use scale::Decode;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+        type Event = <FoodOrder as ink::reflect::ContractEventBase>::Type;
+
+        const PRICE: u128 = 1_000_000;
+        const DELIVERY_FEE: u128 = 100_000;
+
+        macro_rules! address_of {
+            ($account:ident) => {
+                ink_e2e::account_id(AccountKeyring::$account)
+            };
+        }
+
+        /// Decodes the `FoodOrder` events out of the `Contracts::ContractEmitted`
+        /// events of an extrinsic.
+        fn contract_events<C: Config>(events: &ExtrinsicEvents<C>) -> Vec<Event> {
+            events
+                .iter()
+                .filter_map(|event| event.ok())
+                .filter(|event| event.pallet_name() == "Contracts" && event.variant_name() == "ContractEmitted")
+                .map(|event| {
+                    let (_contract, data) = <(AccountId, Vec<u8>)>::decode(&mut event.field_bytes())
+                        .expect("ContractEmitted fields decode");
+                    Event::decode(&mut &data[..]).expect("contract event decodes")
+                })
+                .collect()
+        }
+
+        #[ink_e2e::test]
+        async fn order_lifecycle_pays_restaurant_and_deliver(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let constructor = FoodOrderRef::new();
+            let instantiated = client
+                .instantiate("contract", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("instantiate failed");
+            assert!(matches!(
+                contract_events(&instantiated.events)[..],
+                [Event::EventSchemaEvent(EventSchemaEvent { version: EVENT_SCHEMA_VERSION })]
+            ));
+            let contract = instantiated.account_id;
+
+            let add_restaurant = build_message::<FoodOrderRef>(contract.clone()).call(|food_order| {
+                food_order.add_restaurant(address_of!(Bob), String::from("Bob's"), String::from("1 Main St"), String::from("555-0100"))
+            });
+            client.call(&ink_e2e::alice(), add_restaurant, 0, None).await.expect("add_restaurant failed");
+            let add_deliver = build_message::<FoodOrderRef>(contract.clone()).call(|food_order| {
+                food_order.add_deliver(address_of!(Dave), String::from("Dave"), String::from("2 Main St"), String::from("555-0101"))
+            });
+            client.call(&ink_e2e::alice(), add_deliver, 0, None).await.expect("add_deliver failed");
+            let set_delivery_fee = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.set_delivery_fee(DELIVERY_FEE));
+            let result = client.call(&ink_e2e::alice(), set_delivery_fee, 0, None).await.expect("set_delivery_fee failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::SetDeliveryFeeEvent(SetDeliveryFeeEvent { delivery_fee: DELIVERY_FEE })]
+            ));
+            let set_commission = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.set_commission(1_000, 2_000));
+            client.call(&ink_e2e::alice(), set_commission, 0, None).await.expect("set_commission failed");
+
+            let add_food = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.add_food(String::from("Pizza"), String::from("Margherita"), PRICE, 600_000));
+            let result = client.call(&ink_e2e::bob(), add_food, 0, None).await.expect("add_food failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::AddFoodEvent(AddFoodEvent { food_id: 1, restaurant_id: 1, price: PRICE, .. })]
+            ));
+
+            let add_customer = build_message::<FoodOrderRef>(contract.clone()).call(|food_order| {
+                food_order.add_customer(
+                    String::from("Charlie"),
+                    PersonalData::Plain(String::from("3 Main St")),
+                    PersonalData::Plain(String::from("555-0102")),
+                )
+            });
+            let result = client.call(&ink_e2e::charlie(), add_customer, 0, None).await.expect("add_customer failed");
+            assert!(matches!(
+                &contract_events(&result.events)[..],
+                [Event::AddCustomerEvent(AddCustomerEvent { customer_id: 1, customer_account, .. })]
+                    if *customer_account == address_of!(Charlie)
+            ));
+
+            let contract_balance = client.balance(contract.clone()).await?;
+            let submit_order = build_message::<FoodOrderRef>(contract.clone()).call(|food_order| {
+                food_order.submit_order(
+                    1,
+                    1,
+                    PersonalData::Plain(String::from("3 Main St")),
+                    PersonalData::Plain(String::from("555-0102")),
+                    None,
+                    0,
+                    None,
+                    None,
+                )
+            });
+            let result = client
+                .call(&ink_e2e::charlie(), submit_order, PRICE + DELIVERY_FEE, None)
+                .await
+                .expect("submit_order failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::SubmitOrderEvent(SubmitOrderEvent { order_id: 1, food_id: 1, restaurant_id: 1, customer_id: 1, .. })]
+            ));
+            assert_eq!(client.balance(contract.clone()).await?, contract_balance + PRICE + DELIVERY_FEE);
+
+            let confirm_order = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.confirm_order(1));
+            let result = client.call(&ink_e2e::bob(), confirm_order, 0, None).await.expect("confirm_order failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::ConfirmOrderEvent(ConfirmOrderEvent { order_id: 1, eta: 600_000 })]
+            ));
+
+            let deliver_order = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.deliver_order(1));
+            let result = client.call(&ink_e2e::bob(), deliver_order, 0, None).await.expect("deliver_order failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::DeliverOrderEvent(DeliverOrderEvent { order_id: 1, delivery_id: 1, restaurant_id: 1, customer_id: 1, .. })]
+            ));
+
+            let confirm_delivery = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.confirm_delivery(1));
+            let result = client.call(&ink_e2e::dave(), confirm_delivery, 0, None).await.expect("confirm_delivery failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::PickupDeliveryEvent(PickupDeliveryEvent { delivery_id: 1, order_id: 1, deliver_id: 1 })]
+            ));
+
+            let accept_delivery = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.confrim_delivery(1));
+            let result = client.call(&ink_e2e::charlie(), accept_delivery, 0, None).await.expect("confrim_delivery failed");
+            let events = contract_events(&result.events);
+            assert!(matches!(
+                events[..],
+                [
+                    Event::CreditBalanceEvent(CreditBalanceEvent { order_id: 1, amount: 900_000, .. }),
+                    Event::CreditBalanceEvent(CreditBalanceEvent { order_id: 1, amount: 80_000, .. }),
+                    Event::SettleOrderEvent(SettleOrderEvent {
+                        order_id: 1,
+                        refund: 0,
+                        restaurant_amount: 900_000,
+                        deliver_amount: 80_000,
+                        held_amount: 0,
+                        platform_amount: 120_000,
+                    }),
+                    Event::ConfirmDeliveryEvent(ConfirmDeliveryEvent { order_id: 1 }),
+                ]
+            ));
+
+            let order = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.get_order_from_id(1));
+            let order = client.call_dry_run(&ink_e2e::alice(), &order, 0, None).await.return_value();
+            assert_eq!(order.status, logics::impls::types::OrderStatus::DeliveryAcceptted);
+            let restaurant_balance = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.get_balance(address_of!(Bob)));
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &restaurant_balance, 0, None).await.return_value(), 900_000);
+            let deliver_balance = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.get_balance(address_of!(Dave)));
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &deliver_balance, 0, None).await.return_value(), 80_000);
+            let treasury = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.get_treasury_balance());
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &treasury, 0, None).await.return_value(), 120_000);
+
+            let contract_balance = client.balance(contract.clone()).await?;
+            let withdraw = build_message::<FoodOrderRef>(contract.clone())
+                .call(|food_order| food_order.withdraw(900_000));
+            let result = client.call(&ink_e2e::bob(), withdraw, 0, None).await.expect("withdraw failed");
+            assert!(matches!(
+                contract_events(&result.events)[..],
+                [Event::WithdrawEvent(WithdrawEvent { amount: 900_000, .. })]
+            ));
+            assert_eq!(client.balance(contract.clone()).await?, contract_balance - 900_000);
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &restaurant_balance, 0, None).await.return_value(), 0);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn only_manager_can_add_restaurant(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let constructor = FoodOrderRef::new();
+            let contract = client
+                .instantiate("contract", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let add_restaurant = build_message::<FoodOrderRef>(contract.clone()).call(|food_order| {
+                food_order.add_restaurant(address_of!(Eve), String::from("Eve's"), String::new(), String::new())
+            });
+            let result = client.call(&ink_e2e::eve(), add_restaurant, 0, None).await;
+            assert!(result.is_err());
+
+            Ok(())
         }
     }
 }
@@ -0,0 +1,46 @@
+use crate::impls::types::{Data, PaymentData};
+use crate::traits::BalanceService::BalanceService;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait BalanceServiceEvents {
+
+    fn emit_withdraw_event(
+        &self,
+        account: AccountId,
+        amount: u128,
+    );
+}
+
+impl<T> BalanceService for T
+where
+    T: Storage<Data> + Storage<PaymentData>,
+{
+    default fn withdraw(
+        &mut self,
+        amount: u128,
+    ) {
+        let caller = T::env().caller();
+        let balance = self.data::<PaymentData>().balances.get(&caller).unwrap_or(0);
+        assert!(amount > 0, "Withdraw amount must be positive!");
+        assert!(amount <= balance, "Insufficient balance!");
+        self.data::<PaymentData>().balances.insert(&caller, &(balance - amount));
+        assert!(T::env().transfer(caller, amount).is_ok(), "Withdraw transfer failed!");
+        self.emit_withdraw_event(
+            caller,
+            amount,
+        );
+    }
+}
+
+impl<T> BalanceServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_withdraw_event(
+        &self,
+        account: AccountId,
+        amount: u128,
+    ) {}
+}
@@ -0,0 +1,196 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Ledger::Ledger,
+    Ordering::Ordering,
+    types::{Data, DeliveryData, BondPolicy, DeliverId, DeliveryId, DeliveryStatus, OrderId, OrderStatus, BASIS_POINTS},
+};
+use crate::traits::BondService::BondService;
+use ink::prelude::{
+    vec::Vec,
+};
+use openbrush::{
+    traits::{Storage, Timestamp},
+};
+
+pub trait BondServiceEvents {
+
+    fn emit_post_bond_event(
+        &self,
+        deliver_id: DeliverId,
+        amount: u128,
+        bond: u128,
+    );
+
+    fn emit_deregister_deliver_event(
+        &self,
+        deliver_id: DeliverId,
+        unlocks_at: Timestamp,
+    );
+
+    fn emit_withdraw_bond_event(
+        &self,
+        deliver_id: DeliverId,
+        amount: u128,
+    );
+
+    fn emit_time_out_delivery_event(
+        &self,
+        delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    );
+
+    fn emit_set_bond_policy_event(
+        &self,
+        policy: BondPolicy,
+    );
+}
+
+impl<T> BondService for T
+where
+    T: Storage<Data> + Storage<DeliveryData> + Ordering + Ledger,
+{
+    default fn post_bond(
+        &mut self,
+    ) {
+        let caller = T::env().caller();
+        assert!(self.data::<Data>().deliver_whitelist.contains(&caller), "only deliver can post bond");
+        let amount = T::env().transferred_value();
+        assert!(amount > 0, "Bond must be positive!");
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap();
+        let mut bond = self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).unwrap_or_default();
+        bond.amount += amount;
+        self.data::<DeliveryData>().deliver_bonds.insert(&deliver_id, &bond);
+        self.emit_post_bond_event(
+            deliver_id,
+            amount,
+            bond.amount,
+        );
+    }
+
+    default fn deregister_deliver(
+        &mut self,
+    ) {
+        let caller = T::env().caller();
+        assert!(self.data::<Data>().deliver_whitelist.contains(&caller), "only deliver can deregister");
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap();
+        let delivery_vec = self.data::<DeliveryData>().deliver_delivery_data.get(&deliver_id).unwrap_or(Vec::new());
+        for delivery_id in delivery_vec {
+            let order_id = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap().order_id;
+            assert!(self._order(order_id).status.is_closed(), "Deliver has open deliveries!");
+        }
+        self.data::<Data>().deliver_whitelist.retain(|account| *account != caller);
+        // The bond stays locked for the lock period after deregistering.
+        let unlocks_at = T::env().block_timestamp() + self.data::<DeliveryData>().bond_policy.lock_period;
+        let mut bond = self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).unwrap_or_default();
+        bond.unlocks_at = Some(unlocks_at);
+        self.data::<DeliveryData>().deliver_bonds.insert(&deliver_id, &bond);
+        self.emit_deregister_deliver_event(
+            deliver_id,
+            unlocks_at,
+        );
+    }
+
+    default fn withdraw_bond(
+        &mut self,
+    ) {
+        let caller = T::env().caller();
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap_or(0);
+        assert!(deliver_id != 0, "only deliver can withdraw bond");
+        let mut bond = self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).unwrap_or_default();
+        assert!(bond.unlocks_at.is_some(), "Deliver must deregister first!");
+        assert!(bond.unlocks_at.unwrap() <= T::env().block_timestamp(), "Bond is still locked!");
+        let amount = bond.amount;
+        assert!(amount > 0, "No bond to withdraw!");
+        bond.amount = 0;
+        self.data::<DeliveryData>().deliver_bonds.insert(&deliver_id, &bond);
+        assert!(T::env().transfer(caller, amount).is_ok(), "Bond transfer failed!");
+        self.emit_withdraw_bond_event(
+            deliver_id,
+            amount,
+        );
+    }
+
+    default fn time_out_delivery(
+        &mut self,
+        delivery_id: DeliveryId,
+    ) {
+        assert!(self.data::<DeliveryData>().delivery_data.contains(&delivery_id), "Delivery not exist!");
+        let mut delivery = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap();
+        // A drop-off attestation does not stop the clock, the customer still
+        // has to confirm the delivery before it times out.
+        assert!(delivery.status == DeliveryStatus::PickUp || delivery.status == DeliveryStatus::DroppedOff, "Delivery is not picked up!");
+        let order_id = delivery.order_id;
+        assert!(self._order(order_id).status == OrderStatus::OrderDelivered, "Order is not out for delivery!");
+        let delivery_timeout = self.data::<DeliveryData>().bond_policy.delivery_timeout;
+        assert!(delivery_timeout > 0, "Delivery timeout is not set!");
+        assert!(delivery.picked_up_at + delivery_timeout <= T::env().block_timestamp(), "Delivery has not timed out!");
+        delivery.status = DeliveryStatus::TimedOut;
+        self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+        let payers = self._time_out_order(order_id);
+        self._slash_deliver(delivery.deliver_id, order_id, payers);
+        self.emit_time_out_delivery_event(
+            delivery_id,
+            order_id,
+            delivery.deliver_id,
+        );
+    }
+
+    default fn set_bond_policy(
+        &mut self,
+        minimum_bond: u128,
+        lock_period: u64,
+        slash_rate: u16,
+        delivery_timeout: u64,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set bond policy!");
+        assert!(slash_rate as u128 <= BASIS_POINTS, "Slash rate exceeds 100%!");
+        let policy = BondPolicy {
+            minimum_bond,
+            lock_period,
+            slash_rate,
+            delivery_timeout,
+        };
+        self.data::<DeliveryData>().bond_policy = policy.clone();
+        self.emit_set_bond_policy_event(
+            policy,
+        );
+    }
+}
+
+impl<T> BondServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_post_bond_event(
+        &self,
+        deliver_id: DeliverId,
+        amount: u128,
+        bond: u128,
+    ) {}
+
+    default fn emit_deregister_deliver_event(
+        &self,
+        deliver_id: DeliverId,
+        unlocks_at: Timestamp,
+    ) {}
+
+    default fn emit_withdraw_bond_event(
+        &self,
+        deliver_id: DeliverId,
+        amount: u128,
+    ) {}
+
+    default fn emit_time_out_delivery_event(
+        &self,
+        delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    ) {}
+
+    default fn emit_set_bond_policy_event(
+        &self,
+        policy: BondPolicy,
+    ) {}
+}
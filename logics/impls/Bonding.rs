@@ -0,0 +1,92 @@
+use crate::impls::{
+    Ledger::Ledger,
+    Settlement::{pro_rata, SettlementEvents},
+    types::{Data, DeliveryData, DeliverId, OrderId, BASIS_POINTS},
+};
+use ink::prelude::vec::Vec;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait BondingEvents {
+
+    fn emit_slash_bond_event(
+        &self,
+        deliver_id: DeliverId,
+        order_id: OrderId,
+        amount: u128,
+    );
+}
+
+pub trait Bonding {
+
+    // Slashes the bond of a courier for an order and credits the slashed
+    // amount to the payers in proportion to what they paid.
+    fn _slash_deliver(
+        &mut self,
+        deliver_id: DeliverId,
+        order_id: OrderId,
+        payers: Vec<(AccountId, u128)>,
+    ) -> u128;
+
+    // The bond a courier has posted.
+    fn _bond_of(
+        &self,
+        deliver_id: DeliverId,
+    ) -> u128;
+}
+
+impl<T> Bonding for T
+where
+    T: Storage<Data> + Storage<DeliveryData> + Ledger,
+{
+    default fn _slash_deliver(
+        &mut self,
+        deliver_id: DeliverId,
+        order_id: OrderId,
+        payers: Vec<(AccountId, u128)>,
+    ) -> u128 {
+        let mut bond = self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).unwrap_or_default();
+        let amount = bond.amount * self.data::<DeliveryData>().bond_policy.slash_rate as u128 / BASIS_POINTS;
+        if amount == 0 {
+            return 0
+        }
+        bond.amount -= amount;
+        self.data::<DeliveryData>().deliver_bonds.insert(&deliver_id, &bond);
+        for (account, share) in pro_rata(amount, &payers) {
+            if share > 0 {
+                self._add_balance(account, share);
+                self.emit_credit_balance_event(
+                    account,
+                    order_id,
+                    share,
+                );
+            }
+        }
+        self.emit_slash_bond_event(
+            deliver_id,
+            order_id,
+            amount,
+        );
+        amount
+    }
+
+    default fn _bond_of(
+        &self,
+        deliver_id: DeliverId,
+    ) -> u128 {
+        self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).map_or(0, |bond| bond.amount)
+    }
+}
+
+impl<T> BondingEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_slash_bond_event(
+        &self,
+        deliver_id: DeliverId,
+        order_id: OrderId,
+        amount: u128,
+    ) {}
+}
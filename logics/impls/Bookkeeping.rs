@@ -0,0 +1,249 @@
+use crate::impls::types::{OrderingData, CustomerId, OrderId, OrderStatus, RestaurantId, StatusChange};
+use ink::prelude::{
+    vec::Vec,
+};
+use openbrush::{
+    traits::Storage,
+};
+
+pub trait Bookkeeping {
+
+    fn _append_restaurant_order(
+        &mut self,
+        restaurant_id: RestaurantId,
+        order_id: OrderId,
+    );
+
+    fn _append_customer_order(
+        &mut self,
+        customer_id: CustomerId,
+        order_id: OrderId,
+    );
+
+    fn _record_status(
+        &mut self,
+        order_id: OrderId,
+        status: OrderStatus,
+    );
+
+    fn _restaurant_orders(
+        &self,
+        restaurant_id: RestaurantId,
+    ) -> Vec<OrderId>;
+
+    fn _customer_orders(
+        &self,
+        customer_id: CustomerId,
+    ) -> Vec<OrderId>;
+
+    fn _status_history(
+        &self,
+        order_id: OrderId,
+    ) -> Vec<StatusChange>;
+}
+
+#[cfg(not(feature = "chain-extension"))]
+impl<T> Bookkeeping for T
+where
+    T: Storage<OrderingData>,
+{
+    default fn _append_restaurant_order(
+        &mut self,
+        restaurant_id: RestaurantId,
+        order_id: OrderId,
+    ) {
+        let mut restaurant_vec = self.data::<OrderingData>().restaurant_order_data.get(&restaurant_id).unwrap_or(Vec::new());
+        restaurant_vec.push(order_id);
+        self.data::<OrderingData>().restaurant_order_data.insert(&restaurant_id, &restaurant_vec);
+    }
+
+    default fn _append_customer_order(
+        &mut self,
+        customer_id: CustomerId,
+        order_id: OrderId,
+    ) {
+        let mut customer_vec = self.data::<OrderingData>().customer_order_data.get(&customer_id).unwrap_or(Vec::new());
+        customer_vec.push(order_id);
+        self.data::<OrderingData>().customer_order_data.insert(&customer_id, &customer_vec);
+    }
+
+    default fn _record_status(
+        &mut self,
+        order_id: OrderId,
+        status: OrderStatus,
+    ) {
+        let timestamp = T::env().block_timestamp();
+        let mut status_vec = self.data::<OrderingData>().order_status_data.get(&order_id).unwrap_or(Vec::new());
+        status_vec.push(StatusChange { status, timestamp });
+        self.data::<OrderingData>().order_status_data.insert(&order_id, &status_vec);
+    }
+
+    default fn _restaurant_orders(
+        &self,
+        restaurant_id: RestaurantId,
+    ) -> Vec<OrderId> {
+        self.data::<OrderingData>().restaurant_order_data.get(&restaurant_id).unwrap_or(Vec::new())
+    }
+
+    default fn _customer_orders(
+        &self,
+        customer_id: CustomerId,
+    ) -> Vec<OrderId> {
+        self.data::<OrderingData>().customer_order_data.get(&customer_id).unwrap_or(Vec::new())
+    }
+
+    default fn _status_history(
+        &self,
+        order_id: OrderId,
+    ) -> Vec<StatusChange> {
+        self.data::<OrderingData>().order_status_data.get(&order_id).unwrap_or(Vec::new())
+    }
+}
+
+#[cfg(feature = "chain-extension")]
+fn extension() -> <crate::traits::Bookkeeping::BookkeepingExtension as ink::ChainExtensionInstance>::Instance {
+    <crate::traits::Bookkeeping::BookkeepingExtension as ink::ChainExtensionInstance>::instantiate()
+}
+
+#[cfg(feature = "chain-extension")]
+impl<T> Bookkeeping for T
+where
+    T: Storage<OrderingData>,
+{
+    default fn _append_restaurant_order(
+        &mut self,
+        restaurant_id: RestaurantId,
+        order_id: OrderId,
+    ) {
+        extension().append_restaurant_order(restaurant_id, order_id).expect("Bookkeeping extension failed!");
+    }
+
+    default fn _append_customer_order(
+        &mut self,
+        customer_id: CustomerId,
+        order_id: OrderId,
+    ) {
+        extension().append_customer_order(customer_id, order_id).expect("Bookkeeping extension failed!");
+    }
+
+    default fn _record_status(
+        &mut self,
+        order_id: OrderId,
+        status: OrderStatus,
+    ) {
+        let timestamp = T::env().block_timestamp();
+        extension().record_status(order_id, status, timestamp).expect("Bookkeeping extension failed!");
+    }
+
+    default fn _restaurant_orders(
+        &self,
+        restaurant_id: RestaurantId,
+    ) -> Vec<OrderId> {
+        extension().restaurant_orders(restaurant_id).expect("Bookkeeping extension failed!")
+    }
+
+    default fn _customer_orders(
+        &self,
+        customer_id: CustomerId,
+    ) -> Vec<OrderId> {
+        extension().customer_orders(customer_id).expect("Bookkeeping extension failed!")
+    }
+
+    default fn _status_history(
+        &self,
+        order_id: OrderId,
+    ) -> Vec<StatusChange> {
+        extension().status_history(order_id).expect("Bookkeeping extension failed!")
+    }
+}
+
+// In-memory stand-in for the bookkeeping pallet, registered with
+// `ink::env::test::register_chain_extension` in off-chain tests.
+#[cfg(feature = "std")]
+pub mod mock {
+    use crate::impls::types::{CustomerId, OrderId, OrderStatus, RestaurantId, StatusChange};
+    use crate::traits::Bookkeeping::{
+        APPEND_CUSTOMER_ORDER, APPEND_RESTAURANT_ORDER, CUSTOMER_ORDERS,
+        RECORD_STATUS, RESTAURANT_ORDERS, STATUS_HISTORY,
+    };
+    use openbrush::traits::Timestamp;
+    use scale::{Decode, Encode};
+    use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+    #[derive(Default, Debug)]
+    pub struct MockState {
+        pub restaurant_orders: BTreeMap<RestaurantId, Vec<OrderId>>,
+        pub customer_orders: BTreeMap<CustomerId, Vec<OrderId>>,
+        pub status_history: BTreeMap<OrderId, Vec<StatusChange>>,
+    }
+
+    struct MockMethod {
+        func_id: u32,
+        state: Rc<RefCell<MockState>>,
+    }
+
+    impl ink::env::test::ChainExtension for MockMethod {
+        fn func_id(&self) -> u32 {
+            self.func_id
+        }
+
+        fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+            // The off-chain engine hands over the call input SCALE-encoded
+            // once more, i.e. with a compact length prefix.
+            let input = Vec::<u8>::decode(&mut &input[..]).unwrap();
+            let mut input = &input[..];
+            let mut state = self.state.borrow_mut();
+            match self.func_id {
+                APPEND_RESTAURANT_ORDER => {
+                    let (restaurant_id, order_id) = <(RestaurantId, OrderId)>::decode(&mut input).unwrap();
+                    state.restaurant_orders.entry(restaurant_id).or_default().push(order_id);
+                }
+                APPEND_CUSTOMER_ORDER => {
+                    let (customer_id, order_id) = <(CustomerId, OrderId)>::decode(&mut input).unwrap();
+                    state.customer_orders.entry(customer_id).or_default().push(order_id);
+                }
+                RECORD_STATUS => {
+                    let (order_id, status, timestamp) = <(OrderId, OrderStatus, Timestamp)>::decode(&mut input).unwrap();
+                    state.status_history.entry(order_id).or_default().push(StatusChange { status, timestamp });
+                }
+                RESTAURANT_ORDERS => {
+                    let restaurant_id = RestaurantId::decode(&mut input).unwrap();
+                    let orders = state.restaurant_orders.get(&restaurant_id).cloned().unwrap_or_default();
+                    orders.encode_to(output);
+                }
+                CUSTOMER_ORDERS => {
+                    let customer_id = CustomerId::decode(&mut input).unwrap();
+                    let orders = state.customer_orders.get(&customer_id).cloned().unwrap_or_default();
+                    orders.encode_to(output);
+                }
+                STATUS_HISTORY => {
+                    let order_id = OrderId::decode(&mut input).unwrap();
+                    let history = state.status_history.get(&order_id).cloned().unwrap_or_default();
+                    history.encode_to(output);
+                }
+                _ => return 1,
+            }
+            0
+        }
+    }
+
+    // Registers every bookkeeping function and returns the shared state so
+    // tests can inspect what the contract wrote.
+    pub fn register() -> Rc<RefCell<MockState>> {
+        let state = Rc::new(RefCell::new(MockState::default()));
+        for func_id in [
+            APPEND_RESTAURANT_ORDER,
+            APPEND_CUSTOMER_ORDER,
+            RECORD_STATUS,
+            RESTAURANT_ORDERS,
+            CUSTOMER_ORDERS,
+            STATUS_HISTORY,
+        ] {
+            ink::env::test::register_chain_extension(MockMethod {
+                func_id,
+                state: state.clone(),
+            });
+        }
+        state
+    }
+}
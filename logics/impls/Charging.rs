@@ -0,0 +1,64 @@
+use crate::impls::{
+    Bookkeeping::Bookkeeping,
+    types::{Data, OrderingData, Order, OrderId, OrderStatus},
+};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait Charging {
+
+    // The order a charge is for, wherever the orders are kept.
+    fn _charged_order(
+        &self,
+        order_id: OrderId,
+    ) -> Order;
+
+    // The customer and restaurant accounts of an order, who may pick the
+    // processor that charges it.
+    fn _order_accounts(
+        &self,
+        order_id: OrderId,
+    ) -> (AccountId, AccountId);
+
+    // Closes an accepted order once its processor captured the charge.
+    fn _charge_order(
+        &mut self,
+        order_id: OrderId,
+    );
+}
+
+impl<T> Charging for T
+where
+    T: Storage<Data> + Storage<OrderingData>,
+{
+    default fn _charged_order(
+        &self,
+        order_id: OrderId,
+    ) -> Order {
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        self.data::<OrderingData>().order_data.get(&order_id).unwrap()
+    }
+
+    default fn _order_accounts(
+        &self,
+        order_id: OrderId,
+    ) -> (AccountId, AccountId) {
+        let order = self._charged_order(order_id);
+        let customer_account = self.data::<OrderingData>().customers.get(&order.customer_id).unwrap().customer_account;
+        let restaurant_account = self.data::<Data>().restaurants.get(&order.restaurant_id).unwrap().restaurant_account;
+        (customer_account, restaurant_account)
+    }
+
+    default fn _charge_order(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.status == OrderStatus::DeliveryAcceptted, "Order is not accepted yet!");
+        let status = OrderStatus::OrderCharged;
+        order.status = status.clone();
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, status);
+    }
+}
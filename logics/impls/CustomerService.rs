@@ -1,105 +1,157 @@
-use crate::impls::types::{Data, OrderStatus, Customer, Order, FoodId, RestaurantId, OrderId, CustomerId};
+use crate::impls::{
+    Bonding::Bonding,
+    Bookkeeping::Bookkeeping,
+    Holding::Holding,
+    Ledger::Ledger,
+    Loyalty::Loyalty,
+    Ordering::Ordering,
+    Placement::Placement,
+    Pricing::Pricing,
+    Settlement::Settlement,
+    types::{Data, OrderingData, OrderStatus, Customer, FoodId, RestaurantId, OrderId, CustomerId, PersonalData},
+};
 use crate::traits::CustomerService::CustomerService;
 
 use ink::prelude::{
     string::String,
-    vec::Vec,
 };
 use openbrush::{
-    traits::Storage,
+    traits::{AccountId, Hash, Storage, Timestamp},
 };
 
 pub trait CustomerServiceEvents {
 
+    fn emit_add_customer_event(
+        &self,
+        customer_id: CustomerId,
+        customer_account: AccountId,
+        customer_name: String,
+    );
+
     fn emit_submit_order_event(
         &self,
         order_id: OrderId,
         food_id: FoodId,
         restaurant_id: RestaurantId,
         customer_id: CustomerId,
-        delivery_address: String,
-        phone_number: String,
+        delivery_address: PersonalData,
+        phone_number: PersonalData,
     );
 
     fn emit_confirm_delivery_event(
         &self,
         order_id: OrderId,
     );
+
+    fn emit_cancel_order_event(
+        &self,
+        order_id: OrderId,
+        customer_id: CustomerId,
+    );
 }
 
 impl<T> CustomerService for T
 where
-    T: Storage<Data>,
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding,
 {
     default fn add_customer(
         &mut self,
         customer_name: String,
-        customer_address: String,
-        phone_number: String
+        customer_address: PersonalData,
+        phone_number: PersonalData,
     ) {
         let customer_account = T::env().caller();
-        assert!(!self.data::<Data>().customer_whitelist.contains(&customer_account), "alread exist customer!");
+        assert!(!self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "alread exist customer!");
         let customer = Customer {
             customer_account,
             customer_name,
             customer_address,
             phone_number,
         };
-        let customer_id = self.data::<Data>().customer_id;
-        self.data::<Data>().customer_id += 1;
-        self.data::<Data>().customers.insert(&customer_id, &customer);
-        self.data::<Data>().customer_whitelist.push(customer_account);
-        self.data::<Data>().customer_account_id.insert(&customer_account, &customer_id);
+        let customer_id = self.data::<OrderingData>().customer_id;
+        self.data::<OrderingData>().customer_id += 1;
+        self.data::<OrderingData>().customers.insert(&customer_id, &customer);
+        self.data::<OrderingData>().customer_whitelist.push(customer_account);
+        self.data::<OrderingData>().customer_account_id.insert(&customer_account, &customer_id);
+        let customer_name = self.data::<OrderingData>().customers.get(&customer_id).unwrap().customer_name;
+        self.emit_add_customer_event(
+            customer_id,
+            customer_account,
+            customer_name,
+        );
     }
 
     default fn submit_order(
         &mut self, 
         food_id: FoodId,
         restaurant_id: RestaurantId,
-        delivery_address: String,
-        phone_number: String,
+        delivery_address: PersonalData,
+        phone_number: PersonalData,
+        requested_time: Option<Timestamp>,
+        redeem_points: u128,
+        promo_code: Option<String>,
+        handoff_hash: Option<Hash>,
     ) {
         let customer_account = T::env().caller();
-        assert!(self.data::<Data>().customer_whitelist.contains(&customer_account), "only customer can submit order!");
-        let customer_id = self.data::<Data>().customer_account_id.get(&customer_account).unwrap();
-        let order_id = self.data::<Data>().order_id;
-        let deliver_id = 0;
-        let price = T::env().transferred_value();
-        assert!(self.data::<Data>().food_data.get(&food_id).unwrap().price == price, "you must pay same of price!");
-        let eta = 0;
-        let timestamp = T::env().block_timestamp();
-        let status = OrderStatus::OrderSubmitted;
-        let order = Order {
-            food_id,
-            restaurant_id,
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can submit order!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        let mut food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        assert!(food.restaurant_id == restaurant_id, "Food not served by this restaurant!");
+        let restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        if let PersonalData::Sealed(_) = delivery_address {
+            assert!(delivery_address.is_sealed_for(&restaurant.encryption_key), "Delivery address must be sealed for the restaurant!");
+        }
+        let requested_time = requested_time.unwrap_or_default();
+        if requested_time == 0 {
+            assert!(restaurant.is_open_at(T::env().block_timestamp()), "Restaurant is closed!");
+        } else {
+            assert!(requested_time > T::env().block_timestamp(), "Requested time must be in the future!");
+            assert!(restaurant.is_open_at(requested_time), "Restaurant is closed at requested time!");
+        }
+        assert!(food.available, "Food is not available!");
+        if let Some(stock) = food.stock {
+            assert!(stock > 0, "Food is out of stock!");
+            food.stock = Some(stock - 1);
+            self.data::<OrderingData>().food_data.insert(&food_id, &food);
+        }
+        let (mut price, fiat_quote) = self._quote_food(&food);
+        let promo = promo_code.map(|code| self._quote_promo_code(&code, customer_id, restaurant_id, price));
+        if let Some((_, discount)) = promo {
+            price -= discount;
+        }
+        let delivery_fee = self.data::<Data>().delivery_fee;
+        if redeem_points > 0 {
+            // Each point takes one native unit off the food price, paid to
+            // the restaurant out of the treasury.
+            assert!(redeem_points <= price, "Redeemed points exceed food price!");
+            assert!(self._points_of(customer_account) >= redeem_points, "Not enough loyalty points!");
+            self._take_treasury(redeem_points);
+            self._burn_points(customer_account, redeem_points);
+        }
+        assert!(T::env().transferred_value() == price + delivery_fee - redeem_points, "you must pay same of price and delivery fee!");
+        let order_id = self._place_order(
             customer_id,
-            deliver_id,
-            delivery_address,
-            status,
-            timestamp,
-            price,
-            eta,
-        };
-        let order_id = self.data::<Data>().order_id;
-        self.data::<Data>().order_id += 1;
-        self.data::<Data>().order_data.insert(&order_id, &order);
-        let mut customer_vec = self.data::<Data>().customer_order_data.get(&customer_id).unwrap_or(Vec::new());
-        customer_vec.push(order_id);
-        self.data::<Data>().customer_order_data.insert(&customer_id, &customer_vec);
-        let mut restaurant_vec = self.data::<Data>().restaurant_order_data.get(&restaurant_id).unwrap_or(Vec::new());
-        restaurant_vec.push(order_id);
-        self.data::<Data>().restaurant_order_data.insert(&restaurant_id, &restaurant_vec);
-        let delivery_address = self.data::<Data>().order_data.get(&order_id).unwrap().delivery_address;
-        let customer_id = self.data::<Data>().order_data.get(&order_id).unwrap().customer_id;
-        let phone_number = self.data::<Data>().customers.get(&customer_id).unwrap().phone_number;
-        self.emit_submit_order_event(
-            order_id,
             food_id,
             restaurant_id,
-            customer_id,
             delivery_address,
-            phone_number,
+            requested_time,
+            price,
+            delivery_fee,
+            fiat_quote,
+            None,
+            redeem_points,
         );
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        order.handoff_hash = handoff_hash;
+        if let Some((code_hash, discount)) = promo {
+            order.promo_code = Some(code_hash);
+            order.promo_discount = discount;
+        }
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        if let Some((code_hash, discount)) = promo {
+            self._redeem_promo_code(code_hash, customer_id, order_id, discount);
+        }
     }
 
     default fn confrim_delivery(
@@ -107,35 +159,70 @@ where
         order_id: OrderId,
     ) {
         let customer_account = T::env().caller();
-        assert!(self.data::<Data>().customer_whitelist.contains(&customer_account), "only customer can submit order!");
-        let customer_id = self.data::<Data>().customer_account_id.get(&customer_account).unwrap();
-        assert!(self.data::<Data>().order_data.get(&order_id).unwrap().customer_id == customer_id, "not customer of this order!");
-        let mut order = self.data::<Data>().order_data.get(&order_id).unwrap();
-        let status = OrderStatus::DeliveryAcceptted;
-        order.status = status;
-        self.data::<Data>().order_data.insert(&order_id, &order);
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can submit order!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        assert!(self.data::<OrderingData>().order_data.get(&order_id).unwrap().customer_id == customer_id, "not customer of this order!");
+        let order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.status != OrderStatus::OrderDisputed, "Order is under dispute!");
+        assert!(!order.status.is_closed(), "Order is already settled!");
+        self._accept_delivery(order_id);
         self.emit_confirm_delivery_event(
             order_id,
         );
     }
+
+    default fn cancel_order(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can cancel order!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.customer_id == customer_id, "not customer of this order!");
+        assert!(order.status == OrderStatus::OrderSubmitted, "Order is already confirmed!");
+        self._restock_order(&order);
+        self._settle_order(order_id, order.price + order.delivery_fee);
+        order.status = OrderStatus::OrderCancelled;
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, OrderStatus::OrderCancelled);
+        self.emit_cancel_order_event(
+            order_id,
+            customer_id,
+        );
+    }
 }
 
 impl<T> CustomerServiceEvents for T
 where
     T: Storage<Data>,
 {
+    default fn emit_add_customer_event(
+        &self,
+        customer_id: CustomerId,
+        customer_account: AccountId,
+        customer_name: String,
+    ) {}
+
     default fn emit_submit_order_event(
         &self,
         order_id: OrderId,
         food_id: FoodId,
         restaurant_id: RestaurantId,
         customer_id: CustomerId,
-        delivery_address: String,
-        phone_number: String,
+        delivery_address: PersonalData,
+        phone_number: PersonalData,
     ) {}
 
     default fn emit_confirm_delivery_event(
         &self,
         order_id: OrderId,
     ) {}
+
+    default fn emit_cancel_order_event(
+        &self,
+        order_id: OrderId,
+        customer_id: CustomerId,
+    ) {}
 }
\ No newline at end of file
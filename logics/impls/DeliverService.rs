@@ -1,7 +1,12 @@
 use crate::impls::{
-    types::{ Data, DeliveryId, DeliveryStatus, OrderStatus },
+    Ordering::Ordering,
+    types::{ Data, DeliveryData, DeliverId, DeliveryId, DeliveryStatus, DropOffAttestation, EcdsaAddress, EcdsaSignature, OrderId, OrderStatus, PublicKey, hash_secret, MIN_HANDOFF_CODE_LEN },
 };
 use crate::traits::DeliverService::DeliverService;
+use ink::prelude::{
+    string::String,
+    vec::Vec,
+};
 use openbrush::{
     traits::Storage,
 };
@@ -11,12 +16,41 @@ pub trait DeliverServiceEvents {
     fn emit_pickup_delivery_event(
         &self,
         delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    );
+
+    fn emit_handoff_delivery_event(
+        &self,
+        delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    );
+
+    fn emit_drop_off_delivery_event(
+        &self,
+        delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+        attestation: DropOffAttestation,
+    );
+
+    fn emit_update_deliver_encryption_key_event(
+        &self,
+        deliver_id: DeliverId,
+        encryption_key: Option<PublicKey>,
+    );
+
+    fn emit_update_deliver_attestation_key_event(
+        &self,
+        deliver_id: DeliverId,
+        attestation_key: Option<EcdsaAddress>,
     );
 }
 
 impl<T> DeliverService for T
 where
-    T: Storage<Data>,
+    T: Storage<Data> + Storage<DeliveryData> + Ordering,
 {
     default fn confirm_delivery(
         &mut self,
@@ -24,17 +58,122 @@ where
     ) {
         let caller = T::env().caller();
         assert!(self.data::<Data>().deliver_whitelist.contains(&caller), "only deliver can confirm devliery");
-        assert!(self.data::<Data>().delivery_data.get(&delivery_id).unwrap().status == DeliveryStatus::Waiting, "this delivery is already picked up!");
-        let mut delivery = self.data::<Data>().delivery_data.get(&delivery_id).unwrap();
+        assert!(self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap().status != DeliveryStatus::Cancelled, "Delivery is cancelled!");
+        assert!(self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap().status == DeliveryStatus::Waiting, "this delivery is already picked up!");
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap();
+        let bond = self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).unwrap_or_default();
+        assert!(bond.amount >= self.data::<DeliveryData>().bond_policy.minimum_bond, "Deliver bond is below minimum!");
+        let order_id = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap().order_id;
+        let mut delivery = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap();
         let status = DeliveryStatus::PickUp;
         delivery.status = status;
-        self.data::<Data>().delivery_data.insert(&delivery_id, &delivery);
-        let order_id = self.data::<Data>().delivery_data.get(&delivery_id).unwrap().order_id;
-        let order_status = OrderStatus::OrderDelivered;
-        let mut order = self.data::<Data>().order_data.get(&order_id).unwrap();
-        order.status = order_status;
-        self.data::<Data>().order_data.insert(&order_id, &order);
-        self.emit_pickup_delivery_event(delivery_id);
+        delivery.deliver_id = deliver_id;
+        delivery.picked_up_at = T::env().block_timestamp();
+        self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+        let mut delivery_vec = self.data::<DeliveryData>().deliver_delivery_data.get(&deliver_id).unwrap_or(Vec::new());
+        delivery_vec.push(delivery_id);
+        self.data::<DeliveryData>().deliver_delivery_data.insert(&deliver_id, &delivery_vec);
+        self._pick_up_order(order_id, deliver_id);
+        self.emit_pickup_delivery_event(
+            delivery_id,
+            order_id,
+            deliver_id,
+        );
+    }
+
+    default fn complete_handoff(
+        &mut self,
+        delivery_id: DeliveryId,
+        handoff_code: String,
+    ) {
+        assert!(self.data::<DeliveryData>().delivery_data.contains(&delivery_id), "Delivery not exist!");
+        let caller = T::env().caller();
+        assert!(self.data::<Data>().deliver_whitelist.contains(&caller), "only deliver can complete handoff");
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap();
+        let mut delivery = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap();
+        assert!(delivery.status == DeliveryStatus::PickUp && delivery.deliver_id == deliver_id, "Delivery is not picked up by this deliver!");
+        let order_id = delivery.order_id;
+        let order = self._order(order_id);
+        assert!(order.status == OrderStatus::OrderDelivered, "Order is not out for delivery!");
+        assert!(order.handoff_hash.is_some(), "Order has no handoff code!");
+        assert!(handoff_code.len() >= MIN_HANDOFF_CODE_LEN, "Handoff code is too short!");
+        assert!(Some(hash_secret(&handoff_code)) == order.handoff_hash, "Handoff code does not match!");
+        // The code is spent once the delivery is handed off.
+        delivery.status = DeliveryStatus::HandedOff;
+        self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+        self._accept_delivery(order_id);
+        self.emit_handoff_delivery_event(
+            delivery_id,
+            order_id,
+            deliver_id,
+        );
+    }
+
+    default fn submit_attestation(
+        &mut self,
+        attestation: DropOffAttestation,
+        signature: EcdsaSignature,
+    ) {
+        let delivery_id = attestation.delivery_id;
+        assert!(self.data::<DeliveryData>().delivery_data.contains(&delivery_id), "Delivery not exist!");
+        let mut delivery = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap();
+        assert!(attestation.order_id == delivery.order_id, "Attestation does not match delivery!");
+        assert!(delivery.status == DeliveryStatus::PickUp, "Delivery is not picked up!");
+        let order_id = delivery.order_id;
+        assert!(self._order(order_id).status == OrderStatus::OrderDelivered, "Order is not out for delivery!");
+        assert!(delivery.timestamp <= attestation.timestamp && attestation.timestamp <= T::env().block_timestamp(), "Attestation timestamp out of range!");
+        let deliver_id = delivery.deliver_id;
+        let attestation_key = self.data::<Data>().delivers.get(&deliver_id).unwrap().attestation_key;
+        assert!(attestation_key.is_some(), "Deliver has no attestation key!");
+        let message_hash = attestation.signing_hash(&T::env().account_id());
+        let signer = T::env()
+            .ecdsa_recover(&signature, &message_hash)
+            .and_then(|public_key| T::env().ecdsa_to_eth_address(&public_key))
+            .ok();
+        assert!(signer == attestation_key, "Invalid attestation signature!");
+        // The customer still confirms or disputes, the attestation is the
+        // courier's evidence for either.
+        delivery.status = DeliveryStatus::DroppedOff;
+        self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+        self.data::<DeliveryData>().delivery_attestations.insert(&delivery_id, &attestation);
+        self.emit_drop_off_delivery_event(
+            delivery_id,
+            order_id,
+            deliver_id,
+            attestation,
+        );
+    }
+
+    default fn set_deliver_encryption_key(
+        &mut self,
+        encryption_key: Option<PublicKey>,
+    ) {
+        let caller = T::env().caller();
+        assert!(self.data::<Data>().deliver_whitelist.contains(&caller), "only deliver can set encryption key");
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap();
+        let mut deliver = self.data::<Data>().delivers.get(&deliver_id).unwrap();
+        deliver.encryption_key = encryption_key;
+        self.data::<Data>().delivers.insert(&deliver_id, &deliver);
+        self.emit_update_deliver_encryption_key_event(
+            deliver_id,
+            encryption_key,
+        );
+    }
+
+    default fn set_deliver_attestation_key(
+        &mut self,
+        attestation_key: Option<EcdsaAddress>,
+    ) {
+        let caller = T::env().caller();
+        assert!(self.data::<Data>().deliver_whitelist.contains(&caller), "only deliver can set attestation key");
+        let deliver_id = self.data::<Data>().deliver_account_id.get(&caller).unwrap();
+        let mut deliver = self.data::<Data>().delivers.get(&deliver_id).unwrap();
+        deliver.attestation_key = attestation_key;
+        self.data::<Data>().delivers.insert(&deliver_id, &deliver);
+        self.emit_update_deliver_attestation_key_event(
+            deliver_id,
+            attestation_key,
+        );
     }
 }
 
@@ -45,5 +184,34 @@ where
     default fn emit_pickup_delivery_event(
         &self,
         delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    ) {}
+
+    default fn emit_handoff_delivery_event(
+        &self,
+        delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    ) {}
+
+    default fn emit_drop_off_delivery_event(
+        &self,
+        delivery_id: DeliveryId,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+        attestation: DropOffAttestation,
+    ) {}
+
+    default fn emit_update_deliver_encryption_key_event(
+        &self,
+        deliver_id: DeliverId,
+        encryption_key: Option<PublicKey>,
+    ) {}
+
+    default fn emit_update_deliver_attestation_key_event(
+        &self,
+        deliver_id: DeliverId,
+        attestation_key: Option<EcdsaAddress>,
     ) {}
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::impls::Bonding::Bonding;
+use crate::impls::types::{Data, Deliver, DeliverId};
+use crate::traits::DeliveryManagerService::DeliveryManagerService;
+use ink::prelude::{
+    string::String,
+};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait DeliveryManagerServiceEvents {
+
+    fn emit_add_deliver_event(
+        &self,
+        deliver_id: DeliverId,
+        deliver_name: String,
+        deliver_address: String,
+        phone_number: String,
+    );
+}
+
+impl<T> DeliveryManagerService for T
+where
+    T: Storage<Data> + Bonding,
+{
+    default fn add_deliver(
+        &mut self,
+        deliver_account: AccountId,
+        deliver_name: String,
+        deliver_address: String,
+        phone_number: String,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can add deliver!");
+        assert!(!self.data::<Data>().deliver_whitelist.contains(&deliver_account), "already exist deliver!");
+        let previous_id = self.data::<Data>().deliver_account_id.get(&deliver_account).unwrap_or(0);
+        assert!(previous_id == 0 || self._bond_of(previous_id) == 0, "Deliver must withdraw bond first!");
+        let deliver_id = self.data::<Data>().deliver_id;
+        self.data::<Data>().deliver_id += 1;
+        let deliver = Deliver {
+            deliver_account,
+            deliver_name,
+            deliver_address,
+            phone_number,
+            encryption_key: None,
+            attestation_key: None,
+        };
+        self.data::<Data>().delivers.insert(&deliver_id, &deliver);
+        self.data::<Data>().deliver_account_id.insert(&deliver_account, &deliver_id);
+        self.data::<Data>().deliver_whitelist.push(deliver_account);
+        let deliver_name = self.data::<Data>().delivers.get(&deliver_id).unwrap().deliver_name;
+        let deliver_address = self.data::<Data>().delivers.get(&deliver_id).unwrap().deliver_address;
+        let phone_number = self.data::<Data>().delivers.get(&deliver_id).unwrap().phone_number;
+        self.emit_add_deliver_event(
+            deliver_id,
+            deliver_name,
+            deliver_address,
+            phone_number,
+        );
+    }
+}
+
+impl<T> DeliveryManagerServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_add_deliver_event(
+        &self,
+        deliver_id: DeliverId,
+        deliver_name: String,
+        deliver_address: String,
+        phone_number: String,
+    ) {}
+}
@@ -0,0 +1,54 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Dispatch::Dispatch,
+    Ledger::Ledger,
+    types::{Data, DeliveryData, CustomerId, DeliverId, DeliveryId, OrderId, PersonalData, RestaurantId},
+};
+use crate::traits::DeliveryWorkflow::DeliveryWorkflow;
+use ink::prelude::vec::Vec;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+impl<T> DeliveryWorkflow for T
+where
+    T: Storage<Data> + Storage<DeliveryData> + Ledger,
+{
+    default fn dispatch_delivery(
+        &mut self,
+        order_id: OrderId,
+        restaurant_id: RestaurantId,
+        customer_id: CustomerId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId {
+        assert!(T::env().caller() == self.data::<Data>().order_contract, "Only order contract can dispatch delivery!");
+        self._dispatch_delivery(order_id, restaurant_id, customer_id, delivery_address)
+    }
+
+    default fn update_delivery_address(
+        &mut self,
+        order_id: OrderId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId {
+        assert!(T::env().caller() == self.data::<Data>().order_contract, "Only order contract can update delivery address!");
+        self._readdress_delivery(order_id, delivery_address)
+    }
+
+    default fn cancel_delivery(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(T::env().caller() == self.data::<Data>().order_contract, "Only order contract can cancel delivery!");
+        self._cancel_delivery(order_id);
+    }
+
+    default fn slash_deliver(
+        &mut self,
+        deliver_id: DeliverId,
+        order_id: OrderId,
+        payers: Vec<(AccountId, u128)>,
+    ) -> u128 {
+        assert!(T::env().caller() == self.data::<Data>().order_contract, "Only order contract can slash deliver!");
+        self._slash_deliver(deliver_id, order_id, payers)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::impls::{
+    types::{DeliveryData, CustomerId, Delivery, DeliveryId, DeliveryStatus, OrderId, PersonalData, RestaurantId},
+};
+use openbrush::{
+    traits::Storage,
+};
+
+pub trait Dispatch {
+
+    // Opens the delivery of an order that is ready for pickup.
+    fn _dispatch_delivery(
+        &mut self,
+        order_id: OrderId,
+        restaurant_id: RestaurantId,
+        customer_id: CustomerId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId;
+
+    // Replaces the address couriers see for the delivery of an order.
+    fn _readdress_delivery(
+        &mut self,
+        order_id: OrderId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId;
+
+    // Withdraws the delivery of an order that was settled before any
+    // courier picked it up.
+    fn _cancel_delivery(
+        &mut self,
+        order_id: OrderId,
+    );
+}
+
+impl<T> Dispatch for T
+where
+    T: Storage<DeliveryData>,
+{
+    default fn _dispatch_delivery(
+        &mut self,
+        order_id: OrderId,
+        restaurant_id: RestaurantId,
+        customer_id: CustomerId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId {
+        let delivery_id = self.data::<DeliveryData>().delivery_id;
+        self.data::<DeliveryData>().delivery_id += 1;
+        let delivery = Delivery {
+            order_id,
+            restaurant_id,
+            customer_id,
+            deliver_id: 0,
+            delivery_address,
+            status: DeliveryStatus::Waiting,
+            timestamp: T::env().block_timestamp(),
+            picked_up_at: 0,
+        };
+        self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+        self.data::<DeliveryData>().order_delivery_data.insert(&order_id, &delivery_id);
+        delivery_id
+    }
+
+    default fn _readdress_delivery(
+        &mut self,
+        order_id: OrderId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId {
+        let delivery_id = self.data::<DeliveryData>().order_delivery_data.get(&order_id).unwrap();
+        let mut delivery = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap();
+        delivery.delivery_address = delivery_address;
+        self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+        delivery_id
+    }
+
+    default fn _cancel_delivery(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        if let Some(delivery_id) = self.data::<DeliveryData>().order_delivery_data.get(&order_id) {
+            let mut delivery = self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap();
+            if delivery.status == DeliveryStatus::Waiting {
+                delivery.status = DeliveryStatus::Cancelled;
+                self.data::<DeliveryData>().delivery_data.insert(&delivery_id, &delivery);
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Bookkeeping::Bookkeeping,
+    Dispatch::Dispatch,
+    Holding::Holding,
+    Ledger::Ledger,
+    Settlement::Settlement,
+    types::{Data, OrderingData, Dispute, DisputeId, DisputeResolution, DisputeStatus, OrderId, OrderStatus, CustomerId},
+};
+use crate::traits::DisputeService::DisputeService;
+use openbrush::{
+    traits::{AccountId, Hash, Storage},
+};
+
+pub trait DisputeServiceEvents {
+
+    fn emit_open_dispute_event(
+        &self,
+        dispute_id: DisputeId,
+        order_id: OrderId,
+        customer_id: CustomerId,
+        evidence_hash: Hash,
+    );
+
+    fn emit_respond_dispute_event(
+        &self,
+        dispute_id: DisputeId,
+        responder: AccountId,
+        response_hash: Hash,
+    );
+
+    fn emit_resolve_dispute_event(
+        &self,
+        dispute_id: DisputeId,
+        order_id: OrderId,
+        resolution: DisputeResolution,
+    );
+
+    fn emit_set_arbiter_event(
+        &self,
+        arbiter: AccountId,
+    );
+}
+
+impl<T> DisputeService for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding + Dispatch,
+{
+    default fn open_dispute(
+        &mut self,
+        order_id: OrderId,
+        evidence_hash: Hash,
+    ) {
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can open dispute!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.customer_id == customer_id, "not customer of this order!");
+        assert!(order.status != OrderStatus::OrderDisputed, "Order is already under dispute!");
+        assert!(!order.status.is_closed(), "Order is already settled!");
+        let dispute_id = self.data::<OrderingData>().dispute_id;
+        self.data::<OrderingData>().dispute_id += 1;
+        let dispute = Dispute {
+            order_id,
+            customer_id,
+            evidence_hash,
+            restaurant_response: None,
+            deliver_response: None,
+            status: DisputeStatus::Open,
+            resolution: None,
+            timestamp: T::env().block_timestamp(),
+        };
+        self.data::<OrderingData>().dispute_data.insert(&dispute_id, &dispute);
+        self.data::<OrderingData>().order_dispute_data.insert(&order_id, &dispute_id);
+        order.status = OrderStatus::OrderDisputed;
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, OrderStatus::OrderDisputed);
+        self.emit_open_dispute_event(
+            dispute_id,
+            order_id,
+            customer_id,
+            evidence_hash,
+        );
+    }
+
+    default fn respond_dispute(
+        &mut self,
+        dispute_id: DisputeId,
+        response_hash: Hash,
+    ) {
+        assert!(self.data::<OrderingData>().dispute_data.contains(&dispute_id), "Dispute not exist!");
+        let mut dispute = self.data::<OrderingData>().dispute_data.get(&dispute_id).unwrap();
+        assert!(dispute.status == DisputeStatus::Open, "Dispute is already resolved!");
+        let caller = T::env().caller();
+        let order = self.data::<OrderingData>().order_data.get(&dispute.order_id).unwrap();
+        let restaurant_account = self.data::<Data>().restaurants.get(&order.restaurant_id).unwrap().restaurant_account;
+        if caller == restaurant_account {
+            dispute.restaurant_response = Some(response_hash);
+        } else {
+            // The order contract of a split deployment only knows the couriers
+            // that picked up its orders.
+            let deliver_account = self.data::<Data>().delivers.get(&order.deliver_id).map(|deliver| deliver.deliver_account);
+            assert!(order.deliver_id != 0 && deliver_account == Some(caller), "Only restaurant or deliver of this order can respond!");
+            dispute.deliver_response = Some(response_hash);
+        }
+        self.data::<OrderingData>().dispute_data.insert(&dispute_id, &dispute);
+        self.emit_respond_dispute_event(
+            dispute_id,
+            caller,
+            response_hash,
+        );
+    }
+
+    default fn resolve_dispute(
+        &mut self,
+        dispute_id: DisputeId,
+        resolution: DisputeResolution,
+        deliver_at_fault: bool,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager || caller == self.data::<OrderingData>().arbiter, "Only manager or arbiter can resolve dispute!");
+        assert!(self.data::<OrderingData>().dispute_data.contains(&dispute_id), "Dispute not exist!");
+        let mut dispute = self.data::<OrderingData>().dispute_data.get(&dispute_id).unwrap();
+        assert!(dispute.status == DisputeStatus::Open, "Dispute is already resolved!");
+        let order_id = dispute.order_id;
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(!deliver_at_fault || order.deliver_id != 0, "Order has no deliver!");
+        let (refund, status) = match resolution {
+            DisputeResolution::FullRefund => (order.price + order.delivery_fee, OrderStatus::OrderRefunded),
+            DisputeResolution::PartialRefund(amount) => (amount, OrderStatus::OrderRefunded),
+            DisputeResolution::Release => (0, OrderStatus::DeliveryAcceptted),
+        };
+        self._settle_order(order_id, refund);
+        if deliver_at_fault {
+            self._slash_bond(order_id);
+        }
+        // A courier must not pick up an order that is already settled.
+        self._cancel_delivery(order_id);
+        order.status = status.clone();
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, status);
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolution = Some(resolution.clone());
+        self.data::<OrderingData>().dispute_data.insert(&dispute_id, &dispute);
+        self.emit_resolve_dispute_event(
+            dispute_id,
+            order_id,
+            resolution,
+        );
+    }
+
+    default fn set_arbiter(
+        &mut self,
+        arbiter: AccountId,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set arbiter!");
+        self.data::<OrderingData>().arbiter = arbiter;
+        self.emit_set_arbiter_event(
+            arbiter,
+        );
+    }
+}
+
+impl<T> DisputeServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_open_dispute_event(
+        &self,
+        dispute_id: DisputeId,
+        order_id: OrderId,
+        customer_id: CustomerId,
+        evidence_hash: Hash,
+    ) {}
+
+    default fn emit_respond_dispute_event(
+        &self,
+        dispute_id: DisputeId,
+        responder: AccountId,
+        response_hash: Hash,
+    ) {}
+
+    default fn emit_resolve_dispute_event(
+        &self,
+        dispute_id: DisputeId,
+        order_id: OrderId,
+        resolution: DisputeResolution,
+    ) {}
+
+    default fn emit_set_arbiter_event(
+        &self,
+        arbiter: AccountId,
+    ) {}
+}
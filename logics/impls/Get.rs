@@ -1,110 +1,177 @@
-use crate::impls::types::{Data, OrderId, Order, RestaurantId, CustomerId, Food, FoodId, DeliverId, DeliveryId, Delivery};
+use crate::impls::Bookkeeping::Bookkeeping;
+use crate::impls::Pricing::Pricing;
+use crate::impls::types::{Data, OrderingData, EVENT_SCHEMA_VERSION, OrderId, Order, StatusChange, Restaurant, RestaurantId, CustomerId, Food, FoodId, Deliver, DeliverId, Dispute, DisputeId, CurrencyCode, ExchangeRate, Schedule, ScheduleId, GroupId, GroupOrder, PromoCode, BASIS_POINTS};
 use crate::traits::Get::Get;
 use ink::prelude::{
     vec::Vec,
 };
 use openbrush::{
-    traits::{Storage},
+    traits::{Hash, Storage, Timestamp},
 };
 
 impl<T> Get for T
 where
-    T: Storage<Data>,
+    T: Storage<Data> + Storage<OrderingData>,
 {
+    default fn get_restaurant_from_id(&self, restaurant_id: RestaurantId) -> Restaurant {
+        assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
+        self.data::<Data>().restaurants.get(&restaurant_id).unwrap()
+    }
+
+    default fn get_deliver_from_id(&self, deliver_id: DeliverId) -> Deliver {
+        assert!(self.data::<Data>().delivers.contains(&deliver_id), "Deliver does not exist!");
+        self.data::<Data>().delivers.get(&deliver_id).unwrap()
+    }
+
     default fn get_eta(&self, order_id: OrderId) -> u64 {
-        assert!(self.data::<Data>().order_data.contains(&order_id), "Order does not exist!");
-        let timestamp = self.data::<Data>().order_data.get(&order_id).unwrap().timestamp;
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order does not exist!");
+        let timestamp = self.data::<OrderingData>().order_data.get(&order_id).unwrap().timestamp;
         let cur_timestamp = T::env().block_timestamp();
-        let order_eta = self.data::<Data>().order_data.get(&order_id).unwrap().eta;
-        let eta = order_eta - (cur_timestamp - timestamp);
-        if eta > 0 {
-            eta
-        } else {
-            0
-        }
+        let order_eta = self.data::<OrderingData>().order_data.get(&order_id).unwrap().eta;
+        order_eta.saturating_sub(cur_timestamp.saturating_sub(timestamp))
     }
 
     default fn get_order_from_id(&self, order_id: OrderId) -> Order {
-        assert!(self.data::<Data>().order_data.contains(&order_id), "Order does not exist!");
-        self.data::<Data>().order_data.get(&order_id).unwrap()
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order does not exist!");
+        self.data::<OrderingData>().order_data.get(&order_id).unwrap()
     }
 
     default fn get_order_from_restaurant(&self, restaurant_id: RestaurantId) -> Vec<u64> {
         assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
-        let order_data = self.data::<Data>().restaurant_order_data.get(&restaurant_id).unwrap_or(Vec::new());
+        let order_data = self._restaurant_orders(restaurant_id);
         order_data        
     }
 
     default fn get_order_from_customer(&self, customer_id: CustomerId) -> Vec<u64> {
-        assert!(self.data::<Data>().customers.contains(&customer_id), "Restaurant does not exist!");
+        assert!(self.data::<OrderingData>().customers.contains(&customer_id), "Restaurant does not exist!");
         let mut order_vec: Vec<Order> = Vec::new();
-        let order_data = self.data::<Data>().customer_order_data.get(&customer_id).unwrap_or(Vec::new());
+        let order_data = self._customer_orders(customer_id);
         order_data
     }
 
     default fn get_order_all(&self, from: u64, to: u64) -> Vec<Order> {
         let mut order_vec: Vec<Order> = Vec::new();
-        if to < self.data::<Data>().order_id {
+        if to < self.data::<OrderingData>().order_id {
             for i in from..to {
-                order_vec.push(self.data::<Data>().order_data.get(&i).unwrap());
+                if let Some(order) = self.data::<OrderingData>().order_data.get(&i) {
+                    order_vec.push(order);
+                }
             }
         } else {
-            for i in from..self.data::<Data>().order_id {
-                order_vec.push(self.data::<Data>().order_data.get(&i).unwrap());
+            for i in from..self.data::<OrderingData>().order_id {
+                if let Some(order) = self.data::<OrderingData>().order_data.get(&i) {
+                    order_vec.push(order);
+                }
             }
         }
         order_vec
     }
 
+    default fn get_order_status_history(&self, order_id: OrderId) -> Vec<StatusChange> {
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order does not exist!");
+        self._status_history(order_id)
+    }
+
     default fn get_food_from_id(&self, food_id: FoodId) -> Food {
-        assert!(self.data::<Data>().food_data.contains(&food_id), "Order does not exist!");
-        self.data::<Data>().food_data.get(&food_id).unwrap()
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food does not exist!");
+        self.data::<OrderingData>().food_data.get(&food_id).unwrap()
     }
 
     default fn get_food_from_restaurant(&self, restaurant_id: RestaurantId) -> Vec<u64> {
         assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
         let mut food_vec: Vec<Food> = Vec::new();
-        let food_data = self.data::<Data>().restaurant_food_data.get(&restaurant_id).unwrap_or(Vec::new());
+        let food_data = self.data::<OrderingData>().restaurant_food_data.get(&restaurant_id).unwrap_or(Vec::new());
         food_data        
     }
 
     default fn get_food_all(&self, from: u64, to: u64) -> Vec<Food> {
         let mut food_vec: Vec<Food> = Vec::new();
-        if to < self.data::<Data>().food_id {
+        if to < self.data::<OrderingData>().food_id {
             for i in from..to {
-                food_vec.push(self.data::<Data>().food_data.get(&i).unwrap());
+                if let Some(food) = self.data::<OrderingData>().food_data.get(&i) {
+                    food_vec.push(food);
+                }
             }
         } else {
-            for i in from..self.data::<Data>().food_id {
-                food_vec.push(self.data::<Data>().food_data.get(&i).unwrap());
+            for i in from..self.data::<OrderingData>().food_id {
+                if let Some(food) = self.data::<OrderingData>().food_data.get(&i) {
+                    food_vec.push(food);
+                }
             }
         }
         food_vec
     }
 
-    default fn get_delivery_from_id(&self, delivery_id: DeliveryId) -> Delivery {
-        assert!(self.data::<Data>().delivery_data.contains(&delivery_id), "Order does not exist!");
-        self.data::<Data>().delivery_data.get(&delivery_id).unwrap()
+    default fn get_dispute_from_id(&self, dispute_id: DisputeId) -> Dispute {
+        assert!(self.data::<OrderingData>().dispute_data.contains(&dispute_id), "Dispute does not exist!");
+        self.data::<OrderingData>().dispute_data.get(&dispute_id).unwrap()
     }
 
-    default fn get_delivery_from_deliver(&self, deliver_id: DeliverId) -> Vec<u64> {
-        assert!(self.data::<Data>().delivers.contains(&deliver_id), "Restaurant does not exist!");
-        let mut delivery_vec: Vec<Delivery> = Vec::new();
-        let delivery_data = self.data::<Data>().deliver_delivery_data.get(&deliver_id).unwrap_or(Vec::new());
-        delivery_data        
+    default fn get_dispute_from_order(&self, order_id: OrderId) -> Dispute {
+        assert!(self.data::<OrderingData>().order_dispute_data.contains(&order_id), "Dispute does not exist!");
+        let dispute_id = self.data::<OrderingData>().order_dispute_data.get(&order_id).unwrap();
+        self.data::<OrderingData>().dispute_data.get(&dispute_id).unwrap()
     }
 
-    default fn get_delivery_all(&self, from: u64, to: u64) -> Vec<Delivery> {
-        let mut delivery_vec: Vec<Delivery> = Vec::new();
-        if to < self.data::<Data>().delivery_id {
-            for i in from..to {
-                delivery_vec.push(self.data::<Data>().delivery_data.get(&i).unwrap());
-            }
-        } else {
-            for i in from..self.data::<Data>().delivery_id {
-                delivery_vec.push(self.data::<Data>().delivery_data.get(&i).unwrap());
-            }
-        }
-        delivery_vec
+    default fn get_schedule_from_id(&self, schedule_id: ScheduleId) -> Schedule {
+        assert!(self.data::<OrderingData>().schedule_data.contains(&schedule_id), "Schedule does not exist!");
+        self.data::<OrderingData>().schedule_data.get(&schedule_id).unwrap()
+    }
+
+    default fn get_schedule_from_customer(&self, customer_id: CustomerId) -> Vec<u64> {
+        assert!(self.data::<OrderingData>().customers.contains(&customer_id), "Customer does not exist!");
+        self.data::<OrderingData>().customer_schedule_data.get(&customer_id).unwrap_or(Vec::new())
+    }
+
+    default fn get_group_order_from_id(&self, group_id: GroupId) -> GroupOrder {
+        assert!(self.data::<OrderingData>().group_data.contains(&group_id), "Group order does not exist!");
+        self.data::<OrderingData>().group_data.get(&group_id).unwrap()
+    }
+
+    default fn get_exchange_rate(&self, currency: CurrencyCode) -> ExchangeRate {
+        assert!(self.data::<OrderingData>().exchange_rates.contains(&currency), "Exchange rate does not exist!");
+        self.data::<OrderingData>().exchange_rates.get(&currency).unwrap()
     }
+
+    default fn get_food_price(&self, food_id: FoodId) -> u128 {
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food does not exist!");
+        let food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        self._quote_food(&food).0
+    }
+
+    default fn get_delivery_fee(&self) -> u128 {
+        self.data::<Data>().delivery_fee
+    }
+
+    default fn get_food_commission(&self, restaurant_id: RestaurantId) -> u16 {
+        self.data::<Data>().restaurant_commission.get(&restaurant_id).unwrap_or(self.data::<Data>().food_commission)
+    }
+
+    default fn get_delivery_commission(&self) -> u16 {
+        self.data::<Data>().delivery_commission
+    }
+
+    default fn get_loyalty_rate(&self, restaurant_id: RestaurantId) -> u16 {
+        let multiplier = self.data::<OrderingData>().restaurant_loyalty_multiplier.get(&restaurant_id).unwrap_or(BASIS_POINTS as u16);
+        (self.data::<OrderingData>().loyalty_rate as u128 * multiplier as u128 / BASIS_POINTS).min(BASIS_POINTS) as u16
+    }
+
+    default fn get_promo_code(&self, code_hash: Hash) -> PromoCode {
+        assert!(self.data::<OrderingData>().promo_codes.contains(&code_hash), "Promo code does not exist!");
+        self.data::<OrderingData>().promo_codes.get(&code_hash).unwrap()
+    }
+
+    default fn get_promo_code_uses(&self, code_hash: Hash, customer_id: CustomerId) -> u32 {
+        self.data::<OrderingData>().promo_customer_uses.get(&(code_hash, customer_id)).unwrap_or(0)
+    }
+
+    default fn is_restaurant_open(&self, restaurant_id: RestaurantId, timestamp: Timestamp) -> bool {
+        assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
+        self.data::<Data>().restaurants.get(&restaurant_id).unwrap().is_open_at(timestamp)
+    }
+
+    default fn get_event_schema_version(&self) -> u16 {
+        EVENT_SCHEMA_VERSION
+    }
+
 }
\ No newline at end of file
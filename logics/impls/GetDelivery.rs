@@ -0,0 +1,62 @@
+use crate::impls::types::{Data, DeliveryData, OrderId, DeliverId, DeliveryId, Delivery, DropOffAttestation, BondPolicy, DeliverBond};
+use crate::traits::GetDelivery::GetDelivery;
+use ink::prelude::{
+    vec::Vec,
+};
+use openbrush::{
+    traits::Storage,
+};
+
+impl<T> GetDelivery for T
+where
+    T: Storage<Data> + Storage<DeliveryData>,
+{
+    default fn get_delivery_from_id(&self, delivery_id: DeliveryId) -> Delivery {
+        assert!(self.data::<DeliveryData>().delivery_data.contains(&delivery_id), "Order does not exist!");
+        self.data::<DeliveryData>().delivery_data.get(&delivery_id).unwrap()
+    }
+
+    default fn get_delivery_from_order(&self, order_id: OrderId) -> DeliveryId {
+        assert!(self.data::<DeliveryData>().order_delivery_data.contains(&order_id), "Delivery does not exist!");
+        self.data::<DeliveryData>().order_delivery_data.get(&order_id).unwrap()
+    }
+
+    default fn get_delivery_attestation(&self, delivery_id: DeliveryId) -> DropOffAttestation {
+        assert!(self.data::<DeliveryData>().delivery_attestations.contains(&delivery_id), "Attestation does not exist!");
+        self.data::<DeliveryData>().delivery_attestations.get(&delivery_id).unwrap()
+    }
+
+    default fn get_deliver_bond(&self, deliver_id: DeliverId) -> DeliverBond {
+        assert!(self.data::<Data>().delivers.contains(&deliver_id), "Deliver does not exist!");
+        self.data::<DeliveryData>().deliver_bonds.get(&deliver_id).unwrap_or_default()
+    }
+
+    default fn get_bond_policy(&self) -> BondPolicy {
+        self.data::<DeliveryData>().bond_policy.clone()
+    }
+
+    default fn get_delivery_from_deliver(&self, deliver_id: DeliverId) -> Vec<u64> {
+        assert!(self.data::<Data>().delivers.contains(&deliver_id), "Restaurant does not exist!");
+        let mut delivery_vec: Vec<Delivery> = Vec::new();
+        let delivery_data = self.data::<DeliveryData>().deliver_delivery_data.get(&deliver_id).unwrap_or(Vec::new());
+        delivery_data        
+    }
+
+    default fn get_delivery_all(&self, from: u64, to: u64) -> Vec<Delivery> {
+        let mut delivery_vec: Vec<Delivery> = Vec::new();
+        if to < self.data::<DeliveryData>().delivery_id {
+            for i in from..to {
+                if let Some(delivery) = self.data::<DeliveryData>().delivery_data.get(&i) {
+                    delivery_vec.push(delivery);
+                }
+            }
+        } else {
+            for i in from..self.data::<DeliveryData>().delivery_id {
+                if let Some(delivery) = self.data::<DeliveryData>().delivery_data.get(&i) {
+                    delivery_vec.push(delivery);
+                }
+            }
+        }
+        delivery_vec
+    }
+}
@@ -0,0 +1,28 @@
+use crate::impls::types::{PaymentData, OrderId, PaymentProcessor, PaymentProcessorId, Charge};
+use crate::traits::GetPayment::GetPayment;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+impl<T> GetPayment for T
+where
+    T: Storage<PaymentData>,
+{
+    default fn get_treasury_balance(&self) -> u128 {
+        self.data::<PaymentData>().treasury
+    }
+
+    default fn get_balance(&self, account: AccountId) -> u128 {
+        self.data::<PaymentData>().balances.get(&account).unwrap_or(0)
+    }
+
+    default fn get_payment_processor_from_id(&self, processor_id: PaymentProcessorId) -> PaymentProcessor {
+        assert!(self.data::<PaymentData>().payment_processors.contains(&processor_id), "Payment processor does not exist!");
+        self.data::<PaymentData>().payment_processors.get(&processor_id).unwrap()
+    }
+
+    default fn get_charge_from_order(&self, order_id: OrderId) -> Charge {
+        assert!(self.data::<PaymentData>().charge_data.contains(&order_id), "Charge does not exist!");
+        self.data::<PaymentData>().charge_data.get(&order_id).unwrap()
+    }
+}
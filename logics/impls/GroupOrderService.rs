@@ -0,0 +1,275 @@
+use crate::impls::{
+    Ledger::Ledger,
+    Placement::Placement,
+    Pricing::Pricing,
+    types::{Data, OrderingData, CustomerId, FoodId, GroupId, GroupItem, GroupOrder, GroupShare, GroupStatus, OrderId, PersonalData, RestaurantId},
+};
+use crate::traits::GroupOrderService::GroupOrderService;
+use ink::prelude::{
+    vec,
+    vec::Vec,
+};
+use openbrush::{
+    traits::Storage,
+};
+
+pub trait GroupOrderServiceEvents {
+
+    fn emit_open_group_order_event(
+        &self,
+        group_id: GroupId,
+        organizer_id: CustomerId,
+        restaurant_id: RestaurantId,
+    );
+
+    fn emit_add_group_item_event(
+        &self,
+        group_id: GroupId,
+        customer_id: CustomerId,
+        food_id: FoodId,
+        price: u128,
+    );
+
+    fn emit_fund_group_share_event(
+        &self,
+        group_id: GroupId,
+        customer_id: CustomerId,
+        amount: u128,
+    );
+
+    fn emit_submit_group_order_event(
+        &self,
+        group_id: GroupId,
+        order_id: OrderId,
+    );
+
+    fn emit_cancel_group_order_event(
+        &self,
+        group_id: GroupId,
+        refund: u128,
+    );
+}
+
+impl<T> GroupOrderService for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger,
+{
+    default fn open_group_order(
+        &mut self,
+        restaurant_id: RestaurantId,
+        delivery_address: PersonalData,
+    ) {
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can open group order!");
+        let organizer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
+        let restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        if let PersonalData::Sealed(_) = delivery_address {
+            assert!(delivery_address.is_sealed_for(&restaurant.encryption_key), "Delivery address must be sealed for the restaurant!");
+        }
+        let delivery_fee = self.data::<Data>().delivery_fee;
+        let group = GroupOrder {
+            organizer_id,
+            restaurant_id,
+            delivery_address,
+            delivery_fee,
+            items: Vec::new(),
+            shares: vec![GroupShare {
+                customer_id: organizer_id,
+                due: delivery_fee,
+                paid: 0,
+            }],
+            status: GroupStatus::Open,
+            order_id: None,
+            timestamp: T::env().block_timestamp(),
+        };
+        let group_id = self.data::<OrderingData>().group_id;
+        self.data::<OrderingData>().group_id += 1;
+        self.data::<OrderingData>().group_data.insert(&group_id, &group);
+        self.emit_open_group_order_event(
+            group_id,
+            organizer_id,
+            restaurant_id,
+        );
+    }
+
+    default fn add_group_item(
+        &mut self,
+        group_id: GroupId,
+        food_id: FoodId,
+    ) {
+        assert!(self.data::<OrderingData>().group_data.contains(&group_id), "Group order not exist!");
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can add group item!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        let mut group = self.data::<OrderingData>().group_data.get(&group_id).unwrap();
+        assert!(group.status == GroupStatus::Open, "Group order is not open!");
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        let food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        assert!(food.restaurant_id == group.restaurant_id, "Food not served by this restaurant!");
+        assert!(food.available, "Food is not available!");
+        let (price, _) = self._quote_food(&food);
+        group.items.push(GroupItem {
+            customer_id,
+            food_id,
+            price,
+        });
+        match group.shares.iter_mut().find(|share| share.customer_id == customer_id) {
+            Some(share) => share.due += price,
+            None => group.shares.push(GroupShare {
+                customer_id,
+                due: price,
+                paid: 0,
+            }),
+        }
+        self.data::<OrderingData>().group_data.insert(&group_id, &group);
+        self.emit_add_group_item_event(
+            group_id,
+            customer_id,
+            food_id,
+            price,
+        );
+    }
+
+    default fn fund_group_share(
+        &mut self,
+        group_id: GroupId,
+    ) {
+        assert!(self.data::<OrderingData>().group_data.contains(&group_id), "Group order not exist!");
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can fund group share!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        let mut group = self.data::<OrderingData>().group_data.get(&group_id).unwrap();
+        assert!(group.status == GroupStatus::Open, "Group order is not open!");
+        let share = group.shares.iter_mut().find(|share| share.customer_id == customer_id);
+        assert!(share.is_some(), "not participant of this group order!");
+        let share = share.unwrap();
+        let amount = T::env().transferred_value();
+        assert!(amount > 0 && amount == share.due - share.paid, "you must pay your outstanding share!");
+        share.paid += amount;
+        self.data::<OrderingData>().group_data.insert(&group_id, &group);
+        self.emit_fund_group_share_event(
+            group_id,
+            customer_id,
+            amount,
+        );
+    }
+
+    default fn submit_group_order(
+        &mut self,
+        group_id: GroupId,
+    ) {
+        assert!(self.data::<OrderingData>().group_data.contains(&group_id), "Group order not exist!");
+        let customer_account = T::env().caller();
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap_or(0);
+        let mut group = self.data::<OrderingData>().group_data.get(&group_id).unwrap();
+        assert!(customer_id != 0 && customer_id == group.organizer_id, "Only organizer can submit group order!");
+        assert!(group.status == GroupStatus::Open, "Group order is not open!");
+        assert!(!group.items.is_empty(), "Group order is empty!");
+        assert!(group.shares.iter().all(|share| share.paid == share.due), "Not all shares are funded!");
+        let restaurant = self.data::<Data>().restaurants.get(&group.restaurant_id).unwrap();
+        assert!(restaurant.is_open_at(T::env().block_timestamp()), "Restaurant is closed!");
+        // The order is prepared in the time of its slowest food.
+        let mut food_id = 0;
+        let mut eta = 0;
+        for item in group.items.iter() {
+            assert!(self.data::<OrderingData>().food_data.contains(&item.food_id), "Food not exist!");
+            let mut food = self.data::<OrderingData>().food_data.get(&item.food_id).unwrap();
+            assert!(food.available, "Food is not available!");
+            if let Some(stock) = food.stock {
+                assert!(stock > 0, "Food is out of stock!");
+                food.stock = Some(stock - 1);
+                self.data::<OrderingData>().food_data.insert(&item.food_id, &food);
+            }
+            if food_id == 0 || food.eta > eta {
+                food_id = item.food_id;
+                eta = food.eta;
+            }
+        }
+        let price = group.items.iter().map(|item| item.price).sum();
+        let order_id = self._place_order(
+            group.organizer_id,
+            food_id,
+            group.restaurant_id,
+            group.delivery_address.clone(),
+            0,
+            price,
+            group.delivery_fee,
+            None,
+            Some(group_id),
+            0,
+        );
+        group.status = GroupStatus::Submitted;
+        group.order_id = Some(order_id);
+        self.data::<OrderingData>().group_data.insert(&group_id, &group);
+        self.emit_submit_group_order_event(
+            group_id,
+            order_id,
+        );
+    }
+
+    default fn cancel_group_order(
+        &mut self,
+        group_id: GroupId,
+    ) {
+        assert!(self.data::<OrderingData>().group_data.contains(&group_id), "Group order not exist!");
+        let customer_account = T::env().caller();
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap_or(0);
+        let mut group = self.data::<OrderingData>().group_data.get(&group_id).unwrap();
+        assert!(customer_id != 0 && customer_id == group.organizer_id, "Only organizer can cancel group order!");
+        assert!(group.status == GroupStatus::Open, "Group order is not open!");
+        let mut refund = 0;
+        for share in group.shares.iter_mut() {
+            if share.paid > 0 {
+                let account = self.data::<OrderingData>().customers.get(&share.customer_id).unwrap().customer_account;
+                self._add_balance(account, share.paid);
+                refund += share.paid;
+            }
+        }
+        group.status = GroupStatus::Cancelled;
+        self.data::<OrderingData>().group_data.insert(&group_id, &group);
+        self.emit_cancel_group_order_event(
+            group_id,
+            refund,
+        );
+    }
+}
+
+impl<T> GroupOrderServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_open_group_order_event(
+        &self,
+        group_id: GroupId,
+        organizer_id: CustomerId,
+        restaurant_id: RestaurantId,
+    ) {}
+
+    default fn emit_add_group_item_event(
+        &self,
+        group_id: GroupId,
+        customer_id: CustomerId,
+        food_id: FoodId,
+        price: u128,
+    ) {}
+
+    default fn emit_fund_group_share_event(
+        &self,
+        group_id: GroupId,
+        customer_id: CustomerId,
+        amount: u128,
+    ) {}
+
+    default fn emit_submit_group_order_event(
+        &self,
+        group_id: GroupId,
+        order_id: OrderId,
+    ) {}
+
+    default fn emit_cancel_group_order_event(
+        &self,
+        group_id: GroupId,
+        refund: u128,
+    ) {}
+}
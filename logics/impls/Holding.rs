@@ -0,0 +1,68 @@
+use crate::impls::types::{PaymentData, ChargeHold, ChargeStatus, OrderId};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait Holding {
+
+    // An order with an authorized charge pays its restaurant when the
+    // payment processor captures the charge, not when it is settled.
+    fn _awaits_capture(
+        &self,
+        order_id: OrderId,
+    ) -> bool;
+
+    // Keeps the restaurant share of a settled order until its charge is
+    // captured or voided. A split deployment keeps it in the payment
+    // contract and moves the funds along.
+    fn _hold_for_capture(
+        &mut self,
+        order_id: OrderId,
+        restaurant_account: AccountId,
+        amount: u128,
+    );
+
+    // Voids the authorized charge of an order refunded in settlement.
+    fn _cancel_charge(
+        &mut self,
+        order_id: OrderId,
+    );
+}
+
+impl<T> Holding for T
+where
+    T: Storage<PaymentData>,
+{
+    default fn _awaits_capture(
+        &self,
+        order_id: OrderId,
+    ) -> bool {
+        self.data::<PaymentData>().charge_data.get(&order_id).map_or(false, |charge| charge.status == ChargeStatus::Authorized)
+    }
+
+    default fn _hold_for_capture(
+        &mut self,
+        order_id: OrderId,
+        restaurant_account: AccountId,
+        amount: u128,
+    ) {
+        let hold = ChargeHold {
+            restaurant_account,
+            amount,
+            timestamp: T::env().block_timestamp(),
+        };
+        self.data::<PaymentData>().charge_holds.insert(&order_id, &hold);
+    }
+
+    default fn _cancel_charge(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        if let Some(mut charge) = self.data::<PaymentData>().charge_data.get(&order_id) {
+            if charge.status == ChargeStatus::Authorized {
+                charge.status = ChargeStatus::Voided;
+                self.data::<PaymentData>().charge_data.insert(&order_id, &charge);
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::impls::types::PaymentData;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait Ledger {
+
+    // Withdrawable balances and the platform treasury. A split deployment
+    // keeps them in the payment contract and moves the funds along.
+    fn _add_balance(
+        &mut self,
+        account: AccountId,
+        amount: u128,
+    );
+
+    fn _add_treasury(
+        &mut self,
+        amount: u128,
+    );
+
+    // Pays out of the treasury to this contract.
+    fn _take_treasury(
+        &mut self,
+        amount: u128,
+    );
+
+    fn _treasury(
+        &self,
+    ) -> u128;
+}
+
+impl<T> Ledger for T
+where
+    T: Storage<PaymentData>,
+{
+    default fn _add_balance(
+        &mut self,
+        account: AccountId,
+        amount: u128,
+    ) {
+        let balance = self.data::<PaymentData>().balances.get(&account).unwrap_or(0);
+        self.data::<PaymentData>().balances.insert(&account, &(balance + amount));
+    }
+
+    default fn _add_treasury(
+        &mut self,
+        amount: u128,
+    ) {
+        self.data::<PaymentData>().treasury += amount;
+    }
+
+    default fn _take_treasury(
+        &mut self,
+        amount: u128,
+    ) {
+        assert!(self.data::<PaymentData>().treasury >= amount, "Treasury cannot fund the discount!");
+        self.data::<PaymentData>().treasury -= amount;
+    }
+
+    default fn _treasury(
+        &self,
+    ) -> u128 {
+        self.data::<PaymentData>().treasury
+    }
+}
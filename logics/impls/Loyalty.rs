@@ -0,0 +1,115 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Holding::Holding,
+    Ledger::Ledger,
+    Settlement::{pro_rata, Settlement},
+    types::{Data, OrderingData, OrderId, BASIS_POINTS},
+};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait Loyalty {
+
+    // Balances of the loyalty token. The contract keeps them in its PSP22
+    // storage, without it no points are ever held.
+    fn _mint_points(
+        &mut self,
+        account: AccountId,
+        amount: u128,
+    );
+
+    fn _burn_points(
+        &mut self,
+        account: AccountId,
+        amount: u128,
+    );
+
+    fn _points_of(
+        &self,
+        account: AccountId,
+    ) -> u128;
+
+    // Mints the points an accepted order earns to whoever paid for it.
+    fn _award_points(
+        &mut self,
+        order_id: OrderId,
+    );
+
+    // Gives back the refunded share of the redeemed points and claws back
+    // the same share of the earned ones, as far as the payers still hold.
+    fn _refund_points(
+        &mut self,
+        order_id: OrderId,
+        refund: u128,
+    );
+}
+
+impl<T> Loyalty for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding,
+{
+    default fn _mint_points(
+        &mut self,
+        account: AccountId,
+        amount: u128,
+    ) {}
+
+    default fn _burn_points(
+        &mut self,
+        account: AccountId,
+        amount: u128,
+    ) {}
+
+    default fn _points_of(
+        &self,
+        account: AccountId,
+    ) -> u128 {
+        0
+    }
+
+    default fn _award_points(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        let multiplier = self.data::<OrderingData>().restaurant_loyalty_multiplier.get(&order.restaurant_id).unwrap_or(BASIS_POINTS as u16);
+        // The multiplier was capped against the rate it was set with, a
+        // later rate change must not push the effective rate past 100%.
+        let rate = (self.data::<OrderingData>().loyalty_rate as u128 * multiplier as u128).min(BASIS_POINTS * BASIS_POINTS);
+        let mut points_earned = 0;
+        for (customer_id, paid) in self._payers(&order) {
+            let points = paid * rate / BASIS_POINTS / BASIS_POINTS;
+            if points > 0 {
+                let customer_account = self.data::<OrderingData>().customers.get(&customer_id).unwrap().customer_account;
+                self._mint_points(customer_account, points);
+                points_earned += points;
+            }
+        }
+        order.points_earned = points_earned;
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+    }
+
+    default fn _refund_points(
+        &mut self,
+        order_id: OrderId,
+        refund: u128,
+    ) {
+        let order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        let total = order.price + order.delivery_fee;
+        let restored = order.points_redeemed * refund / total;
+        if restored > 0 {
+            let customer_account = self.data::<OrderingData>().customers.get(&order.customer_id).unwrap().customer_account;
+            self._mint_points(customer_account, restored);
+        }
+        let clawback = order.points_earned * refund / total;
+        for (customer_id, points) in pro_rata(clawback, &self._payers(&order)) {
+            let customer_account = self.data::<OrderingData>().customers.get(&customer_id).unwrap().customer_account;
+            let held = self._points_of(customer_account);
+            let points = if points < held { points } else { held };
+            if points > 0 {
+                self._burn_points(customer_account, points);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::impls::types::{Data, OrderingData, RestaurantId, BASIS_POINTS};
+use crate::traits::LoyaltyService::LoyaltyService;
+use openbrush::{
+    traits::Storage,
+};
+
+pub trait LoyaltyServiceEvents {
+
+    fn emit_set_loyalty_rate_event(
+        &self,
+        loyalty_rate: u16,
+    );
+
+    fn emit_set_loyalty_multiplier_event(
+        &self,
+        restaurant_id: RestaurantId,
+        multiplier: Option<u16>,
+    );
+}
+
+impl<T> LoyaltyService for T
+where
+    T: Storage<Data> + Storage<OrderingData>,
+{
+    default fn set_loyalty_rate(
+        &mut self,
+        loyalty_rate: u16,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set loyalty rate!");
+        assert!(loyalty_rate as u128 <= BASIS_POINTS, "Loyalty rate exceeds 100%!");
+        self.data::<OrderingData>().loyalty_rate = loyalty_rate;
+        self.emit_set_loyalty_rate_event(
+            loyalty_rate,
+        );
+    }
+
+    default fn set_restaurant_loyalty_multiplier(
+        &mut self,
+        restaurant_id: RestaurantId,
+        multiplier: Option<u16>,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set loyalty multiplier!");
+        assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
+        match multiplier {
+            Some(multiplier) => {
+                let rate = self.data::<OrderingData>().loyalty_rate as u128 * multiplier as u128;
+                assert!(rate <= BASIS_POINTS * BASIS_POINTS, "Loyalty rate exceeds 100%!");
+                self.data::<OrderingData>().restaurant_loyalty_multiplier.insert(&restaurant_id, &multiplier);
+            }
+            None => self.data::<OrderingData>().restaurant_loyalty_multiplier.remove(&restaurant_id),
+        }
+        self.emit_set_loyalty_multiplier_event(
+            restaurant_id,
+            multiplier,
+        );
+    }
+}
+
+impl<T> LoyaltyServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_set_loyalty_rate_event(
+        &self,
+        loyalty_rate: u16,
+    ) {}
+
+    default fn emit_set_loyalty_multiplier_event(
+        &self,
+        restaurant_id: RestaurantId,
+        multiplier: Option<u16>,
+    ) {}
+}
@@ -0,0 +1,101 @@
+use crate::impls::types::{Data, OrderingData, CurrencyCode, ExchangeRate};
+use crate::traits::OracleService::OracleService;
+use openbrush::{
+    traits::{AccountId, Storage, Timestamp},
+};
+
+pub trait OracleServiceEvents {
+
+    fn emit_post_exchange_rate_event(
+        &self,
+        currency: CurrencyCode,
+        rate: u128,
+        timestamp: Timestamp,
+    );
+
+    fn emit_set_oracle_event(
+        &self,
+        oracle: AccountId,
+    );
+
+    fn emit_set_max_rate_age_event(
+        &self,
+        max_rate_age: u64,
+    );
+}
+
+impl<T> OracleService for T
+where
+    T: Storage<Data> + Storage<OrderingData>,
+{
+    default fn post_exchange_rate(
+        &mut self,
+        currency: CurrencyCode,
+        rate: u128,
+        timestamp: Timestamp,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<OrderingData>().oracle, "Only oracle can post exchange rate!");
+        assert!(rate > 0, "Exchange rate must be positive!");
+        assert!(timestamp <= T::env().block_timestamp(), "Exchange rate is from the future!");
+        if let Some(current) = self.data::<OrderingData>().exchange_rates.get(&currency) {
+            assert!(timestamp > current.timestamp, "Exchange rate is older than the current one!");
+        }
+        let exchange_rate = ExchangeRate {
+            rate,
+            timestamp,
+        };
+        self.data::<OrderingData>().exchange_rates.insert(&currency, &exchange_rate);
+        self.emit_post_exchange_rate_event(
+            currency,
+            rate,
+            timestamp,
+        );
+    }
+
+    default fn set_oracle(
+        &mut self,
+        oracle: AccountId,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set oracle!");
+        self.data::<OrderingData>().oracle = oracle;
+        self.emit_set_oracle_event(
+            oracle,
+        );
+    }
+
+    default fn set_max_rate_age(
+        &mut self,
+        max_rate_age: u64,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set max rate age!");
+        self.data::<OrderingData>().max_rate_age = max_rate_age;
+        self.emit_set_max_rate_age_event(
+            max_rate_age,
+        );
+    }
+}
+
+impl<T> OracleServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_post_exchange_rate_event(
+        &self,
+        currency: CurrencyCode,
+        rate: u128,
+        timestamp: Timestamp,
+    ) {}
+
+    default fn emit_set_oracle_event(
+        &self,
+        oracle: AccountId,
+    ) {}
+
+    default fn emit_set_max_rate_age_event(
+        &self,
+        max_rate_age: u64,
+    ) {}
+}
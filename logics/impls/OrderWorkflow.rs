@@ -0,0 +1,66 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Charging::Charging,
+    Holding::Holding,
+    Ledger::Ledger,
+    Ordering::Ordering,
+    types::{Data, OrderingData, Deliver, DeliverId, OrderId, OrderStatus},
+};
+use crate::traits::OrderWorkflow::OrderWorkflow;
+use ink::prelude::vec::Vec;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+impl<T> OrderWorkflow for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding + Charging,
+{
+    default fn pick_up_order(
+        &mut self,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+        deliver: Deliver,
+    ) {
+        assert!(T::env().caller() == self.data::<Data>().delivery_contract, "Only delivery contract can pick up order!");
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        // Settlement pays the courier from this copy.
+        self.data::<Data>().delivers.insert(&deliver_id, &deliver);
+        self._pick_up_order(order_id, deliver_id);
+    }
+
+    default fn accept_delivery(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(T::env().caller() == self.data::<Data>().delivery_contract, "Only delivery contract can accept delivery!");
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        assert!(self.data::<OrderingData>().order_data.get(&order_id).unwrap().status == OrderStatus::OrderDelivered, "Order is not out for delivery!");
+        self._accept_delivery(order_id);
+    }
+
+    default fn time_out_order(
+        &mut self,
+        order_id: OrderId,
+    ) -> Vec<(AccountId, u128)> {
+        assert!(T::env().caller() == self.data::<Data>().delivery_contract, "Only delivery contract can time out order!");
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        self._time_out_order(order_id)
+    }
+
+    default fn get_order_accounts(
+        &self,
+        order_id: OrderId,
+    ) -> (AccountId, AccountId) {
+        self._order_accounts(order_id)
+    }
+
+    default fn charge_order(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(T::env().caller() == self.data::<Data>().payment_contract, "Only payment contract can charge order!");
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        self._charge_order(order_id);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Bookkeeping::Bookkeeping,
+    Holding::Holding,
+    Ledger::Ledger,
+    Settlement::Settlement,
+    types::{Data, OrderingData, DeliverId, Order, OrderId, OrderStatus},
+};
+use ink::prelude::vec::Vec;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait Ordering {
+
+    // The order a delivery is for, wherever the orders are kept.
+    fn _order(
+        &self,
+        order_id: OrderId,
+    ) -> Order;
+
+    // Sends an order out with the courier that picked it up.
+    fn _pick_up_order(
+        &mut self,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    );
+
+    // Closes an order whose food reached the customer and settles it. A
+    // payment processor that still has to capture the charge is paid from
+    // the restaurant share held on settlement.
+    fn _accept_delivery(
+        &mut self,
+        order_id: OrderId,
+    );
+
+    // Refunds an order its courier never delivered in full and returns
+    // who paid for it.
+    fn _time_out_order(
+        &mut self,
+        order_id: OrderId,
+    ) -> Vec<(AccountId, u128)>;
+}
+
+impl<T> Ordering for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding,
+{
+    default fn _order(
+        &self,
+        order_id: OrderId,
+    ) -> Order {
+        self.data::<OrderingData>().order_data.get(&order_id).unwrap()
+    }
+
+    default fn _pick_up_order(
+        &mut self,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+    ) {
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.status != OrderStatus::OrderDisputed, "Order is under dispute!");
+        assert!(order.status == OrderStatus::WaitingDeliver, "Order is not waiting for deliver!");
+        let status = OrderStatus::OrderDelivered;
+        order.status = status.clone();
+        order.deliver_id = deliver_id;
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, status);
+    }
+
+    default fn _accept_delivery(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        self._settle_order(order_id, 0);
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        let status = OrderStatus::DeliveryAcceptted;
+        order.status = status.clone();
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, status);
+    }
+
+    default fn _time_out_order(
+        &mut self,
+        order_id: OrderId,
+    ) -> Vec<(AccountId, u128)> {
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.status == OrderStatus::OrderDelivered, "Order is not out for delivery!");
+        self._settle_order(order_id, order.price + order.delivery_fee);
+        let status = OrderStatus::OrderRefunded;
+        order.status = status.clone();
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, status);
+        self._payer_accounts(&order)
+    }
+}
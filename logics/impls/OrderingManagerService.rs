@@ -0,0 +1,149 @@
+use crate::impls::types::{Data, Restaurant, RestaurantId, BASIS_POINTS};
+use crate::traits::OrderingManagerService::OrderingManagerService;
+use ink::prelude::{
+    string::String,
+    vec::Vec,
+};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait OrderingManagerServiceEvents {
+
+    fn emit_add_restaurant_event(
+        &self,
+        restaurant_id: RestaurantId,
+        restaurant_name: String,
+        restaurant_address: String,
+        phone_number: String,
+    );
+
+    fn emit_set_delivery_fee_event(
+        &self,
+        delivery_fee: u128,
+    );
+
+    fn emit_set_commission_event(
+        &self,
+        restaurant_id: Option<RestaurantId>,
+        food_commission: Option<u16>,
+        delivery_commission: u16,
+    );
+}
+
+impl<T> OrderingManagerService for T
+where
+    T: Storage<Data>,
+{
+    default fn add_restaurant(
+        &mut self,
+        restaurant_account: AccountId,
+        restaurant_name: String,
+        restaurant_address: String,
+        phone_number: String,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can add restaurant!");
+        assert!(!self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "already exist restaurant!");
+        let restaurant_id = self.data::<Data>().restaurant_id;
+        self.data::<Data>().restaurant_id += 1;
+        let restaurant = Restaurant {
+            restaurant_account,
+            restaurant_name,
+            restaurant_address,
+            phone_number,
+            encryption_key: None,
+            opening_hours: Vec::new(),
+            closures: Vec::new(),
+        };
+        self.data::<Data>().restaurants.insert(&restaurant_id, &restaurant);
+        self.data::<Data>().restaurant_account_id.insert(&restaurant_account, &restaurant_id);
+        self.data::<Data>().restaurant_whitelist.push(restaurant_account);
+        let restaurant_name = self.data::<Data>().restaurants.get(&restaurant_id).unwrap().restaurant_name;
+        let restaurant_address = self.data::<Data>().restaurants.get(&restaurant_id).unwrap().restaurant_address;
+        let phone_number = self.data::<Data>().restaurants.get(&restaurant_id).unwrap().phone_number;
+        self.emit_add_restaurant_event(
+            restaurant_id,
+            restaurant_name,
+            restaurant_address,
+            phone_number,
+        );
+    }
+
+    default fn set_delivery_fee(
+        &mut self,
+        delivery_fee: u128,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set delivery fee!");
+        self.data::<Data>().delivery_fee = delivery_fee;
+        self.emit_set_delivery_fee_event(
+            delivery_fee,
+        );
+    }
+
+    default fn set_commission(
+        &mut self,
+        food_commission: u16,
+        delivery_commission: u16,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set commission!");
+        assert!(food_commission as u128 <= BASIS_POINTS && delivery_commission as u128 <= BASIS_POINTS, "Commission exceeds 100%!");
+        self.data::<Data>().food_commission = food_commission;
+        self.data::<Data>().delivery_commission = delivery_commission;
+        self.emit_set_commission_event(
+            None,
+            Some(food_commission),
+            delivery_commission,
+        );
+    }
+
+    default fn set_restaurant_commission(
+        &mut self,
+        restaurant_id: RestaurantId,
+        food_commission: Option<u16>,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set commission!");
+        assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
+        match food_commission {
+            Some(commission) => {
+                assert!(commission as u128 <= BASIS_POINTS, "Commission exceeds 100%!");
+                self.data::<Data>().restaurant_commission.insert(&restaurant_id, &commission);
+            }
+            None => self.data::<Data>().restaurant_commission.remove(&restaurant_id),
+        }
+        let delivery_commission = self.data::<Data>().delivery_commission;
+        self.emit_set_commission_event(
+            Some(restaurant_id),
+            food_commission,
+            delivery_commission,
+        );
+    }
+}
+
+impl<T> OrderingManagerServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_add_restaurant_event(
+        &self,
+        restaurant_id: RestaurantId,
+        restaurant_name: String,
+        restaurant_address: String,
+        phone_number: String,
+    ) {}
+
+    default fn emit_set_delivery_fee_event(
+        &self,
+        delivery_fee: u128,
+    ) {}
+
+    default fn emit_set_commission_event(
+        &self,
+        restaurant_id: Option<RestaurantId>,
+        food_commission: Option<u16>,
+        delivery_commission: u16,
+    ) {}
+}
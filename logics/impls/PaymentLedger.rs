@@ -0,0 +1,65 @@
+use crate::impls::{
+    Holding::Holding,
+    Ledger::Ledger,
+    types::{Data, PaymentData, OrderId},
+};
+use crate::traits::PaymentLedger::PaymentLedger;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+impl<T> PaymentLedger for T
+where
+    T: Storage<Data> + Storage<PaymentData> + Holding,
+{
+    default fn credit(
+        &mut self,
+        account: AccountId,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().order_contract || caller == self.data::<Data>().delivery_contract, "Only workflow contracts can credit!");
+        self._add_balance(account, T::env().transferred_value());
+    }
+
+    default fn credit_treasury(
+        &mut self,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().order_contract || caller == self.data::<Data>().delivery_contract, "Only workflow contracts can credit!");
+        self._add_treasury(T::env().transferred_value());
+    }
+
+    default fn draw_treasury(
+        &mut self,
+        amount: u128,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().order_contract, "Only order contract can draw treasury!");
+        self._take_treasury(amount);
+        assert!(T::env().transfer(caller, amount).is_ok(), "Treasury transfer failed!");
+    }
+
+    default fn awaits_capture(
+        &self,
+        order_id: OrderId,
+    ) -> bool {
+        self._awaits_capture(order_id)
+    }
+
+    default fn hold_for_capture(
+        &mut self,
+        order_id: OrderId,
+        restaurant_account: AccountId,
+    ) {
+        assert!(T::env().caller() == self.data::<Data>().order_contract, "Only order contract can hold for capture!");
+        self._hold_for_capture(order_id, restaurant_account, T::env().transferred_value());
+    }
+
+    default fn cancel_charge(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(T::env().caller() == self.data::<Data>().order_contract, "Only order contract can cancel charge!");
+        self._cancel_charge(order_id);
+    }
+}
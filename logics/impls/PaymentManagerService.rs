@@ -0,0 +1,141 @@
+use crate::impls::Ledger::Ledger;
+use crate::impls::types::{Data, PaymentData, PaymentProcessor, PaymentProcessorId, BASIS_POINTS};
+use crate::traits::PaymentManagerService::PaymentManagerService;
+use ink::prelude::{
+    string::String,
+};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait PaymentManagerServiceEvents {
+
+    fn emit_add_payment_processor_event(
+        &self,
+        processor_id: PaymentProcessorId,
+        processor_account: AccountId,
+        processor_name: String,
+    );
+
+    fn emit_set_processor_fee_rate_event(
+        &self,
+        processor_id: PaymentProcessorId,
+        fee_rate: u16,
+    );
+
+    fn emit_set_capture_timeout_event(
+        &self,
+        capture_timeout: u64,
+    );
+
+    fn emit_withdraw_treasury_event(
+        &self,
+        manager: AccountId,
+        amount: u128,
+    );
+}
+
+impl<T> PaymentManagerService for T
+where
+    T: Storage<Data> + Storage<PaymentData> + Ledger,
+{
+    default fn add_payment_processor(
+        &mut self,
+        processor_account: AccountId,
+        processor_name: String,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can add payment processor!");
+        assert!(!self.data::<PaymentData>().payment_processor_whitelist.contains(&processor_account), "already exist payment processor!");
+        let processor_id = self.data::<PaymentData>().payment_processor_id;
+        self.data::<PaymentData>().payment_processor_id += 1;
+        let processor = PaymentProcessor {
+            processor_account,
+            processor_name,
+            fee_rate: 0,
+        };
+        self.data::<PaymentData>().payment_processors.insert(&processor_id, &processor);
+        self.data::<PaymentData>().payment_processor_account_id.insert(&processor_account, &processor_id);
+        self.data::<PaymentData>().payment_processor_whitelist.push(processor_account);
+        let processor_name = self.data::<PaymentData>().payment_processors.get(&processor_id).unwrap().processor_name;
+        self.emit_add_payment_processor_event(
+            processor_id,
+            processor_account,
+            processor_name,
+        );
+    }
+
+    default fn set_processor_fee_rate(
+        &mut self,
+        processor_id: PaymentProcessorId,
+        fee_rate: u16,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set processor fee rate!");
+        assert!(fee_rate as u128 <= BASIS_POINTS, "Fee rate exceeds 100%!");
+        assert!(self.data::<PaymentData>().payment_processors.contains(&processor_id), "Payment processor does not exist!");
+        let mut processor = self.data::<PaymentData>().payment_processors.get(&processor_id).unwrap();
+        processor.fee_rate = fee_rate;
+        self.data::<PaymentData>().payment_processors.insert(&processor_id, &processor);
+        self.emit_set_processor_fee_rate_event(
+            processor_id,
+            fee_rate,
+        );
+    }
+
+    default fn set_capture_timeout(
+        &mut self,
+        capture_timeout: u64,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set capture timeout!");
+        self.data::<PaymentData>().capture_timeout = capture_timeout;
+        self.emit_set_capture_timeout_event(
+            capture_timeout,
+        );
+    }
+
+    default fn withdraw_treasury(
+        &mut self,
+        amount: u128,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can withdraw treasury!");
+        assert!(amount <= self._treasury(), "Insufficient treasury balance!");
+        self._take_treasury(amount);
+        assert!(T::env().transfer(caller, amount).is_ok(), "Treasury transfer failed!");
+        self.emit_withdraw_treasury_event(
+            caller,
+            amount,
+        );
+    }
+}
+
+impl<T> PaymentManagerServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_add_payment_processor_event(
+        &self,
+        processor_id: PaymentProcessorId,
+        processor_account: AccountId,
+        processor_name: String,
+    ) {}
+
+    default fn emit_set_processor_fee_rate_event(
+        &self,
+        processor_id: PaymentProcessorId,
+        fee_rate: u16,
+    ) {}
+
+    default fn emit_set_capture_timeout_event(
+        &self,
+        capture_timeout: u64,
+    ) {}
+
+    default fn emit_withdraw_treasury_event(
+        &self,
+        manager: AccountId,
+        amount: u128,
+    ) {}
+}
@@ -0,0 +1,200 @@
+use crate::impls::{
+    Charging::Charging,
+    Holding::Holding,
+    Ledger::Ledger,
+    types::{Data, PaymentData, Charge, ChargeStatus, OrderId, PaymentProcessorId, BASIS_POINTS},
+};
+use crate::traits::PaymentService::PaymentService;
+use ink::prelude::{
+    string::String,
+};
+use openbrush::{
+    traits::Storage,
+};
+
+pub trait PaymentServiceEvents {
+
+    fn emit_authorize_charge_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+        charge_reference: String,
+        amount: u128,
+        fee: u128,
+    );
+
+    fn emit_capture_charge_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+        processor_amount: u128,
+    );
+
+    fn emit_select_payment_processor_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+    );
+
+    fn emit_void_charge_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+    );
+}
+
+impl<T> PaymentService for T
+where
+    T: Storage<Data> + Storage<PaymentData> + Ledger + Holding + Charging,
+{
+    default fn select_payment_processor(
+        &mut self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+    ) {
+        let order = self._charged_order(order_id);
+        let caller = T::env().caller();
+        let (customer_account, restaurant_account) = self._order_accounts(order_id);
+        assert!(caller == customer_account || caller == restaurant_account, "Only customer or restaurant can select payment processor!");
+        assert!(self.data::<PaymentData>().payment_processors.contains(&processor_id), "Payment processor does not exist!");
+        assert!(!order.status.is_closed(), "Order is already settled!");
+        assert!(!self.data::<PaymentData>().charge_data.contains(&order_id), "Charge already exist!");
+        self.data::<PaymentData>().order_processors.insert(&order_id, &processor_id);
+        self.emit_select_payment_processor_event(
+            order_id,
+            processor_id,
+        );
+    }
+
+    default fn authorize_charge(
+        &mut self,
+        order_id: OrderId,
+        charge_reference: String,
+        fee: u128,
+    ) {
+        let order = self._charged_order(order_id);
+        let processor_account = T::env().caller();
+        assert!(self.data::<PaymentData>().payment_processor_whitelist.contains(&processor_account), "Only payment processor can authorize charge!");
+        let processor_id = self.data::<PaymentData>().payment_processor_account_id.get(&processor_account).unwrap();
+        assert!(!order.status.is_closed(), "Order is already settled!");
+        assert!(!self.data::<PaymentData>().charge_data.contains(&order_id), "Charge already exist!");
+        assert!(self.data::<PaymentData>().order_processors.get(&order_id) == Some(processor_id), "Processor was not selected for this order!");
+        let fee_rate = self.data::<PaymentData>().payment_processors.get(&processor_id).unwrap().fee_rate;
+        assert!(fee <= order.price * fee_rate as u128 / BASIS_POINTS, "Fee exceeds processor fee rate!");
+        let amount = order.price + order.delivery_fee;
+        let charge = Charge {
+            processor_id,
+            charge_reference,
+            amount,
+            fee,
+            status: ChargeStatus::Authorized,
+            timestamp: T::env().block_timestamp(),
+        };
+        self.data::<PaymentData>().charge_data.insert(&order_id, &charge);
+        let charge_reference = self.data::<PaymentData>().charge_data.get(&order_id).unwrap().charge_reference;
+        self.emit_authorize_charge_event(
+            order_id,
+            processor_id,
+            charge_reference,
+            amount,
+            fee,
+        );
+    }
+
+    default fn capture_charge(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(self.data::<PaymentData>().charge_data.contains(&order_id), "Charge not exist!");
+        let processor_account = T::env().caller();
+        assert!(self.data::<PaymentData>().payment_processor_whitelist.contains(&processor_account), "Only payment processor can capture charge!");
+        let processor_id = self.data::<PaymentData>().payment_processor_account_id.get(&processor_account).unwrap();
+        let mut charge = self.data::<PaymentData>().charge_data.get(&order_id).unwrap();
+        assert!(charge.processor_id == processor_id, "Not processor of this charge!");
+        assert!(charge.status == ChargeStatus::Authorized, "Charge is not authorized!");
+        assert!(self.data::<PaymentData>().charge_holds.contains(&order_id), "Order is not accepted yet!");
+        let hold = self.data::<PaymentData>().charge_holds.get(&order_id).unwrap();
+        charge.status = ChargeStatus::Captured;
+        self.data::<PaymentData>().charge_data.insert(&order_id, &charge);
+        self.data::<PaymentData>().charge_holds.remove(&order_id);
+        // The processor fee is paid out of the held restaurant share.
+        let processor_amount = if charge.fee < hold.amount { charge.fee } else { hold.amount };
+        let restaurant_amount = hold.amount - processor_amount;
+        if restaurant_amount > 0 {
+            self._add_balance(hold.restaurant_account, restaurant_amount);
+        }
+        if processor_amount > 0 {
+            self._add_balance(processor_account, processor_amount);
+        }
+        self._charge_order(order_id);
+        self.emit_capture_charge_event(
+            order_id,
+            processor_id,
+            restaurant_amount,
+            processor_amount,
+        );
+    }
+
+    default fn void_charge(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(self.data::<PaymentData>().charge_data.contains(&order_id), "Charge not exist!");
+        let mut charge = self.data::<PaymentData>().charge_data.get(&order_id).unwrap();
+        assert!(charge.status == ChargeStatus::Authorized, "Charge is not authorized!");
+        assert!(self.data::<PaymentData>().charge_holds.contains(&order_id), "Order is not accepted yet!");
+        // The share was held when the order was accepted.
+        let hold = self.data::<PaymentData>().charge_holds.get(&order_id).unwrap();
+        let deadline_passed = T::env().block_timestamp() >= hold.timestamp + self.data::<PaymentData>().capture_timeout;
+        assert!(T::env().caller() == self.data::<Data>().manager || deadline_passed, "Capture deadline has not passed!");
+        // Pays the restaurant as if no charge had been authorized.
+        charge.status = ChargeStatus::Voided;
+        self.data::<PaymentData>().charge_data.insert(&order_id, &charge);
+        self.data::<PaymentData>().charge_holds.remove(&order_id);
+        if hold.amount > 0 {
+            self._add_balance(hold.restaurant_account, hold.amount);
+        }
+        self.emit_void_charge_event(
+            order_id,
+            charge.processor_id,
+            hold.amount,
+        );
+    }
+}
+
+impl<T> PaymentServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_authorize_charge_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+        charge_reference: String,
+        amount: u128,
+        fee: u128,
+    ) {}
+
+    default fn emit_capture_charge_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+        processor_amount: u128,
+    ) {}
+
+    default fn emit_select_payment_processor_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+    ) {}
+
+    default fn emit_void_charge_event(
+        &self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+    ) {}
+}
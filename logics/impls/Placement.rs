@@ -0,0 +1,112 @@
+use crate::impls::{
+    Bookkeeping::Bookkeeping,
+    CustomerService::CustomerServiceEvents,
+    types::{Data, OrderingData, CustomerId, FiatQuote, FoodId, GroupId, Order, OrderId, OrderStatus, PersonalData, RestaurantId},
+};
+use ink::prelude::{
+    vec,
+    vec::Vec,
+};
+use openbrush::{
+    traits::{Storage, Timestamp},
+};
+
+pub trait Placement {
+
+    // Stores a paid order and announces it to the restaurant. Callers check
+    // the food can be ordered and take the payment.
+    fn _place_order(
+        &mut self,
+        customer_id: CustomerId,
+        food_id: FoodId,
+        restaurant_id: RestaurantId,
+        delivery_address: PersonalData,
+        requested_time: Timestamp,
+        price: u128,
+        delivery_fee: u128,
+        fiat_quote: Option<FiatQuote>,
+        group_id: Option<GroupId>,
+        points_redeemed: u128,
+    ) -> OrderId;
+
+    // Puts every food of an unconfirmed order back in stock.
+    fn _restock_order(
+        &mut self,
+        order: &Order,
+    );
+}
+
+impl<T> Placement for T
+where
+    T: Storage<Data> + Storage<OrderingData>,
+{
+    default fn _place_order(
+        &mut self,
+        customer_id: CustomerId,
+        food_id: FoodId,
+        restaurant_id: RestaurantId,
+        delivery_address: PersonalData,
+        requested_time: Timestamp,
+        price: u128,
+        delivery_fee: u128,
+        fiat_quote: Option<FiatQuote>,
+        group_id: Option<GroupId>,
+        points_redeemed: u128,
+    ) -> OrderId {
+        let order = Order {
+            food_id,
+            restaurant_id,
+            customer_id,
+            deliver_id: 0,
+            delivery_address,
+            status: OrderStatus::OrderSubmitted,
+            timestamp: T::env().block_timestamp(),
+            price,
+            delivery_fee,
+            eta: 0,
+            requested_time,
+            fiat_quote,
+            group_id,
+            points_redeemed,
+            points_earned: 0,
+            promo_code: None,
+            promo_discount: 0,
+            handoff_hash: None,
+        };
+        let order_id = self.data::<OrderingData>().order_id;
+        self.data::<OrderingData>().order_id += 1;
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._append_customer_order(customer_id, order_id);
+        self._append_restaurant_order(restaurant_id, order_id);
+        self._record_status(order_id, OrderStatus::OrderSubmitted);
+        let delivery_address = self.data::<OrderingData>().order_data.get(&order_id).unwrap().delivery_address;
+        let phone_number = self.data::<OrderingData>().customers.get(&customer_id).unwrap().phone_number;
+        self.emit_submit_order_event(
+            order_id,
+            food_id,
+            restaurant_id,
+            customer_id,
+            delivery_address,
+            phone_number,
+        );
+        order_id
+    }
+
+    default fn _restock_order(
+        &mut self,
+        order: &Order,
+    ) {
+        let food_ids: Vec<FoodId> = match order.group_id.and_then(|group_id| self.data::<OrderingData>().group_data.get(&group_id)) {
+            Some(group) => group.items.iter().map(|item| item.food_id).collect(),
+            None => vec![order.food_id],
+        };
+        for food_id in food_ids {
+            if let Some(mut food) = self.data::<OrderingData>().food_data.get(&food_id) {
+                if let Some(stock) = food.stock {
+                    food.stock = Some(stock + 1);
+                    self.data::<OrderingData>().food_data.insert(&food_id, &food);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::impls::{
+    PromoService::PromoServiceEvents,
+    types::{Data, OrderingData, CustomerId, FiatQuote, Food, OrderId, RestaurantId, RATE_PRECISION, hash_secret},
+};
+use ink::prelude::string::String;
+use openbrush::{
+    traits::{Hash, Storage},
+};
+
+pub trait Pricing {
+
+    // Native price of a food at the current block, together with the quote
+    // it was converted from when the food is priced in fiat.
+    fn _quote_food(
+        &self,
+        food: &Food,
+    ) -> (u128, Option<FiatQuote>);
+
+    // Checks the customer may use a promo code at the restaurant and
+    // returns the code's hash with the discount it takes off `price`.
+    fn _quote_promo_code(
+        &self,
+        code: &String,
+        customer_id: CustomerId,
+        restaurant_id: RestaurantId,
+        price: u128,
+    ) -> (Hash, u128);
+
+    // Counts a use of the promo code against its caps.
+    fn _redeem_promo_code(
+        &mut self,
+        code_hash: Hash,
+        customer_id: CustomerId,
+        order_id: OrderId,
+        discount: u128,
+    );
+}
+
+impl<T> Pricing for T
+where
+    T: Storage<Data> + Storage<OrderingData>,
+{
+    default fn _quote_food(
+        &self,
+        food: &Food,
+    ) -> (u128, Option<FiatQuote>) {
+        let currency = match food.currency {
+            Some(currency) => currency,
+            None => return (food.price, None),
+        };
+        assert!(self.data::<OrderingData>().exchange_rates.contains(&currency), "Exchange rate not exist!");
+        let exchange_rate = self.data::<OrderingData>().exchange_rates.get(&currency).unwrap();
+        let age = T::env().block_timestamp().saturating_sub(exchange_rate.timestamp);
+        assert!(age <= self.data::<OrderingData>().max_rate_age, "Exchange rate is stale!");
+        // Rounded up so the restaurant is never paid less than its fiat price.
+        let price = food.price
+            .checked_mul(exchange_rate.rate)
+            .and_then(|amount| amount.checked_add(RATE_PRECISION - 1))
+            .expect("Price overflow!")
+            / RATE_PRECISION;
+        let quote = FiatQuote {
+            currency,
+            fiat_price: food.price,
+            exchange_rate,
+        };
+        (price, Some(quote))
+    }
+
+    default fn _quote_promo_code(
+        &self,
+        code: &String,
+        customer_id: CustomerId,
+        restaurant_id: RestaurantId,
+        price: u128,
+    ) -> (Hash, u128) {
+        let code_hash = hash_secret(code);
+        assert!(self.data::<OrderingData>().promo_codes.contains(&code_hash), "Promo code not exist!");
+        let promo = self.data::<OrderingData>().promo_codes.get(&code_hash).unwrap();
+        assert!(promo.active, "Promo code is revoked!");
+        let now = T::env().block_timestamp();
+        assert!(promo.valid_from <= now && now < promo.valid_until, "Promo code is not valid now!");
+        assert!(promo.restaurant_id.map_or(true, |id| id == restaurant_id), "Promo code not valid at this restaurant!");
+        assert!(promo.max_uses.map_or(true, |max_uses| promo.uses < max_uses), "Promo code is used up!");
+        let customer_uses = self.data::<OrderingData>().promo_customer_uses.get(&(code_hash, customer_id)).unwrap_or(0);
+        assert!(promo.max_uses_per_customer.map_or(true, |max_uses| customer_uses < max_uses), "Promo code already used by customer!");
+        (code_hash, promo.discount.apply(price))
+    }
+
+    default fn _redeem_promo_code(
+        &mut self,
+        code_hash: Hash,
+        customer_id: CustomerId,
+        order_id: OrderId,
+        discount: u128,
+    ) {
+        let mut promo = self.data::<OrderingData>().promo_codes.get(&code_hash).unwrap();
+        promo.uses += 1;
+        self.data::<OrderingData>().promo_codes.insert(&code_hash, &promo);
+        let customer_uses = self.data::<OrderingData>().promo_customer_uses.get(&(code_hash, customer_id)).unwrap_or(0);
+        self.data::<OrderingData>().promo_customer_uses.insert(&(code_hash, customer_id), &(customer_uses + 1));
+        self.emit_redeem_promo_code_event(
+            code_hash,
+            order_id,
+            customer_id,
+            discount,
+        );
+    }
+}
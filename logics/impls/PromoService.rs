@@ -0,0 +1,116 @@
+use crate::impls::types::{Data, OrderingData, CustomerId, OrderId, PromoCode, PromoDiscount, RestaurantId, BASIS_POINTS};
+use crate::traits::PromoService::PromoService;
+use openbrush::{
+    traits::{AccountId, Hash, Storage, Timestamp},
+};
+
+pub trait PromoServiceEvents {
+
+    fn emit_add_promo_code_event(
+        &self,
+        code_hash: Hash,
+        creator: AccountId,
+        restaurant_id: Option<RestaurantId>,
+    );
+
+    fn emit_revoke_promo_code_event(
+        &self,
+        code_hash: Hash,
+    );
+
+    fn emit_redeem_promo_code_event(
+        &self,
+        code_hash: Hash,
+        order_id: OrderId,
+        customer_id: CustomerId,
+        discount: u128,
+    );
+}
+
+impl<T> PromoService for T
+where
+    T: Storage<Data> + Storage<OrderingData>,
+{
+    default fn add_promo_code(
+        &mut self,
+        code_hash: Hash,
+        restaurant_id: Option<RestaurantId>,
+        discount: PromoDiscount,
+        max_uses: Option<u32>,
+        max_uses_per_customer: Option<u32>,
+        valid_from: Timestamp,
+        valid_until: Timestamp,
+    ) {
+        let caller = T::env().caller();
+        if caller != self.data::<Data>().manager {
+            assert!(self.data::<Data>().restaurant_whitelist.contains(&caller), "Only manager or restaurant can add promo code!");
+            let caller_id = self.data::<Data>().restaurant_account_id.get(&caller).unwrap();
+            assert!(restaurant_id == Some(caller_id), "Restaurant can only add promo code for itself!");
+        }
+        if let Some(restaurant_id) = restaurant_id {
+            assert!(self.data::<Data>().restaurants.contains(&restaurant_id), "Restaurant does not exist!");
+        }
+        assert!(!self.data::<OrderingData>().promo_codes.contains(&code_hash), "Promo code already exist!");
+        if let PromoDiscount::Percent(percent) = discount {
+            assert!(percent as u128 <= BASIS_POINTS, "Discount exceeds 100%!");
+        }
+        assert!(valid_from < valid_until, "Promo code validity window is empty!");
+        let promo = PromoCode {
+            creator: caller,
+            restaurant_id,
+            discount,
+            max_uses,
+            max_uses_per_customer,
+            valid_from,
+            valid_until,
+            uses: 0,
+            active: true,
+        };
+        self.data::<OrderingData>().promo_codes.insert(&code_hash, &promo);
+        self.emit_add_promo_code_event(
+            code_hash,
+            caller,
+            restaurant_id,
+        );
+    }
+
+    default fn revoke_promo_code(
+        &mut self,
+        code_hash: Hash,
+    ) {
+        assert!(self.data::<OrderingData>().promo_codes.contains(&code_hash), "Promo code not exist!");
+        let mut promo = self.data::<OrderingData>().promo_codes.get(&code_hash).unwrap();
+        let caller = T::env().caller();
+        assert!(caller == promo.creator || caller == self.data::<Data>().manager, "Only creator or manager can revoke promo code!");
+        promo.active = false;
+        self.data::<OrderingData>().promo_codes.insert(&code_hash, &promo);
+        self.emit_revoke_promo_code_event(
+            code_hash,
+        );
+    }
+}
+
+impl<T> PromoServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_add_promo_code_event(
+        &self,
+        code_hash: Hash,
+        creator: AccountId,
+        restaurant_id: Option<RestaurantId>,
+    ) {}
+
+    default fn emit_revoke_promo_code_event(
+        &self,
+        code_hash: Hash,
+    ) {}
+
+    default fn emit_redeem_promo_code_event(
+        &self,
+        code_hash: Hash,
+        order_id: OrderId,
+        customer_id: CustomerId,
+        discount: u128,
+    ) {}
+}
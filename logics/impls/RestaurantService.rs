@@ -1,11 +1,21 @@
-use crate::impls::types::{Data, OrderStatus, DeliveryStatus, Food, FoodId, OrderId, Delivery, RestaurantId, CustomerId, };
+use crate::impls::{
+    Bonding::Bonding,
+    Bookkeeping::Bookkeeping,
+    Dispatch::Dispatch,
+    Holding::Holding,
+    Ledger::Ledger,
+    Loyalty::Loyalty,
+    Placement::Placement,
+    Settlement::Settlement,
+    types::{Data, OrderingData, OrderStatus, Food, FoodId, CurrencyCode, OrderId, DeliveryId, RestaurantId, CustomerId, OpeningSlot, Closure, MINUTES_PER_DAY, PersonalData, PublicKey, SealedBox},
+};
 use crate::traits::RestaurantService::RestaurantService;
 use ink::prelude::{
     string::String,
     vec::Vec,
 };
 use openbrush::{
-    traits::{Storage},
+    traits::{Storage, Timestamp},
 };
 
 pub trait RestaurantServiceEvents {
@@ -29,24 +39,68 @@ pub trait RestaurantServiceEvents {
         eta: u64,
     );
 
+    fn emit_update_food_availability_event(
+        &self,
+        food_id: FoodId,
+        available: bool,
+        stock: Option<u32>,
+    );
+
+    fn emit_update_food_currency_event(
+        &self,
+        food_id: FoodId,
+        currency: Option<CurrencyCode>,
+    );
+
+    fn emit_remove_food_event(
+        &self,
+        food_id: FoodId,
+        restaurant_id: RestaurantId,
+    );
+
+    fn emit_update_opening_hours_event(
+        &self,
+        restaurant_id: RestaurantId,
+        opening_hours: Vec<OpeningSlot>,
+    );
+
+    fn emit_update_closures_event(
+        &self,
+        restaurant_id: RestaurantId,
+        closures: Vec<Closure>,
+    );
+
     fn emit_confirm_order_event(
         &self,
         order_id: OrderId,
         eta: u64,
     );
 
+    fn emit_reject_order_event(
+        &self,
+        order_id: OrderId,
+        restaurant_id: RestaurantId,
+    );
+
     fn emit_deliver_order_event(
         &self,
         order_id: OrderId,
+        delivery_id: DeliveryId,
         restaurant_id: RestaurantId,
         customer_id: CustomerId,
-        delivery_address: String,
+        delivery_address: PersonalData,
+    );
+
+    fn emit_update_restaurant_encryption_key_event(
+        &self,
+        restaurant_id: RestaurantId,
+        encryption_key: Option<PublicKey>,
     );
 }
 
 impl<T> RestaurantService for T
 where
-    T: Storage<Data>,
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding + Dispatch,
 {
     default fn add_food(
         &mut self,
@@ -56,10 +110,10 @@ where
         eta: u64,
     ) {
         let restaurant_account = T::env().caller();
-        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
         assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can add food!");
-        let food_id = self.data::<Data>().food_id;
-        self.data::<Data>().food_id += 1;
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        let food_id = self.data::<OrderingData>().food_id;
+        self.data::<OrderingData>().food_id += 1;
         let food = Food {
             food_name,
             restaurant_id,
@@ -67,13 +121,16 @@ where
             price,
             eta,
             timestamp: T::env().block_timestamp(),
+            available: true,
+            stock: None,
+            currency: None,
         };
-        self.data::<Data>().food_data.insert(&food_id, &food);
-        let mut food_vec = self.data::<Data>().restaurant_food_data.get(&restaurant_id).unwrap_or(Vec::new());
+        self.data::<OrderingData>().food_data.insert(&food_id, &food);
+        let mut food_vec = self.data::<OrderingData>().restaurant_food_data.get(&restaurant_id).unwrap_or(Vec::new());
         food_vec.push(food_id);
-        self.data::<Data>().restaurant_food_data.insert(&restaurant_id, &food_vec);
-        let food_name = self.data::<Data>().food_data.get(&food_id).unwrap().food_name;
-        let description = self.data::<Data>().food_data.get(&food_id).unwrap().description;
+        self.data::<OrderingData>().restaurant_food_data.insert(&restaurant_id, &food_vec);
+        let food_name = self.data::<OrderingData>().food_data.get(&food_id).unwrap().food_name;
+        let description = self.data::<OrderingData>().food_data.get(&food_id).unwrap().description;
         self.emit_add_food_event(
             food_id,
             food_name,
@@ -93,9 +150,11 @@ where
         eta: u64,
     ) {
         let restaurant_account = T::env().caller();
-        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
         assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can update food!");
-        assert!(self.data::<Data>().food_data.get(&food_id).unwrap().restaurant_id == restaurant_id, "Not owner of this food!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        assert!(self.data::<OrderingData>().food_data.get(&food_id).unwrap().restaurant_id == restaurant_id, "Not owner of this food!");
+        let current = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
         let food = Food {
             food_name,
             restaurant_id,
@@ -103,10 +162,13 @@ where
             price,
             eta,
             timestamp: T::env().block_timestamp(),
+            available: current.available,
+            stock: current.stock,
+            currency: current.currency,
         };
-        self.data::<Data>().food_data.insert(&food_id, &food);
-        let food_name = self.data::<Data>().food_data.get(&food_id).unwrap().food_name;
-        let description = self.data::<Data>().food_data.get(&food_id).unwrap().description;
+        self.data::<OrderingData>().food_data.insert(&food_id, &food);
+        let food_name = self.data::<OrderingData>().food_data.get(&food_id).unwrap().food_name;
+        let description = self.data::<OrderingData>().food_data.get(&food_id).unwrap().description;
         self.emit_update_food_event(
             food_id,
             food_name,
@@ -116,64 +178,261 @@ where
         );
     }
 
-    default fn confirm_order(
+    default fn set_food_availability(
+        &mut self,
+        food_id: FoodId,
+        available: bool,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can update food!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        let mut food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        assert!(food.restaurant_id == restaurant_id, "Not owner of this food!");
+        food.available = available;
+        self.data::<OrderingData>().food_data.insert(&food_id, &food);
+        self.emit_update_food_availability_event(
+            food_id,
+            food.available,
+            food.stock,
+        );
+    }
+
+    default fn set_food_stock(
+        &mut self,
+        food_id: FoodId,
+        stock: Option<u32>,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can update food!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        let mut food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        assert!(food.restaurant_id == restaurant_id, "Not owner of this food!");
+        food.stock = stock;
+        self.data::<OrderingData>().food_data.insert(&food_id, &food);
+        self.emit_update_food_availability_event(
+            food_id,
+            food.available,
+            food.stock,
+        );
+    }
+
+    default fn set_food_currency(
+        &mut self,
+        food_id: FoodId,
+        currency: Option<CurrencyCode>,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can update food!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        let mut food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        assert!(food.restaurant_id == restaurant_id, "Not owner of this food!");
+        food.currency = currency;
+        self.data::<OrderingData>().food_data.insert(&food_id, &food);
+        self.emit_update_food_currency_event(
+            food_id,
+            currency,
+        );
+    }
+
+    default fn remove_food(
+        &mut self,
+        food_id: FoodId,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can remove food!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        assert!(self.data::<OrderingData>().food_data.get(&food_id).unwrap().restaurant_id == restaurant_id, "Not owner of this food!");
+        self.data::<OrderingData>().food_data.remove(&food_id);
+        let mut food_vec = self.data::<OrderingData>().restaurant_food_data.get(&restaurant_id).unwrap_or(Vec::new());
+        food_vec.retain(|id| *id != food_id);
+        self.data::<OrderingData>().restaurant_food_data.insert(&restaurant_id, &food_vec);
+        self.emit_remove_food_event(
+            food_id,
+            restaurant_id,
+        );
+    }
+
+    default fn set_opening_hours(
+        &mut self,
+        opening_hours: Vec<OpeningSlot>,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can set opening hours!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        for slot in opening_hours.iter() {
+            assert!(slot.weekday < 7, "Invalid weekday!");
+            assert!(slot.open < slot.close && slot.close <= MINUTES_PER_DAY, "Invalid opening slot!");
+        }
+        let mut restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        restaurant.opening_hours = opening_hours.clone();
+        self.data::<Data>().restaurants.insert(&restaurant_id, &restaurant);
+        self.emit_update_opening_hours_event(
+            restaurant_id,
+            opening_hours,
+        );
+    }
+
+    default fn add_closure(
+        &mut self,
+        start: Timestamp,
+        end: Timestamp,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can add closure!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(start < end, "Invalid closure window!");
+        let now = T::env().block_timestamp();
+        let mut restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        restaurant.closures.retain(|closure| closure.end > now);
+        restaurant.closures.push(Closure { start, end });
+        self.data::<Data>().restaurants.insert(&restaurant_id, &restaurant);
+        self.emit_update_closures_event(
+            restaurant_id,
+            restaurant.closures,
+        );
+    }
+
+    default fn remove_closure(
+        &mut self,
+        start: Timestamp,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can remove closure!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        let mut restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        assert!(restaurant.closures.iter().any(|closure| closure.start == start), "Closure not exist!");
+        restaurant.closures.retain(|closure| closure.start != start);
+        self.data::<Data>().restaurants.insert(&restaurant_id, &restaurant);
+        self.emit_update_closures_event(
+            restaurant_id,
+            restaurant.closures,
+        );
+    }
+
+    default fn set_restaurant_encryption_key(
+        &mut self,
+        encryption_key: Option<PublicKey>,
+    ) {
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can set encryption key!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        let mut restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        restaurant.encryption_key = encryption_key;
+        self.data::<Data>().restaurants.insert(&restaurant_id, &restaurant);
+        self.emit_update_restaurant_encryption_key_event(
+            restaurant_id,
+            encryption_key,
+        );
+    }
+
+    default fn share_delivery_address(
         &mut self,
         order_id: OrderId,
+        sealed_address: SealedBox,
     ) {
-        assert!(self.data::<Data>().order_data.contains(&order_id), "Order not exist!");
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
         let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can share delivery address!");
         let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
-        let food_id = self.data::<Data>().order_data.get(&order_id).unwrap().food_id;
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.restaurant_id == restaurant_id, "Not owner of this order!");
+        assert!(order.deliver_id != 0, "No deliver assigned to this order!");
+        let deliver_key = self.data::<Data>().delivers.get(&order.deliver_id).unwrap().encryption_key;
+        assert!(deliver_key == Some(sealed_address.recipient), "Address must be sealed for the deliver of this order!");
+        match order.delivery_address {
+            PersonalData::Sealed(ref mut boxes) => boxes.push(sealed_address),
+            _ => panic!("Delivery address is not sealed!"),
+        }
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        let delivery_id = self._readdress_delivery(order_id, order.delivery_address.clone());
+        self.emit_deliver_order_event(
+            order_id,
+            delivery_id,
+            restaurant_id,
+            order.customer_id,
+            order.delivery_address,
+        );
+    }
+
+    default fn confirm_order(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        let restaurant_account = T::env().caller();
         assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can confirm order!");
-        assert!(self.data::<Data>().food_data.get(&food_id).unwrap().restaurant_id == restaurant_id, "Not owner of this order!");
-        let mut order = self.data::<Data>().order_data.get(&order_id).unwrap();
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().order_data.get(&order_id).unwrap().restaurant_id == restaurant_id, "Not owner of this order!");
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.status != OrderStatus::OrderDisputed, "Order is under dispute!");
+        assert!(!order.status.is_closed(), "Order is already settled!");
+        assert!(order.status == OrderStatus::OrderSubmitted || order.status == OrderStatus::OrderConfirmed, "Order is already sent out!");
+        let accepted = order.status == OrderStatus::OrderSubmitted;
         let status = OrderStatus::OrderConfirmed;
-        order.status = status;
-        let food_id = self.data::<Data>().order_data.get(&order_id).unwrap().food_id;
-        let eta = self.data::<Data>().food_data.get(&food_id).unwrap().eta;
+        order.status = status.clone();
+        self._record_status(order_id, status);
+        let food_id = self.data::<OrderingData>().order_data.get(&order_id).unwrap().food_id;
+        let eta = self.data::<OrderingData>().food_data.get(&food_id).map(|food| food.eta).unwrap_or_default();
         order.eta = eta;
-        self.data::<Data>().order_data.insert(&order_id, &order);
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        if accepted {
+            self._award_points(order_id);
+        }
         self.emit_confirm_order_event(
             order_id,
             eta,
         );
     }
 
-    default fn deliver_order(
+    default fn reject_order(
         &mut self,
         order_id: OrderId,
     ) {
-        assert!(self.data::<Data>().order_data.contains(&order_id), "Order not exist!");
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
         let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can reject order!");
         let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
-        let food_id = self.data::<Data>().order_data.get(&order_id).unwrap().food_id;
-        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can confirm order!");
-        assert!(self.data::<Data>().food_data.get(&food_id).unwrap().restaurant_id == restaurant_id, "Not owner of this order!");
-        let mut order = self.data::<Data>().order_data.get(&order_id).unwrap();
-        let status = OrderStatus::WaitingDeliver;
-        order.status = status;
-        self.data::<Data>().order_data.insert(&order_id, &order);
-        let delivery_id = self.data::<Data>().delivery_id;
-        self.data::<Data>().delivery_id += 1;
-        let restaurant_id = self.data::<Data>().order_data.get(&order_id).unwrap().restaurant_id;
-        let customer_id = self.data::<Data>().order_data.get(&order_id).unwrap().customer_id;
-        let deliver_id = 0;
-        let delivery_address = self.data::<Data>().order_data.get(&order_id).unwrap().delivery_address;
-        let status = DeliveryStatus::Waiting;
-        let timestamp = T::env().block_timestamp();
-        let delivery = Delivery {
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.restaurant_id == restaurant_id, "Not owner of this order!");
+        assert!(order.status == OrderStatus::OrderSubmitted, "Order is already confirmed!");
+        self._restock_order(&order);
+        self._settle_order(order_id, order.price + order.delivery_fee);
+        order.status = OrderStatus::OrderRejected;
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, OrderStatus::OrderRejected);
+        self.emit_reject_order_event(
             order_id,
             restaurant_id,
-            customer_id,
-            deliver_id,
-            delivery_address,
-            status,
-            timestamp,
-        };
-        self.data::<Data>().delivery_data.insert(&delivery_id, &delivery);
-        let delivery_address = self.data::<Data>().order_data.get(&order_id).unwrap().delivery_address;
+        );
+    }
+
+    default fn deliver_order(
+        &mut self,
+        order_id: OrderId,
+    ) {
+        assert!(self.data::<OrderingData>().order_data.contains(&order_id), "Order not exist!");
+        let restaurant_account = T::env().caller();
+        assert!(self.data::<Data>().restaurant_whitelist.contains(&restaurant_account), "Only restaurant can confirm order!");
+        let restaurant_id = self.data::<Data>().restaurant_account_id.get(&restaurant_account).unwrap();
+        assert!(self.data::<OrderingData>().order_data.get(&order_id).unwrap().restaurant_id == restaurant_id, "Not owner of this order!");
+        let mut order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(order.status != OrderStatus::OrderDisputed, "Order is under dispute!");
+        assert!(!order.status.is_closed(), "Order is already settled!");
+        assert!(order.status == OrderStatus::OrderConfirmed, "Order is not confirmed!");
+        let status = OrderStatus::WaitingDeliver;
+        order.status = status.clone();
+        self.data::<OrderingData>().order_data.insert(&order_id, &order);
+        self._record_status(order_id, status);
+        let customer_id = order.customer_id;
+        let delivery_address = order.delivery_address;
+        let delivery_id = self._dispatch_delivery(order_id, restaurant_id, customer_id, delivery_address.clone());
         self.emit_deliver_order_event(
             order_id,
+            delivery_id,
             restaurant_id,
             customer_id,
             delivery_address,
@@ -185,7 +444,7 @@ impl<T> RestaurantServiceEvents for T
 where
     T: Storage<Data>
 {
-    fn emit_add_food_event(
+    default fn emit_add_food_event(
         &self,
         food_id: FoodId,
         food_name: String,
@@ -195,7 +454,7 @@ where
         eta: u64,
     ) {}
     
-    fn emit_update_food_event(
+    default fn emit_update_food_event(
         &self,
         food_id: FoodId,
         food_name: String,
@@ -204,17 +463,61 @@ where
         eta: u64,
     ) {}
 
-    fn emit_confirm_order_event(
+    default fn emit_update_food_availability_event(
+        &self,
+        food_id: FoodId,
+        available: bool,
+        stock: Option<u32>,
+    ) {}
+
+    default fn emit_update_food_currency_event(
+        &self,
+        food_id: FoodId,
+        currency: Option<CurrencyCode>,
+    ) {}
+
+    default fn emit_remove_food_event(
+        &self,
+        food_id: FoodId,
+        restaurant_id: RestaurantId,
+    ) {}
+
+    default fn emit_update_opening_hours_event(
+        &self,
+        restaurant_id: RestaurantId,
+        opening_hours: Vec<OpeningSlot>,
+    ) {}
+
+    default fn emit_update_closures_event(
+        &self,
+        restaurant_id: RestaurantId,
+        closures: Vec<Closure>,
+    ) {}
+
+    default fn emit_confirm_order_event(
         &self,
         order_id: OrderId,
         eta: u64,
     ) {}
 
-    fn emit_deliver_order_event(
+    default fn emit_reject_order_event(
+        &self,
+        order_id: OrderId,
+        restaurant_id: RestaurantId,
+    ) {}
+
+    default fn emit_deliver_order_event(
         &self,
         order_id: OrderId,
+        delivery_id: DeliveryId,
         restaurant_id: RestaurantId,
         customer_id: CustomerId,
-        delivery_address: String,
+        delivery_address: PersonalData,
+    ) {}
+
+    default fn emit_update_restaurant_encryption_key_event(
+        &self,
+        restaurant_id: RestaurantId,
+        encryption_key: Option<PublicKey>,
     ) {}
 }
\ No newline at end of file
@@ -0,0 +1,226 @@
+use crate::impls::{
+    Ledger::Ledger,
+    Placement::Placement,
+    Pricing::Pricing,
+    types::{Data, OrderingData, CustomerId, FoodId, OrderId, PersonalData, RestaurantId, Schedule, ScheduleId, MINUTES_PER_DAY},
+};
+use crate::traits::ScheduleService::ScheduleService;
+use ink::prelude::{
+    vec::Vec,
+};
+use openbrush::{
+    traits::{Storage, Timestamp},
+};
+
+pub trait ScheduleServiceEvents {
+
+    fn emit_create_schedule_event(
+        &self,
+        schedule_id: ScheduleId,
+        customer_id: CustomerId,
+        food_id: FoodId,
+        next_time: Timestamp,
+        prepaid: u128,
+    );
+
+    fn emit_trigger_schedule_event(
+        &self,
+        schedule_id: ScheduleId,
+        order_id: Option<OrderId>,
+        next_time: Timestamp,
+    );
+
+    fn emit_close_schedule_event(
+        &self,
+        schedule_id: ScheduleId,
+        refund: u128,
+    );
+}
+
+// Ends a schedule and returns what is left of its prepayment to the
+// customer's withdrawable balance.
+fn close_schedule<T: Storage<Data> + Storage<OrderingData> + Ledger>(contract: &mut T, schedule: &mut Schedule) -> u128 {
+    let refund = schedule.prepaid;
+    schedule.prepaid = 0;
+    schedule.active = false;
+    if refund > 0 {
+        let customer_account = contract.data::<OrderingData>().customers.get(&schedule.customer_id).unwrap().customer_account;
+        contract._add_balance(customer_account, refund);
+    }
+    refund
+}
+
+impl<T> ScheduleService for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger,
+{
+    default fn create_schedule(
+        &mut self,
+        food_id: FoodId,
+        restaurant_id: RestaurantId,
+        delivery_address: PersonalData,
+        weekdays: Vec<u8>,
+        minute: u32,
+        occurrences: u32,
+    ) {
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can create schedule!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        assert!(self.data::<OrderingData>().food_data.contains(&food_id), "Food not exist!");
+        let food = self.data::<OrderingData>().food_data.get(&food_id).unwrap();
+        assert!(food.restaurant_id == restaurant_id, "Food not served by this restaurant!");
+        let restaurant = self.data::<Data>().restaurants.get(&restaurant_id).unwrap();
+        if let PersonalData::Sealed(_) = delivery_address {
+            assert!(delivery_address.is_sealed_for(&restaurant.encryption_key), "Delivery address must be sealed for the restaurant!");
+        }
+        assert!(!weekdays.is_empty() && weekdays.iter().all(|weekday| *weekday < 7), "Invalid schedule weekdays!");
+        assert!(minute < MINUTES_PER_DAY, "Invalid schedule time!");
+        assert!(occurrences > 0, "Schedule needs at least one order!");
+        let (price, _) = self._quote_food(&food);
+        let cost = price + self.data::<Data>().delivery_fee;
+        let prepaid = T::env().transferred_value();
+        let total = cost.checked_mul(occurrences as u128).expect("Schedule cost overflows!");
+        assert!(prepaid >= total, "Prepayment does not cover the schedule!");
+        let mut schedule = Schedule {
+            customer_id,
+            food_id,
+            restaurant_id,
+            delivery_address,
+            weekdays,
+            minute,
+            next_time: 0,
+            remaining: occurrences,
+            prepaid,
+            active: true,
+        };
+        schedule.next_time = schedule.next_after(T::env().block_timestamp());
+        let schedule_id = self.data::<OrderingData>().schedule_id;
+        self.data::<OrderingData>().schedule_id += 1;
+        self.data::<OrderingData>().schedule_data.insert(&schedule_id, &schedule);
+        let mut schedule_vec = self.data::<OrderingData>().customer_schedule_data.get(&customer_id).unwrap_or(Vec::new());
+        schedule_vec.push(schedule_id);
+        self.data::<OrderingData>().customer_schedule_data.insert(&customer_id, &schedule_vec);
+        self.emit_create_schedule_event(
+            schedule_id,
+            customer_id,
+            food_id,
+            schedule.next_time,
+            prepaid,
+        );
+    }
+
+    // Anyone may trigger a due schedule. A slot the restaurant cannot serve
+    // is skipped, and the schedule closes once its orders are placed or the
+    // prepayment no longer covers the next one.
+    default fn trigger_schedule(
+        &mut self,
+        schedule_id: ScheduleId,
+    ) {
+        assert!(self.data::<OrderingData>().schedule_data.contains(&schedule_id), "Schedule not exist!");
+        let mut schedule = self.data::<OrderingData>().schedule_data.get(&schedule_id).unwrap();
+        assert!(schedule.active, "Schedule is not active!");
+        let now = T::env().block_timestamp();
+        assert!(now >= schedule.next_time, "Schedule is not due yet!");
+        // A late trigger orders for now rather than the missed slot, so the
+        // restaurant is never asked to deliver in the past.
+        let slot = now;
+        let mut order_id = None;
+        let servable = self.data::<OrderingData>().food_data.get(&schedule.food_id).filter(|food| {
+            let open = self.data::<Data>().restaurants.get(&food.restaurant_id).map_or(false, |restaurant| restaurant.is_open_at(slot));
+            open && food.available && food.stock != Some(0)
+        });
+        if let Some(mut food) = servable {
+            let (price, fiat_quote) = self._quote_food(&food);
+            let delivery_fee = self.data::<Data>().delivery_fee;
+            let cost = price + delivery_fee;
+            if cost <= schedule.prepaid {
+                if let Some(stock) = food.stock {
+                    food.stock = Some(stock - 1);
+                    self.data::<OrderingData>().food_data.insert(&schedule.food_id, &food);
+                }
+                schedule.prepaid -= cost;
+                schedule.remaining -= 1;
+                order_id = Some(self._place_order(
+                    schedule.customer_id,
+                    schedule.food_id,
+                    schedule.restaurant_id,
+                    schedule.delivery_address.clone(),
+                    slot,
+                    price,
+                    delivery_fee,
+                    fiat_quote,
+                    None,
+                    0,
+                ));
+            } else {
+                schedule.remaining = 0;
+            }
+        }
+        // Slots missed while nobody triggered are skipped, not caught up,
+        // and do not use up an occurrence.
+        schedule.next_time = schedule.next_after(now);
+        let refund = if schedule.remaining == 0 {
+            Some(close_schedule(self, &mut schedule))
+        } else {
+            None
+        };
+        self.data::<OrderingData>().schedule_data.insert(&schedule_id, &schedule);
+        self.emit_trigger_schedule_event(
+            schedule_id,
+            order_id,
+            schedule.next_time,
+        );
+        if let Some(refund) = refund {
+            self.emit_close_schedule_event(
+                schedule_id,
+                refund,
+            );
+        }
+    }
+
+    default fn cancel_schedule(
+        &mut self,
+        schedule_id: ScheduleId,
+    ) {
+        assert!(self.data::<OrderingData>().schedule_data.contains(&schedule_id), "Schedule not exist!");
+        let customer_account = T::env().caller();
+        assert!(self.data::<OrderingData>().customer_whitelist.contains(&customer_account), "only customer can cancel schedule!");
+        let customer_id = self.data::<OrderingData>().customer_account_id.get(&customer_account).unwrap();
+        let mut schedule = self.data::<OrderingData>().schedule_data.get(&schedule_id).unwrap();
+        assert!(schedule.customer_id == customer_id, "not customer of this schedule!");
+        assert!(schedule.active, "Schedule is not active!");
+        let refund = close_schedule(self, &mut schedule);
+        self.data::<OrderingData>().schedule_data.insert(&schedule_id, &schedule);
+        self.emit_close_schedule_event(
+            schedule_id,
+            refund,
+        );
+    }
+}
+
+impl<T> ScheduleServiceEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_create_schedule_event(
+        &self,
+        schedule_id: ScheduleId,
+        customer_id: CustomerId,
+        food_id: FoodId,
+        next_time: Timestamp,
+        prepaid: u128,
+    ) {}
+
+    default fn emit_trigger_schedule_event(
+        &self,
+        schedule_id: ScheduleId,
+        order_id: Option<OrderId>,
+        next_time: Timestamp,
+    ) {}
+
+    default fn emit_close_schedule_event(
+        &self,
+        schedule_id: ScheduleId,
+        refund: u128,
+    ) {}
+}
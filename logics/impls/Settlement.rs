@@ -0,0 +1,221 @@
+use crate::impls::{
+    Bonding::Bonding,
+    Holding::Holding,
+    Ledger::Ledger,
+    Loyalty::Loyalty,
+    types::{Data, OrderingData, CustomerId, Order, OrderId, BASIS_POINTS},
+};
+use ink::prelude::{
+    vec,
+    vec::Vec,
+};
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait SettlementEvents {
+
+    fn emit_settle_order_event(
+        &self,
+        order_id: OrderId,
+        refund: u128,
+        restaurant_amount: u128,
+        deliver_amount: u128,
+        held_amount: u128,
+        platform_amount: u128,
+    );
+
+    fn emit_credit_balance_event(
+        &self,
+        account: AccountId,
+        order_id: OrderId,
+        amount: u128,
+    );
+}
+
+// Splits an amount across payers in proportion to what they paid, the
+// first payer takes the rounding remainder.
+pub fn pro_rata<P: Copy>(amount: u128, payers: &[(P, u128)]) -> Vec<(P, u128)> {
+    let total: u128 = payers.iter().map(|(_, paid)| paid).sum();
+    let mut shares: Vec<(P, u128)> = payers
+        .iter()
+        .map(|(payer, paid)| (*payer, if total > 0 { paid * amount / total } else { 0 }))
+        .collect();
+    let assigned: u128 = shares.iter().skip(1).map(|(_, share)| share).sum();
+    if let Some(first) = shares.first_mut() {
+        first.1 = amount - assigned;
+    }
+    shares
+}
+
+pub trait Settlement {
+
+    fn _settle_order(
+        &mut self,
+        order_id: OrderId,
+        refund: u128,
+    );
+
+    fn _credit_balance(
+        &mut self,
+        account: AccountId,
+        order_id: OrderId,
+        amount: u128,
+    );
+
+    // Who paid for an order and how much of it in native units. A group
+    // order lists its shares with the organizer first.
+    fn _payers(
+        &self,
+        order: &Order,
+    ) -> Vec<(CustomerId, u128)>;
+
+    // The payers of an order by account, for a contract that does not keep
+    // the customers.
+    fn _payer_accounts(
+        &self,
+        order: &Order,
+    ) -> Vec<(AccountId, u128)>;
+
+    // Slashes the bond of the courier of an order and credits the slashed
+    // amount to whoever paid for the order.
+    fn _slash_bond(
+        &mut self,
+        order_id: OrderId,
+    ) -> u128;
+}
+
+impl<T> Settlement for T
+where
+    T: Storage<Data> + Storage<OrderingData> + Ledger + Bonding + Holding,
+{
+    default fn _settle_order(
+        &mut self,
+        order_id: OrderId,
+        refund: u128,
+    ) {
+        let order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        assert!(refund <= order.price + order.delivery_fee, "Refund exceeds order price!");
+        let food_refund = if refund < order.price { refund } else { order.price };
+        let food_amount = order.price - food_refund;
+        let fee_amount = order.delivery_fee - (refund - food_refund);
+        let food_commission = self.data::<Data>().restaurant_commission.get(&order.restaurant_id).unwrap_or(self.data::<Data>().food_commission);
+        let delivery_commission = self.data::<Data>().delivery_commission;
+        let food_platform_amount = food_amount * food_commission as u128 / BASIS_POINTS;
+        let mut deliver_platform_amount = fee_amount * delivery_commission as u128 / BASIS_POINTS;
+        let mut restaurant_amount = food_amount - food_platform_amount;
+        let mut held_amount = 0;
+        let mut deliver_amount = fee_amount - deliver_platform_amount;
+        if order.deliver_id == 0 {
+            deliver_platform_amount += deliver_amount;
+            deliver_amount = 0;
+        }
+        let platform_amount = food_platform_amount + deliver_platform_amount;
+        self._add_treasury(platform_amount);
+        if refund > 0 {
+            // The treasury funded the redeemed discount, so it takes back
+            // its share of the refund and the rest goes to the payers.
+            let treasury_refund = order.points_redeemed * refund / (order.price + order.delivery_fee);
+            self._add_treasury(treasury_refund);
+            let payers = self._payer_accounts(&order);
+            for (customer_account, amount) in pro_rata(refund - treasury_refund, &payers) {
+                if amount > 0 {
+                    self._credit_balance(customer_account, order_id, amount);
+                }
+            }
+            self._refund_points(order_id, refund);
+        }
+        let restaurant_account = self.data::<Data>().restaurants.get(&order.restaurant_id).unwrap().restaurant_account;
+        if self._awaits_capture(order_id) {
+            if refund == 0 {
+                // The processor takes its fee out of the restaurant share
+                // when it captures the charge.
+                held_amount = restaurant_amount;
+                restaurant_amount = 0;
+                self._hold_for_capture(order_id, restaurant_account, held_amount);
+            } else {
+                self._cancel_charge(order_id);
+            }
+        }
+        if restaurant_amount > 0 {
+            self._credit_balance(restaurant_account, order_id, restaurant_amount);
+        }
+        if deliver_amount > 0 {
+            let deliver_account = self.data::<Data>().delivers.get(&order.deliver_id).unwrap().deliver_account;
+            self._credit_balance(deliver_account, order_id, deliver_amount);
+        }
+        self.emit_settle_order_event(
+            order_id,
+            refund,
+            restaurant_amount,
+            deliver_amount,
+            held_amount,
+            platform_amount,
+        );
+    }
+
+    default fn _credit_balance(
+        &mut self,
+        account: AccountId,
+        order_id: OrderId,
+        amount: u128,
+    ) {
+        self._add_balance(account, amount);
+        self.emit_credit_balance_event(
+            account,
+            order_id,
+            amount,
+        );
+    }
+
+    default fn _payers(
+        &self,
+        order: &Order,
+    ) -> Vec<(CustomerId, u128)> {
+        match order.group_id.and_then(|group_id| self.data::<OrderingData>().group_data.get(&group_id)) {
+            Some(group) => group.shares.iter().map(|share| (share.customer_id, share.paid)).collect(),
+            None => vec![(order.customer_id, order.price + order.delivery_fee - order.points_redeemed)],
+        }
+    }
+
+    default fn _payer_accounts(
+        &self,
+        order: &Order,
+    ) -> Vec<(AccountId, u128)> {
+        self._payers(order)
+            .into_iter()
+            .map(|(customer_id, paid)| (self.data::<OrderingData>().customers.get(&customer_id).unwrap().customer_account, paid))
+            .collect()
+    }
+
+    default fn _slash_bond(
+        &mut self,
+        order_id: OrderId,
+    ) -> u128 {
+        let order = self.data::<OrderingData>().order_data.get(&order_id).unwrap();
+        let payers = self._payer_accounts(&order);
+        self._slash_deliver(order.deliver_id, order_id, payers)
+    }
+}
+
+impl<T> SettlementEvents for T
+where
+    T: Storage<Data>
+{
+    default fn emit_settle_order_event(
+        &self,
+        order_id: OrderId,
+        refund: u128,
+        restaurant_amount: u128,
+        deliver_amount: u128,
+        held_amount: u128,
+        platform_amount: u128,
+    ) {}
+
+    default fn emit_credit_balance_event(
+        &self,
+        account: AccountId,
+        order_id: OrderId,
+        amount: u128,
+    ) {}
+}
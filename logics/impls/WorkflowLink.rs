@@ -0,0 +1,55 @@
+use crate::impls::types::Data;
+use crate::traits::WorkflowLink::WorkflowLink;
+use openbrush::{
+    traits::{AccountId, Storage},
+};
+
+pub trait WorkflowLinkEvents {
+
+    fn emit_set_workflow_contracts_event(
+        &self,
+        order_contract: AccountId,
+        delivery_contract: AccountId,
+        payment_contract: AccountId,
+    );
+}
+
+impl<T> WorkflowLink for T
+where
+    T: Storage<Data>,
+{
+    default fn set_workflow_contracts(
+        &mut self,
+        order_contract: AccountId,
+        delivery_contract: AccountId,
+        payment_contract: AccountId,
+    ) {
+        let caller = T::env().caller();
+        assert!(caller == self.data::<Data>().manager, "Only manager can set workflow contracts!");
+        self.data::<Data>().order_contract = order_contract;
+        self.data::<Data>().delivery_contract = delivery_contract;
+        self.data::<Data>().payment_contract = payment_contract;
+        self.emit_set_workflow_contracts_event(
+            order_contract,
+            delivery_contract,
+            payment_contract,
+        );
+    }
+
+    default fn get_workflow_contracts(&self) -> (AccountId, AccountId, AccountId) {
+        let data = self.data::<Data>();
+        (data.order_contract, data.delivery_contract, data.payment_contract)
+    }
+}
+
+impl<T> WorkflowLinkEvents for T
+where
+    T: Storage<Data>,
+{
+    default fn emit_set_workflow_contracts_event(
+        &self,
+        order_contract: AccountId,
+        delivery_contract: AccountId,
+        payment_contract: AccountId,
+    ) {}
+}
@@ -1,5 +1,35 @@
+pub mod BalanceService;
+pub mod Bonding;
+pub mod BondService;
+pub mod Bookkeeping;
+pub mod Charging;
 pub mod CustomerService;
 pub mod DeliverService;
+pub mod DeliveryManagerService;
+pub mod DeliveryWorkflow;
+pub mod Dispatch;
+pub mod DisputeService;
 pub mod Get;
+pub mod GetDelivery;
+pub mod GetPayment;
+pub mod GroupOrderService;
+pub mod Holding;
+pub mod Ledger;
+pub mod Loyalty;
+pub mod LoyaltyService;
 pub mod ManagerService;
-pub mod RestaurantService;
\ No newline at end of file
+pub mod OracleService;
+pub mod OrderWorkflow;
+pub mod Ordering;
+pub mod OrderingManagerService;
+pub mod PaymentLedger;
+pub mod PaymentManagerService;
+pub mod PaymentService;
+pub mod Placement;
+pub mod PromoService;
+pub mod Pricing;
+pub mod RestaurantService;
+pub mod ScheduleService;
+pub mod Settlement;
+pub mod WorkflowLink;
+pub mod types;
\ No newline at end of file
@@ -1,3 +1,4 @@
+use ink::env::hash::{Blake2x256, HashOutput};
 use ink::prelude::{
     string::String,
     vec::Vec,
@@ -6,6 +7,7 @@ use openbrush::{
     storage::Mapping,
     traits::{
         AccountId,
+        Hash,
         ZERO_ADDRESS,
         Timestamp
     },
@@ -17,6 +19,29 @@ pub type DeliveryId = u64;
 pub type CustomerId = u64;
 pub type RestaurantId = u64;
 pub type DeliverId = u64;
+pub type DisputeId = u64;
+pub type PaymentProcessorId = u64;
+pub type ScheduleId = u64;
+pub type GroupId = u64;
+// ISO 4217 code such as `*b"USD"`
+pub type CurrencyCode = [u8; 3];
+
+pub type PublicKey = [u8; 32];
+// Ethereum-style address of the secp256k1 key couriers sign drop-off
+// attestations with, and the recoverable signature over it. ink 4 offers no
+// sr25519 verification inside contracts, so attestations use ECDSA.
+pub type EcdsaAddress = [u8; 20];
+pub type EcdsaSignature = [u8; 65];
+
+pub const BASIS_POINTS: u128 = 10_000;
+pub const MILLIS_PER_MINUTE: u64 = 60_000;
+pub const MINUTES_PER_DAY: u32 = 1_440;
+// Exchange rates are native units per fiat minor unit, scaled by this.
+pub const RATE_PRECISION: u128 = 1_000_000_000_000;
+
+// Bumped whenever an event is added, removed or changes its fields, so
+// indexers can tell which layout a contract emits.
+pub const EVENT_SCHEMA_VERSION: u16 = 16;
 
 
 #[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
@@ -30,6 +55,42 @@ pub enum OrderStatus {
     WaitingDeliver,
     OrderDelivered,
     DeliveryAcceptted,
+    OrderDisputed,
+    OrderRefunded,
+    OrderRejected,
+    OrderCancelled,
+    OrderCharged,
+}
+
+// Handoff hashes are public and unsalted, so a code must carry at least 16
+// random bytes, hex encoded, to resist trying every value.
+pub const MIN_HANDOFF_CODE_LEN: usize = 32;
+
+// blake2x256 of a secret revealed on chain, such as a promo or handoff code.
+pub fn hash_secret(secret: &String) -> Hash {
+    let mut output = <Blake2x256 as HashOutput>::Type::default();
+    ink::env::hash_bytes::<Blake2x256>(secret.as_bytes(), &mut output);
+    Hash::from(output)
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct StatusChange {
+    pub status: OrderStatus,
+    pub timestamp: Timestamp,
+}
+
+impl OrderStatus {
+    // Closed orders have been settled and can no longer change.
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::DeliveryAcceptted | OrderStatus::OrderRefunded | OrderStatus::OrderRejected | OrderStatus::OrderCancelled | OrderStatus::OrderCharged
+        )
+    }
 }
 
 #[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
@@ -40,6 +101,46 @@ pub enum OrderStatus {
 pub enum DeliveryStatus {
     Waiting,
     PickUp,
+    HandedOff,
+    DroppedOff,
+    TimedOut,
+    Cancelled,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct SealedBox {
+    pub recipient: PublicKey,
+    pub ciphertext: Vec<u8>,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum PersonalData {
+    Plain(String),
+    Commitment(Hash),
+    Sealed(Vec<SealedBox>),
+}
+
+impl Default for PersonalData {
+    fn default() -> Self {
+        PersonalData::Plain(Default::default())
+    }
+}
+
+impl PersonalData {
+    pub fn is_sealed_for(&self, key: &Option<PublicKey>) -> bool {
+        match (self, key) {
+            (PersonalData::Sealed(boxes), Some(key)) => boxes.iter().any(|sealed| sealed.recipient == *key),
+            _ => false,
+        }
+    }
 }
 
 #[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
@@ -50,8 +151,8 @@ pub enum DeliveryStatus {
 pub struct Customer {
     pub customer_account: AccountId,
     pub customer_name: String,
-    pub customer_address: String,
-    pub phone_number: String,
+    pub customer_address: PersonalData,
+    pub phone_number: PersonalData,
 }
 
 impl Default for Customer {
@@ -65,6 +166,28 @@ impl Default for Customer {
     }
 }
 
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct OpeningSlot {
+    // 0 is Monday, times are minutes since midnight UTC
+    pub weekday: u8,
+    pub open: u32,
+    pub close: u32,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Closure {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
 #[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "std",
@@ -75,6 +198,9 @@ pub struct Restaurant {
     pub restaurant_name: String,
     pub restaurant_address: String,
     pub phone_number: String,
+    pub encryption_key: Option<PublicKey>,
+    pub opening_hours: Vec<OpeningSlot>,
+    pub closures: Vec<Closure>,
 }
 
 impl Default for Restaurant {
@@ -84,7 +210,30 @@ impl Default for Restaurant {
             restaurant_name: Default::default(),
             restaurant_address: Default::default(),
             phone_number: Default::default(),
+            encryption_key: None,
+            opening_hours: Vec::new(),
+            closures: Vec::new(),
+        }
+    }
+}
+
+impl Restaurant {
+    // A restaurant without a published schedule is treated as always open.
+    pub fn is_open_at(&self, timestamp: Timestamp) -> bool {
+        if self.closures.iter().any(|closure| closure.start <= timestamp && timestamp < closure.end) {
+            return false;
+        }
+        if self.opening_hours.is_empty() {
+            return true;
         }
+        let minutes = timestamp / MILLIS_PER_MINUTE;
+        let days = minutes / MINUTES_PER_DAY as u64;
+        // 1970-01-01 was a Thursday
+        let weekday = ((days + 3) % 7) as u8;
+        let minute = (minutes % MINUTES_PER_DAY as u64) as u32;
+        self.opening_hours
+            .iter()
+            .any(|slot| slot.weekday == weekday && slot.open <= minute && minute < slot.close)
     }
 }
 
@@ -98,6 +247,8 @@ pub struct Deliver {
     pub deliver_name: String,
     pub deliver_address: String,
     pub phone_number: String,
+    pub encryption_key: Option<PublicKey>,
+    pub attestation_key: Option<EcdsaAddress>,
 }
 
 impl Default for Deliver {
@@ -107,6 +258,8 @@ impl Default for Deliver {
             deliver_name: Default::default(),
             deliver_address: Default::default(),
             phone_number: Default::default(),
+            encryption_key: None,
+            attestation_key: None,
         }
     }
 }
@@ -123,6 +276,10 @@ pub struct Food {
     pub price: u128,
     pub eta: u64,
     pub timestamp: Timestamp,
+    pub available: bool,
+    pub stock: Option<u32>,
+    // When set, `price` is in minor units of this currency instead of native units.
+    pub currency: Option<CurrencyCode>,
 }
 
 impl Default for Food {
@@ -134,6 +291,9 @@ impl Default for Food {
             price: Default::default(),
             eta: Default::default(),
             timestamp: Default::default(),
+            available: true,
+            stock: None,
+            currency: None,
         }
     }
 }
@@ -148,11 +308,25 @@ pub struct Order {
     pub restaurant_id: RestaurantId,
     pub customer_id: CustomerId,
     pub deliver_id: DeliveryId,
-    pub delivery_address: String,
+    pub delivery_address: PersonalData,
     pub status: OrderStatus,
     pub timestamp: Timestamp,
     pub price: u128,
+    pub delivery_fee: u128,
     pub eta: u64,
+    pub requested_time: Timestamp,
+    pub fiat_quote: Option<FiatQuote>,
+    pub group_id: Option<GroupId>,
+    // Loyalty points burned as a discount on the food price, the treasury
+    // pays the restaurant the difference.
+    pub points_redeemed: u128,
+    // Loyalty points minted when the restaurant accepted the order.
+    pub points_earned: u128,
+    pub promo_code: Option<Hash>,
+    // Taken off the food price before `price` was charged.
+    pub promo_discount: u128,
+    // Hash of the one-time code the customer shows the courier on drop-off.
+    pub handoff_hash: Option<Hash>,
 }
 
 impl Default for Order {
@@ -166,7 +340,16 @@ impl Default for Order {
             status: OrderStatus::OrderSubmitted,
             timestamp: Default::default(),
             price: Default::default(),
+            delivery_fee: Default::default(),
             eta: Default::default(),
+            requested_time: Default::default(),
+            fiat_quote: None,
+            group_id: None,
+            points_redeemed: Default::default(),
+            points_earned: Default::default(),
+            promo_code: None,
+            promo_discount: Default::default(),
+            handoff_hash: None,
         }
     }
 }
@@ -181,9 +364,10 @@ pub struct Delivery {
     pub restaurant_id: RestaurantId,
     pub customer_id: CustomerId,
     pub deliver_id: DeliverId,
-    pub delivery_address: String,
+    pub delivery_address: PersonalData,
     pub status: DeliveryStatus,
     pub timestamp: Timestamp,
+    pub picked_up_at: Timestamp,
 }
 
 impl Default for Delivery {
@@ -196,69 +380,608 @@ impl Default for Delivery {
             delivery_address: Default::default(),
             status: DeliveryStatus::Waiting,
             timestamp: Default::default(),
+            picked_up_at: Default::default(),
         }
     }
 }
 
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum DisputeResolution {
+    FullRefund,
+    PartialRefund(u128),
+    Release,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Dispute {
+    pub order_id: OrderId,
+    pub customer_id: CustomerId,
+    pub evidence_hash: Hash,
+    pub restaurant_response: Option<Hash>,
+    pub deliver_response: Option<Hash>,
+    pub status: DisputeStatus,
+    pub resolution: Option<DisputeResolution>,
+    pub timestamp: Timestamp,
+}
+
+impl Default for Dispute {
+    fn default() -> Self {
+        Dispute {
+            order_id: Default::default(),
+            customer_id: Default::default(),
+            evidence_hash: Default::default(),
+            restaurant_response: None,
+            deliver_response: None,
+            status: DisputeStatus::Open,
+            resolution: None,
+            timestamp: Default::default(),
+        }
+    }
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct PaymentProcessor {
+    pub processor_account: AccountId,
+    pub processor_name: String,
+    // Caps the fee of a charge, in basis points of the order price.
+    pub fee_rate: u16,
+}
+
+impl Default for PaymentProcessor {
+    fn default() -> Self {
+        PaymentProcessor {
+            processor_account: ZERO_ADDRESS.into(),
+            processor_name: Default::default(),
+            fee_rate: 0,
+        }
+    }
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum ChargeStatus {
+    Authorized,
+    Captured,
+    Voided,
+}
+
+// A charge is authorized against the escrowed order payment and captured
+// once the customer accepted the delivery. The processor fee is paid out of
+// the restaurant share on capture.
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Charge {
+    pub processor_id: PaymentProcessorId,
+    pub charge_reference: String,
+    pub amount: u128,
+    pub fee: u128,
+    pub status: ChargeStatus,
+    pub timestamp: Timestamp,
+}
+
+impl Default for Charge {
+    fn default() -> Self {
+        Charge {
+            processor_id: Default::default(),
+            charge_reference: Default::default(),
+            amount: Default::default(),
+            fee: Default::default(),
+            status: ChargeStatus::Authorized,
+            timestamp: Default::default(),
+        }
+    }
+}
+
+// The restaurant share of an accepted order, held until the processor
+// captures the charge or the charge is voided.
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ChargeHold {
+    pub restaurant_account: AccountId,
+    pub amount: u128,
+    pub timestamp: Timestamp,
+}
+
+impl Default for ChargeHold {
+    fn default() -> Self {
+        ChargeHold {
+            restaurant_account: ZERO_ADDRESS.into(),
+            amount: Default::default(),
+            timestamp: Default::default(),
+        }
+    }
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ExchangeRate {
+    pub rate: u128,
+    pub timestamp: Timestamp,
+}
+
+// The fiat price of an order and the rate it was converted at on submission.
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct FiatQuote {
+    pub currency: CurrencyCode,
+    pub fiat_price: u128,
+    pub exchange_rate: ExchangeRate,
+}
+
+// A recurring order placed at `minute` (since midnight UTC) on each of
+// `weekdays` (0 is Monday), paid from `prepaid` until `remaining` orders
+// have been placed.
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Schedule {
+    pub customer_id: CustomerId,
+    pub food_id: FoodId,
+    pub restaurant_id: RestaurantId,
+    pub delivery_address: PersonalData,
+    pub weekdays: Vec<u8>,
+    pub minute: u32,
+    pub next_time: Timestamp,
+    pub remaining: u32,
+    pub prepaid: u128,
+    pub active: bool,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule {
+            customer_id: Default::default(),
+            food_id: Default::default(),
+            restaurant_id: Default::default(),
+            delivery_address: Default::default(),
+            weekdays: Vec::new(),
+            minute: Default::default(),
+            next_time: Default::default(),
+            remaining: Default::default(),
+            prepaid: Default::default(),
+            active: true,
+        }
+    }
+}
+
+impl Schedule {
+    // First slot strictly after `timestamp`, 0 when no weekday is set.
+    pub fn next_after(&self, timestamp: Timestamp) -> Timestamp {
+        let millis_per_day = MINUTES_PER_DAY as u64 * MILLIS_PER_MINUTE;
+        let today = timestamp / millis_per_day;
+        (today..today + 8)
+            .map(|day| day * millis_per_day + self.minute as u64 * MILLIS_PER_MINUTE)
+            .find(|slot| {
+                // 1970-01-01 was a Thursday
+                let weekday = ((slot / millis_per_day + 3) % 7) as u8;
+                *slot > timestamp && self.weekdays.contains(&weekday)
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum GroupStatus {
+    Open,
+    Submitted,
+    Cancelled,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct GroupItem {
+    pub customer_id: CustomerId,
+    pub food_id: FoodId,
+    pub price: u128,
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct GroupShare {
+    pub customer_id: CustomerId,
+    pub due: u128,
+    pub paid: u128,
+}
+
+// One delivery paid by several customers. The organizer owns the first
+// share, which carries the delivery fee, and submits the basket once every
+// share is paid.
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct GroupOrder {
+    pub organizer_id: CustomerId,
+    pub restaurant_id: RestaurantId,
+    pub delivery_address: PersonalData,
+    pub delivery_fee: u128,
+    pub items: Vec<GroupItem>,
+    pub shares: Vec<GroupShare>,
+    pub status: GroupStatus,
+    pub order_id: Option<OrderId>,
+    pub timestamp: Timestamp,
+}
+
+impl Default for GroupOrder {
+    fn default() -> Self {
+        GroupOrder {
+            organizer_id: Default::default(),
+            restaurant_id: Default::default(),
+            delivery_address: Default::default(),
+            delivery_fee: Default::default(),
+            items: Vec::new(),
+            shares: Vec::new(),
+            status: GroupStatus::Open,
+            order_id: None,
+            timestamp: Default::default(),
+        }
+    }
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum PromoDiscount {
+    // Native units off the food price.
+    Amount(u128),
+    // Basis points of the food price.
+    Percent(u16),
+}
+
+impl PromoDiscount {
+    pub fn apply(&self, price: u128) -> u128 {
+        match self {
+            PromoDiscount::Amount(amount) => if *amount < price { *amount } else { price },
+            PromoDiscount::Percent(percent) => price * *percent as u128 / BASIS_POINTS,
+        }
+    }
+}
+
+// A campaign code, stored under the blake2x256 hash of its plaintext so
+// codes cannot be read off chain before they are handed out. The discount
+// comes off the food price, so restaurant and platform share its cost.
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct PromoCode {
+    pub creator: AccountId,
+    pub restaurant_id: Option<RestaurantId>,
+    pub discount: PromoDiscount,
+    pub max_uses: Option<u32>,
+    pub max_uses_per_customer: Option<u32>,
+    pub valid_from: Timestamp,
+    pub valid_until: Timestamp,
+    pub uses: u32,
+    pub active: bool,
+}
+
+impl Default for PromoCode {
+    fn default() -> Self {
+        PromoCode {
+            creator: ZERO_ADDRESS.into(),
+            restaurant_id: None,
+            discount: PromoDiscount::Amount(0),
+            max_uses: None,
+            max_uses_per_customer: None,
+            valid_from: Default::default(),
+            valid_until: Default::default(),
+            uses: Default::default(),
+            active: false,
+        }
+    }
+}
+
+// A courier's signed claim of a contactless drop-off. Anyone may relay it,
+// the signature binds it to the courier's registered attestation key.
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct DropOffAttestation {
+    pub delivery_id: DeliveryId,
+    pub order_id: OrderId,
+    pub timestamp: Timestamp,
+    pub location_hash: Option<Hash>,
+    pub photo_hash: Option<Hash>,
+}
+
+impl DropOffAttestation {
+    // What the courier signs: blake2x256 of the SCALE encoded contract
+    // address and attestation, so it cannot be replayed on another contract.
+    pub fn signing_hash(&self, contract: &AccountId) -> [u8; 32] {
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_encoded::<Blake2x256, _>(&(contract, self), &mut output);
+        output
+    }
+}
+
+// Terms couriers are bonded under. The slash rate is in basis points of the
+// posted bond, periods are in milliseconds and a zero delivery timeout
+// never times deliveries out.
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct BondPolicy {
+    pub minimum_bond: u128,
+    pub lock_period: u64,
+    pub slash_rate: u16,
+    pub delivery_timeout: u64,
+}
+
+// A courier's bond, `unlocks_at` is set once the courier deregistered.
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct DeliverBond {
+    pub amount: u128,
+    pub unlocks_at: Option<Timestamp>,
+}
+
+// Settings a market starts out with when the factory deploys it for a city.
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct MarketConfig {
+    pub delivery_fee: u128,
+    pub food_commission: u16,
+    pub delivery_commission: u16,
+    pub loyalty_rate: u16,
+}
+
 pub const FOODORDER_STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
 
+// What every contract of a market keeps: the manager, the registered
+// restaurants and couriers, the fees and, in a split deployment, the
+// addresses of the other contracts.
 #[derive(Debug)]
 #[openbrush::upgradeable_storage(FOODORDER_STORAGE_KEY)]
-
 pub struct Data {
     pub manager: AccountId,
-    pub food_id: u64,
-    pub order_id: u64,
-    pub delivery_id: u64,
-    pub customer_id: u64,
     pub restaurant_id: u64,
     pub deliver_id: u64,
-    pub customers: Mapping<CustomerId, Customer>,
+    pub delivery_fee: u128,
+    pub food_commission: u16,
+    pub delivery_commission: u16,
     pub restaurants: Mapping<RestaurantId, Restaurant>,
     pub delivers: Mapping<DeliverId, Deliver>,
-    pub food_data: Mapping<FoodId, Food>,
-    pub order_data: Mapping<OrderId, Order>,
-    pub delivery_data: Mapping<DeliveryId, Delivery>,
-    pub restaurant_food_data: Mapping<RestaurantId, Vec<FoodId>>,
-    pub restaurant_order_data: Mapping<RestaurantId, Vec<OrderId>>,
-    pub customer_order_data: Mapping<CustomerId, Vec<OrderId>>,
-    pub deliver_delivery_data: Mapping<DeliverId, Vec<DeliveryId>>,
-    pub customer_whitelist: Vec<AccountId>,
+    pub restaurant_commission: Mapping<RestaurantId, u16>,
     pub restaurant_whitelist: Vec<AccountId>,
     pub deliver_whitelist: Vec<AccountId>,
-    pub customer_account_id: Mapping<AccountId, CustomerId>,
     pub restaurant_account_id: Mapping<AccountId, RestaurantId>,
     pub deliver_account_id: Mapping<AccountId, DeliverId>,
+    // May replace the contract code, the factory for markets it deployed.
+    pub upgrader: AccountId,
+    // The contracts of a split deployment, zero while a single contract
+    // runs the whole workflow.
+    pub order_contract: AccountId,
+    pub delivery_contract: AccountId,
+    pub payment_contract: AccountId,
 }
 
 impl Default for Data {
     fn default() -> Self {
         Data {
             manager: ZERO_ADDRESS.into(),
+            restaurant_id: 1,
+            deliver_id: 1,
+            delivery_fee: 0,
+            food_commission: 0,
+            delivery_commission: 0,
+            restaurants: Mapping::default(),
+            delivers: Mapping::default(),
+            restaurant_commission: Mapping::default(),
+            restaurant_whitelist: Vec::new(),
+            deliver_whitelist: Vec::new(),
+            restaurant_account_id: Mapping::default(),
+            deliver_account_id: Mapping::default(),
+            upgrader: ZERO_ADDRESS.into(),
+            order_contract: ZERO_ADDRESS.into(),
+            delivery_contract: ZERO_ADDRESS.into(),
+            payment_contract: ZERO_ADDRESS.into(),
+        }
+    }
+}
+
+pub const ORDERING_STORAGE_KEY: u32 = openbrush::storage_unique_key!(OrderingData);
+
+// Customers, foods, orders and everything priced or paid per order.
+#[derive(Debug)]
+#[openbrush::upgradeable_storage(ORDERING_STORAGE_KEY)]
+pub struct OrderingData {
+    pub arbiter: AccountId,
+    pub food_id: u64,
+    pub order_id: u64,
+    pub customer_id: u64,
+    pub dispute_id: u64,
+    pub customers: Mapping<CustomerId, Customer>,
+    pub food_data: Mapping<FoodId, Food>,
+    pub order_data: Mapping<OrderId, Order>,
+    pub dispute_data: Mapping<DisputeId, Dispute>,
+    pub restaurant_food_data: Mapping<RestaurantId, Vec<FoodId>>,
+    pub restaurant_order_data: Mapping<RestaurantId, Vec<OrderId>>,
+    pub customer_order_data: Mapping<CustomerId, Vec<OrderId>>,
+    pub order_status_data: Mapping<OrderId, Vec<StatusChange>>,
+    pub order_dispute_data: Mapping<OrderId, DisputeId>,
+    pub customer_whitelist: Vec<AccountId>,
+    pub customer_account_id: Mapping<AccountId, CustomerId>,
+    pub oracle: AccountId,
+    pub max_rate_age: u64,
+    pub exchange_rates: Mapping<CurrencyCode, ExchangeRate>,
+    pub schedule_id: u64,
+    pub schedule_data: Mapping<ScheduleId, Schedule>,
+    pub customer_schedule_data: Mapping<CustomerId, Vec<ScheduleId>>,
+    pub group_id: u64,
+    pub group_data: Mapping<GroupId, GroupOrder>,
+    // Loyalty points minted per spent native unit, in basis points.
+    pub loyalty_rate: u16,
+    // Scales the loyalty rate of a restaurant's orders, in basis points.
+    pub restaurant_loyalty_multiplier: Mapping<RestaurantId, u16>,
+    pub promo_codes: Mapping<Hash, PromoCode>,
+    pub promo_customer_uses: Mapping<(Hash, CustomerId), u32>,
+}
+
+impl Default for OrderingData {
+    fn default() -> Self {
+        OrderingData {
+            arbiter: ZERO_ADDRESS.into(),
             food_id: 1,
             order_id: 1,
-            deliver_id: 1,
             customer_id: 1,
-            restaurant_id: 1,
-            delivery_id: 1,
+            dispute_id: 1,
             customers: Mapping::default(),
-            restaurants: Mapping::default(),
-            delivers: Mapping::default(),
             food_data: Mapping::default(),
             order_data: Mapping::default(),
-            delivery_data: Mapping::default(),
+            dispute_data: Mapping::default(),
             restaurant_food_data: Mapping::default(),
             restaurant_order_data: Mapping::default(),
             customer_order_data: Mapping::default(),
-            deliver_delivery_data: Mapping::default(),
+            order_status_data: Mapping::default(),
+            order_dispute_data: Mapping::default(),
             customer_whitelist: Vec::new(),
-            restaurant_whitelist: Vec::new(),
-            deliver_whitelist: Vec::new(),
             customer_account_id: Mapping::default(),
-            restaurant_account_id: Mapping::default(),
-            deliver_account_id: Mapping::default(),
+            oracle: ZERO_ADDRESS.into(),
+            // one hour
+            max_rate_age: 3_600_000,
+            exchange_rates: Mapping::default(),
+            schedule_id: 1,
+            schedule_data: Mapping::default(),
+            customer_schedule_data: Mapping::default(),
+            group_id: 1,
+            group_data: Mapping::default(),
+            loyalty_rate: 0,
+            restaurant_loyalty_multiplier: Mapping::default(),
+            promo_codes: Mapping::default(),
+            promo_customer_uses: Mapping::default(),
+        }
+    }
+}
+
+pub const DELIVERY_STORAGE_KEY: u32 = openbrush::storage_unique_key!(DeliveryData);
+
+// Deliveries and the bonds of the couriers who run them.
+#[derive(Debug)]
+#[openbrush::upgradeable_storage(DELIVERY_STORAGE_KEY)]
+pub struct DeliveryData {
+    pub delivery_id: u64,
+    pub delivery_data: Mapping<DeliveryId, Delivery>,
+    pub deliver_delivery_data: Mapping<DeliverId, Vec<DeliveryId>>,
+    pub order_delivery_data: Mapping<OrderId, DeliveryId>,
+    pub delivery_attestations: Mapping<DeliveryId, DropOffAttestation>,
+    pub bond_policy: BondPolicy,
+    pub deliver_bonds: Mapping<DeliverId, DeliverBond>,
+}
+
+impl Default for DeliveryData {
+    fn default() -> Self {
+        DeliveryData {
+            delivery_id: 1,
+            delivery_data: Mapping::default(),
+            deliver_delivery_data: Mapping::default(),
+            order_delivery_data: Mapping::default(),
+            delivery_attestations: Mapping::default(),
+            bond_policy: Default::default(),
+            deliver_bonds: Mapping::default(),
         }
     }
 }
 
+pub const PAYMENT_STORAGE_KEY: u32 = openbrush::storage_unique_key!(PaymentData);
+
+// Withdrawable balances, the platform treasury and the payment processors
+// with the charges they hold on orders.
+#[derive(Debug)]
+#[openbrush::upgradeable_storage(PAYMENT_STORAGE_KEY)]
+pub struct PaymentData {
+    pub treasury: u128,
+    pub balances: Mapping<AccountId, u128>,
+    pub payment_processor_id: u64,
+    pub payment_processors: Mapping<PaymentProcessorId, PaymentProcessor>,
+    pub payment_processor_whitelist: Vec<AccountId>,
+    pub payment_processor_account_id: Mapping<AccountId, PaymentProcessorId>,
+    // The processor the customer or restaurant picked to charge an order.
+    pub order_processors: Mapping<OrderId, PaymentProcessorId>,
+    pub charge_data: Mapping<OrderId, Charge>,
+    pub charge_holds: Mapping<OrderId, ChargeHold>,
+    // How long a processor may leave an accepted order's charge uncaptured
+    // before anyone can void it.
+    pub capture_timeout: u64,
+}
 
+impl Default for PaymentData {
+    fn default() -> Self {
+        PaymentData {
+            treasury: 0,
+            balances: Mapping::default(),
+            payment_processor_id: 1,
+            payment_processors: Mapping::default(),
+            payment_processor_whitelist: Vec::new(),
+            payment_processor_account_id: Mapping::default(),
+            order_processors: Mapping::default(),
+            charge_data: Mapping::default(),
+            charge_holds: Mapping::default(),
+            // seven days
+            capture_timeout: 604_800_000,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+#[openbrush::trait_definition]
+pub trait BalanceService {
+
+    #[ink(message)]
+    fn withdraw(
+        &mut self,
+        amount: u128,
+    );
+}
@@ -0,0 +1,37 @@
+use crate::impls::{
+    types::DeliveryId,
+};
+
+#[openbrush::trait_definition]
+pub trait BondService {
+
+    #[ink(message, payable)]
+    fn post_bond(
+        &mut self,
+    );
+
+    #[ink(message)]
+    fn deregister_deliver(
+        &mut self,
+    );
+
+    #[ink(message)]
+    fn withdraw_bond(
+        &mut self,
+    );
+
+    #[ink(message)]
+    fn time_out_delivery(
+        &mut self,
+        delivery_id: DeliveryId,
+    );
+
+    #[ink(message)]
+    fn set_bond_policy(
+        &mut self,
+        minimum_bond: u128,
+        lock_period: u64,
+        slash_rate: u16,
+        delivery_timeout: u64,
+    );
+}
@@ -0,0 +1,59 @@
+use crate::impls::{
+    types::{CustomerId, OrderId, OrderStatus, RestaurantId, StatusChange},
+};
+use ink::prelude::vec::Vec;
+use openbrush::traits::Timestamp;
+
+pub const APPEND_RESTAURANT_ORDER: u32 = 0x464f_0001;
+pub const APPEND_CUSTOMER_ORDER: u32 = 0x464f_0002;
+pub const RECORD_STATUS: u32 = 0x464f_0003;
+pub const RESTAURANT_ORDERS: u32 = 0x464f_0004;
+pub const CUSTOMER_ORDERS: u32 = 0x464f_0005;
+pub const STATUS_HISTORY: u32 = 0x464f_0006;
+
+// Order indexes and status history kept by a runtime pallet instead of
+// contract storage. Enabled with the `chain-extension` feature.
+#[ink::chain_extension]
+pub trait BookkeepingExtension {
+    type ErrorCode = BookkeepingError;
+
+    #[ink(extension = 0x464f_0001)]
+    fn append_restaurant_order(restaurant_id: RestaurantId, order_id: OrderId) -> Result<(), BookkeepingError>;
+
+    #[ink(extension = 0x464f_0002)]
+    fn append_customer_order(customer_id: CustomerId, order_id: OrderId) -> Result<(), BookkeepingError>;
+
+    #[ink(extension = 0x464f_0003)]
+    fn record_status(order_id: OrderId, status: OrderStatus, timestamp: Timestamp) -> Result<(), BookkeepingError>;
+
+    #[ink(extension = 0x464f_0004)]
+    fn restaurant_orders(restaurant_id: RestaurantId) -> Result<Vec<OrderId>, BookkeepingError>;
+
+    #[ink(extension = 0x464f_0005)]
+    fn customer_orders(customer_id: CustomerId) -> Result<Vec<OrderId>, BookkeepingError>;
+
+    #[ink(extension = 0x464f_0006)]
+    fn status_history(order_id: OrderId) -> Result<Vec<StatusChange>, BookkeepingError>;
+}
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BookkeepingError {
+    Failed,
+    DecodeFailed,
+}
+
+impl From<scale::Error> for BookkeepingError {
+    fn from(_: scale::Error) -> Self {
+        BookkeepingError::DecodeFailed
+    }
+}
+
+impl ink::env::chain_extension::FromStatusCode for BookkeepingError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            _ => Err(BookkeepingError::Failed),
+        }
+    }
+}
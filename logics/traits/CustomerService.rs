@@ -1,9 +1,10 @@
 use crate::impls::{
-    types::{FoodId, OrderId, RestaurantId,},
+    types::{FoodId, OrderId, RestaurantId, PersonalData},
 };
 use ink::prelude::{
     string::String,
 };
+use openbrush::traits::{Hash, Timestamp};
 #[openbrush::trait_definition]
 pub trait CustomerService {
 
@@ -11,17 +12,23 @@ pub trait CustomerService {
     fn add_customer(
         &mut self,
         customer_name: String,
-        customer_address: String,
-        phone_number: String
+        customer_address: PersonalData,
+        phone_number: PersonalData,
     );
 
+    // `handoff_hash` hashes a code of at least `MIN_HANDOFF_CODE_LEN`
+    // random characters, a shorter code can never complete the handoff.
     #[ink(message, payable)]
     fn submit_order(
         &mut self, 
         food_id: FoodId,
         restaurant_id: RestaurantId,
-        delivery_address: String,
-        phone_number: String,
+        delivery_address: PersonalData,
+        phone_number: PersonalData,
+        requested_time: Option<Timestamp>,
+        redeem_points: u128,
+        promo_code: Option<String>,
+        handoff_hash: Option<Hash>,
     );
 
     #[ink(message, payable)]
@@ -30,4 +37,10 @@ pub trait CustomerService {
         order_id: OrderId,
     );
 
+    #[ink(message)]
+    fn cancel_order(
+        &mut self,
+        order_id: OrderId,
+    );
+
 }
\ No newline at end of file
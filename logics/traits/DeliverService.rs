@@ -1,6 +1,7 @@
 use crate::impls::{
-    types::{DeliveryId},
+    types::{DeliveryId, DropOffAttestation, EcdsaAddress, EcdsaSignature, PublicKey},
 };
+use ink::prelude::string::String;
 
 #[openbrush::trait_definition]
 pub trait DeliverService {
@@ -10,5 +11,32 @@ pub trait DeliverService {
         &mut self,
         delivery_id: DeliveryId,
     );
-    
+
+    // The code must be at least `MIN_HANDOFF_CODE_LEN` characters, such as
+    // 16 random bytes hex encoded, since its hash is public.
+    #[ink(message)]
+    fn complete_handoff(
+        &mut self,
+        delivery_id: DeliveryId,
+        handoff_code: String,
+    );
+
+    #[ink(message)]
+    fn submit_attestation(
+        &mut self,
+        attestation: DropOffAttestation,
+        signature: EcdsaSignature,
+    );
+
+    #[ink(message)]
+    fn set_deliver_encryption_key(
+        &mut self,
+        encryption_key: Option<PublicKey>,
+    );
+
+    #[ink(message)]
+    fn set_deliver_attestation_key(
+        &mut self,
+        attestation_key: Option<EcdsaAddress>,
+    );
 }
\ No newline at end of file
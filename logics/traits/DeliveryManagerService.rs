@@ -0,0 +1,17 @@
+use ink::prelude::string::String;
+use openbrush::traits::AccountId;
+
+// The courier registry, managed on the delivery contract of a split
+// deployment.
+#[openbrush::trait_definition]
+pub trait DeliveryManagerService {
+
+    #[ink(message)]
+    fn add_deliver(
+        &mut self,
+        deliver_account: AccountId,
+        deliver_name: String,
+        deliver_address: String,
+        phone_number: String,
+    );
+}
@@ -0,0 +1,44 @@
+use crate::impls::{
+    types::{CustomerId, DeliverId, DeliveryId, OrderId, PersonalData, RestaurantId},
+};
+use ink::prelude::vec::Vec;
+use openbrush::traits::AccountId;
+
+// Called by the order contract of a split deployment to open and update
+// deliveries and to slash couriers.
+#[openbrush::trait_definition]
+pub trait DeliveryWorkflow {
+
+    #[ink(message)]
+    fn dispatch_delivery(
+        &mut self,
+        order_id: OrderId,
+        restaurant_id: RestaurantId,
+        customer_id: CustomerId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId;
+
+    #[ink(message)]
+    fn update_delivery_address(
+        &mut self,
+        order_id: OrderId,
+        delivery_address: PersonalData,
+    ) -> DeliveryId;
+
+    #[ink(message)]
+    fn cancel_delivery(
+        &mut self,
+        order_id: OrderId,
+    );
+
+    #[ink(message)]
+    fn slash_deliver(
+        &mut self,
+        deliver_id: DeliverId,
+        order_id: OrderId,
+        payers: Vec<(AccountId, u128)>,
+    ) -> u128;
+}
+
+#[openbrush::wrapper]
+pub type DeliveryWorkflowRef = dyn DeliveryWorkflow;
@@ -0,0 +1,36 @@
+use crate::impls::{
+    types::{DisputeId, DisputeResolution, OrderId},
+};
+use openbrush::traits::{AccountId, Hash};
+
+#[openbrush::trait_definition]
+pub trait DisputeService {
+
+    #[ink(message)]
+    fn open_dispute(
+        &mut self,
+        order_id: OrderId,
+        evidence_hash: Hash,
+    );
+
+    #[ink(message)]
+    fn respond_dispute(
+        &mut self,
+        dispute_id: DisputeId,
+        response_hash: Hash,
+    );
+
+    #[ink(message)]
+    fn resolve_dispute(
+        &mut self,
+        dispute_id: DisputeId,
+        resolution: DisputeResolution,
+        deliver_at_fault: bool,
+    );
+
+    #[ink(message)]
+    fn set_arbiter(
+        &mut self,
+        arbiter: AccountId,
+    );
+}
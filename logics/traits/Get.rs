@@ -1,11 +1,18 @@
 use crate::impls::{
-    types::{Food, FoodId, OrderId, DeliverId, CustomerId, RestaurantId, Delivery, DeliveryId, Order},
+    types::{StatusChange, Food, FoodId, OrderId, Deliver, DeliverId, CustomerId, Restaurant, RestaurantId, Dispute, DisputeId, Order, CurrencyCode, ExchangeRate, Schedule, ScheduleId, GroupId, GroupOrder, PromoCode},
 };
 use ink::prelude::vec::Vec;
+use openbrush::traits::{Hash, Timestamp};
 
 #[openbrush::trait_definition]
 pub trait Get {
 
+    #[ink(message)]
+    fn get_restaurant_from_id(&self, restaurant_id: RestaurantId) -> Restaurant;
+
+    #[ink(message)]
+    fn get_deliver_from_id(&self, deliver_id: DeliverId) -> Deliver;
+
     #[ink(message)]
     fn get_eta(&self, order_id: OrderId) -> u64;
 
@@ -21,6 +28,9 @@ pub trait Get {
     #[ink(message)]
     fn get_order_all(&self, from: u64, to: u64) -> Vec<Order>;
 
+    #[ink(message)]
+    fn get_order_status_history(&self, order_id: OrderId) -> Vec<StatusChange>;
+
     #[ink(message)]
     fn get_food_from_id(&self, food_id: FoodId) -> Food;
 
@@ -31,12 +41,50 @@ pub trait Get {
     fn get_food_all(&self, from: u64, to: u64) -> Vec<Food>;
 
     #[ink(message)]
-    fn get_delivery_from_id(&self, delivery_id: DeliveryId) -> Delivery;
+    fn get_dispute_from_id(&self, dispute_id: DisputeId) -> Dispute;
+
+    #[ink(message)]
+    fn get_dispute_from_order(&self, order_id: OrderId) -> Dispute;
+
+    #[ink(message)]
+    fn get_schedule_from_id(&self, schedule_id: ScheduleId) -> Schedule;
+
+    #[ink(message)]
+    fn get_schedule_from_customer(&self, customer_id: CustomerId) -> Vec<u64>;
+
+    #[ink(message)]
+    fn get_group_order_from_id(&self, group_id: GroupId) -> GroupOrder;
+
+    #[ink(message)]
+    fn get_exchange_rate(&self, currency: CurrencyCode) -> ExchangeRate;
 
     #[ink(message)]
-    fn get_delivery_from_deliver(&self, deliver_id: DeliverId) -> Vec<u64>;
+    fn get_food_price(&self, food_id: FoodId) -> u128;
 
     #[ink(message)]
-    fn get_delivery_all(&self, from: u64, to: u64) -> Vec<Delivery>;
-    
-}
\ No newline at end of file
+    fn get_delivery_fee(&self) -> u128;
+
+    #[ink(message)]
+    fn get_food_commission(&self, restaurant_id: RestaurantId) -> u16;
+
+    #[ink(message)]
+    fn get_delivery_commission(&self) -> u16;
+
+    #[ink(message)]
+    fn get_loyalty_rate(&self, restaurant_id: RestaurantId) -> u16;
+
+    #[ink(message)]
+    fn get_promo_code(&self, code_hash: Hash) -> PromoCode;
+
+    #[ink(message)]
+    fn get_promo_code_uses(&self, code_hash: Hash, customer_id: CustomerId) -> u32;
+
+    #[ink(message)]
+    fn is_restaurant_open(&self, restaurant_id: RestaurantId, timestamp: Timestamp) -> bool;
+
+    #[ink(message)]
+    fn get_event_schema_version(&self) -> u16;
+}
+
+#[openbrush::wrapper]
+pub type GetRef = dyn Get;
\ No newline at end of file
@@ -0,0 +1,34 @@
+use crate::impls::{
+    types::{DeliverId, Delivery, DeliveryId, OrderId, DropOffAttestation, BondPolicy, DeliverBond},
+};
+use ink::prelude::vec::Vec;
+
+// Read by the order and payment contracts of a split deployment, which do not
+// keep deliveries or bonds themselves.
+#[openbrush::trait_definition]
+pub trait GetDelivery {
+
+    #[ink(message)]
+    fn get_delivery_from_id(&self, delivery_id: DeliveryId) -> Delivery;
+
+    #[ink(message)]
+    fn get_delivery_from_order(&self, order_id: OrderId) -> DeliveryId;
+
+    #[ink(message)]
+    fn get_delivery_attestation(&self, delivery_id: DeliveryId) -> DropOffAttestation;
+
+    #[ink(message)]
+    fn get_deliver_bond(&self, deliver_id: DeliverId) -> DeliverBond;
+
+    #[ink(message)]
+    fn get_bond_policy(&self) -> BondPolicy;
+
+    #[ink(message)]
+    fn get_delivery_from_deliver(&self, deliver_id: DeliverId) -> Vec<u64>;
+
+    #[ink(message)]
+    fn get_delivery_all(&self, from: u64, to: u64) -> Vec<Delivery>;
+}
+
+#[openbrush::wrapper]
+pub type GetDeliveryRef = dyn GetDelivery;
@@ -0,0 +1,25 @@
+use crate::impls::{
+    types::{OrderId, PaymentProcessor, PaymentProcessorId, Charge},
+};
+use openbrush::traits::AccountId;
+
+// Read by the order and delivery contracts of a split deployment, which do
+// not keep balances or charges themselves.
+#[openbrush::trait_definition]
+pub trait GetPayment {
+
+    #[ink(message)]
+    fn get_treasury_balance(&self) -> u128;
+
+    #[ink(message)]
+    fn get_balance(&self, account: AccountId) -> u128;
+
+    #[ink(message)]
+    fn get_payment_processor_from_id(&self, processor_id: PaymentProcessorId) -> PaymentProcessor;
+
+    #[ink(message)]
+    fn get_charge_from_order(&self, order_id: OrderId) -> Charge;
+}
+
+#[openbrush::wrapper]
+pub type GetPaymentRef = dyn GetPayment;
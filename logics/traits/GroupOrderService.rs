@@ -0,0 +1,39 @@
+use crate::impls::{
+    types::{FoodId, GroupId, RestaurantId, PersonalData},
+};
+
+#[openbrush::trait_definition]
+pub trait GroupOrderService {
+
+    #[ink(message)]
+    fn open_group_order(
+        &mut self,
+        restaurant_id: RestaurantId,
+        delivery_address: PersonalData,
+    );
+
+    #[ink(message)]
+    fn add_group_item(
+        &mut self,
+        group_id: GroupId,
+        food_id: FoodId,
+    );
+
+    #[ink(message, payable)]
+    fn fund_group_share(
+        &mut self,
+        group_id: GroupId,
+    );
+
+    #[ink(message)]
+    fn submit_group_order(
+        &mut self,
+        group_id: GroupId,
+    );
+
+    #[ink(message)]
+    fn cancel_group_order(
+        &mut self,
+        group_id: GroupId,
+    );
+}
@@ -0,0 +1,20 @@
+use crate::impls::{
+    types::RestaurantId,
+};
+
+#[openbrush::trait_definition]
+pub trait LoyaltyService {
+
+    #[ink(message)]
+    fn set_loyalty_rate(
+        &mut self,
+        loyalty_rate: u16,
+    );
+
+    #[ink(message)]
+    fn set_restaurant_loyalty_multiplier(
+        &mut self,
+        restaurant_id: RestaurantId,
+        multiplier: Option<u16>,
+    );
+}
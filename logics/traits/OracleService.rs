@@ -0,0 +1,28 @@
+use crate::impls::{
+    types::CurrencyCode,
+};
+use openbrush::traits::{AccountId, Timestamp};
+
+#[openbrush::trait_definition]
+pub trait OracleService {
+
+    #[ink(message)]
+    fn post_exchange_rate(
+        &mut self,
+        currency: CurrencyCode,
+        rate: u128,
+        timestamp: Timestamp,
+    );
+
+    #[ink(message)]
+    fn set_oracle(
+        &mut self,
+        oracle: AccountId,
+    );
+
+    #[ink(message)]
+    fn set_max_rate_age(
+        &mut self,
+        max_rate_age: u64,
+    );
+}
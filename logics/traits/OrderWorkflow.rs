@@ -0,0 +1,47 @@
+use crate::impls::{
+    types::{Deliver, DeliverId, OrderId},
+};
+use ink::prelude::vec::Vec;
+use openbrush::traits::AccountId;
+
+// Called by the delivery and payment contracts of a split deployment to
+// move the orders they deliver and charge along.
+#[openbrush::trait_definition]
+pub trait OrderWorkflow {
+
+    #[ink(message)]
+    fn pick_up_order(
+        &mut self,
+        order_id: OrderId,
+        deliver_id: DeliverId,
+        deliver: Deliver,
+    );
+
+    #[ink(message)]
+    fn accept_delivery(
+        &mut self,
+        order_id: OrderId,
+    );
+
+    #[ink(message)]
+    fn time_out_order(
+        &mut self,
+        order_id: OrderId,
+    ) -> Vec<(AccountId, u128)>;
+
+    // The customer and restaurant accounts of an order.
+    #[ink(message)]
+    fn get_order_accounts(
+        &self,
+        order_id: OrderId,
+    ) -> (AccountId, AccountId);
+
+    #[ink(message)]
+    fn charge_order(
+        &mut self,
+        order_id: OrderId,
+    );
+}
+
+#[openbrush::wrapper]
+pub type OrderWorkflowRef = dyn OrderWorkflow;
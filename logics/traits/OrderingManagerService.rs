@@ -0,0 +1,40 @@
+use crate::impls::{
+    types::RestaurantId,
+};
+use ink::prelude::string::String;
+use openbrush::traits::AccountId;
+
+// The restaurants and what an order costs, managed on the order contract
+// of a split deployment.
+#[openbrush::trait_definition]
+pub trait OrderingManagerService {
+
+    #[ink(message)]
+    fn add_restaurant(
+        &mut self,
+        restaurant_account: AccountId,
+        restaurant_name: String,
+        restaurant_address: String,
+        phone_number: String,
+    );
+
+    #[ink(message)]
+    fn set_delivery_fee(
+        &mut self,
+        delivery_fee: u128,
+    );
+
+    #[ink(message)]
+    fn set_commission(
+        &mut self,
+        food_commission: u16,
+        delivery_commission: u16,
+    );
+
+    #[ink(message)]
+    fn set_restaurant_commission(
+        &mut self,
+        restaurant_id: RestaurantId,
+        food_commission: Option<u16>,
+    );
+}
@@ -0,0 +1,49 @@
+use crate::impls::types::OrderId;
+use openbrush::traits::AccountId;
+
+// Called by the order and delivery contracts of a split deployment, which
+// send the funds along with the credit.
+#[openbrush::trait_definition]
+pub trait PaymentLedger {
+
+    #[ink(message, payable)]
+    fn credit(
+        &mut self,
+        account: AccountId,
+    );
+
+    #[ink(message, payable)]
+    fn credit_treasury(
+        &mut self,
+    );
+
+    #[ink(message)]
+    fn draw_treasury(
+        &mut self,
+        amount: u128,
+    );
+
+    #[ink(message)]
+    fn awaits_capture(
+        &self,
+        order_id: OrderId,
+    ) -> bool;
+
+    // The restaurant share of an order settled while its charge waits for
+    // the processor to capture it.
+    #[ink(message, payable)]
+    fn hold_for_capture(
+        &mut self,
+        order_id: OrderId,
+        restaurant_account: AccountId,
+    );
+
+    #[ink(message)]
+    fn cancel_charge(
+        &mut self,
+        order_id: OrderId,
+    );
+}
+
+#[openbrush::wrapper]
+pub type PaymentLedgerRef = dyn PaymentLedger;
@@ -0,0 +1,37 @@
+use crate::impls::{
+    types::PaymentProcessorId,
+};
+use ink::prelude::string::String;
+use openbrush::traits::AccountId;
+
+// The payment processors and the treasury, managed on the payment contract
+// of a split deployment.
+#[openbrush::trait_definition]
+pub trait PaymentManagerService {
+
+    #[ink(message)]
+    fn add_payment_processor(
+        &mut self,
+        processor_account: AccountId,
+        processor_name: String,
+    );
+
+    #[ink(message)]
+    fn set_processor_fee_rate(
+        &mut self,
+        processor_id: PaymentProcessorId,
+        fee_rate: u16,
+    );
+
+    #[ink(message)]
+    fn set_capture_timeout(
+        &mut self,
+        capture_timeout: u64,
+    );
+
+    #[ink(message)]
+    fn withdraw_treasury(
+        &mut self,
+        amount: u128,
+    );
+}
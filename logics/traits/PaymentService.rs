@@ -0,0 +1,42 @@
+use crate::impls::{
+    types::{OrderId, PaymentProcessorId},
+};
+use ink::prelude::{
+    string::String,
+};
+
+#[openbrush::trait_definition]
+pub trait PaymentService {
+
+    // The order's customer or restaurant picks the processor allowed to
+    // charge it.
+    #[ink(message)]
+    fn select_payment_processor(
+        &mut self,
+        order_id: OrderId,
+        processor_id: PaymentProcessorId,
+    );
+
+    #[ink(message)]
+    fn authorize_charge(
+        &mut self,
+        order_id: OrderId,
+        charge_reference: String,
+        fee: u128,
+    );
+
+    #[ink(message)]
+    fn capture_charge(
+        &mut self,
+        order_id: OrderId,
+    );
+
+    // Pays the held share of an accepted order whose charge was never
+    // captured to its restaurant, by the manager or by anyone once the
+    // capture timeout has passed.
+    #[ink(message)]
+    fn void_charge(
+        &mut self,
+        order_id: OrderId,
+    );
+}
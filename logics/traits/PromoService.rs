@@ -0,0 +1,26 @@
+use crate::impls::{
+    types::{PromoDiscount, RestaurantId},
+};
+use openbrush::traits::{Hash, Timestamp};
+
+#[openbrush::trait_definition]
+pub trait PromoService {
+
+    #[ink(message)]
+    fn add_promo_code(
+        &mut self,
+        code_hash: Hash,
+        restaurant_id: Option<RestaurantId>,
+        discount: PromoDiscount,
+        max_uses: Option<u32>,
+        max_uses_per_customer: Option<u32>,
+        valid_from: Timestamp,
+        valid_until: Timestamp,
+    );
+
+    #[ink(message)]
+    fn revoke_promo_code(
+        &mut self,
+        code_hash: Hash,
+    );
+}
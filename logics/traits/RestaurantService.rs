@@ -1,9 +1,11 @@
 use crate::impls::{
-    types::{FoodId, OrderId,},
+    types::{CurrencyCode, FoodId, OrderId, OpeningSlot, PublicKey, SealedBox},
 };
 use ink::prelude::{
     string::String,
+    vec::Vec,
 };
+use openbrush::traits::Timestamp;
 
 #[openbrush::trait_definition]
 pub trait RestaurantService {
@@ -27,12 +29,77 @@ pub trait RestaurantService {
         eta: u64,
     );
 
+    #[ink(message)]
+    fn set_food_availability(
+        &mut self,
+        food_id: FoodId,
+        available: bool,
+    );
+
+    #[ink(message)]
+    fn set_food_stock(
+        &mut self,
+        food_id: FoodId,
+        stock: Option<u32>,
+    );
+
+    #[ink(message)]
+    fn set_food_currency(
+        &mut self,
+        food_id: FoodId,
+        currency: Option<CurrencyCode>,
+    );
+
+    #[ink(message)]
+    fn remove_food(
+        &mut self,
+        food_id: FoodId,
+    );
+
+    #[ink(message)]
+    fn set_opening_hours(
+        &mut self,
+        opening_hours: Vec<OpeningSlot>,
+    );
+
+    #[ink(message)]
+    fn add_closure(
+        &mut self,
+        start: Timestamp,
+        end: Timestamp,
+    );
+
+    #[ink(message)]
+    fn remove_closure(
+        &mut self,
+        start: Timestamp,
+    );
+
+    #[ink(message)]
+    fn set_restaurant_encryption_key(
+        &mut self,
+        encryption_key: Option<PublicKey>,
+    );
+
+    #[ink(message)]
+    fn share_delivery_address(
+        &mut self,
+        order_id: OrderId,
+        sealed_address: SealedBox,
+    );
+
     #[ink(message)]
     fn confirm_order(
         &mut self,
         order_id: OrderId,
     );
 
+    #[ink(message)]
+    fn reject_order(
+        &mut self,
+        order_id: OrderId,
+    );
+
     #[ink(message)]
     fn deliver_order(
         &mut self,
@@ -0,0 +1,31 @@
+use crate::impls::{
+    types::{FoodId, RestaurantId, PersonalData, ScheduleId},
+};
+use ink::prelude::vec::Vec;
+
+#[openbrush::trait_definition]
+pub trait ScheduleService {
+
+    #[ink(message, payable)]
+    fn create_schedule(
+        &mut self,
+        food_id: FoodId,
+        restaurant_id: RestaurantId,
+        delivery_address: PersonalData,
+        weekdays: Vec<u8>,
+        minute: u32,
+        occurrences: u32,
+    );
+
+    #[ink(message)]
+    fn trigger_schedule(
+        &mut self,
+        schedule_id: ScheduleId,
+    );
+
+    #[ink(message)]
+    fn cancel_schedule(
+        &mut self,
+        schedule_id: ScheduleId,
+    );
+}
@@ -0,0 +1,20 @@
+use openbrush::traits::AccountId;
+
+// Links the order, delivery and payment contracts of a split deployment.
+#[openbrush::trait_definition]
+pub trait WorkflowLink {
+
+    #[ink(message)]
+    fn set_workflow_contracts(
+        &mut self,
+        order_contract: AccountId,
+        delivery_contract: AccountId,
+        payment_contract: AccountId,
+    );
+
+    #[ink(message)]
+    fn get_workflow_contracts(&self) -> (AccountId, AccountId, AccountId);
+}
+
+#[openbrush::wrapper]
+pub type WorkflowLinkRef = dyn WorkflowLink;
@@ -1,5 +1,24 @@
 pub mod CustomerService;
 pub mod ManagerService;
+pub mod OrderingManagerService;
+pub mod DeliveryManagerService;
+pub mod PaymentManagerService;
 pub mod RestaurantService;
 pub mod DeliverService;
-pub mod Get;
\ No newline at end of file
+pub mod DisputeService;
+pub mod PaymentService;
+pub mod OracleService;
+pub mod ScheduleService;
+pub mod GroupOrderService;
+pub mod LoyaltyService;
+pub mod PromoService;
+pub mod BalanceService;
+pub mod BondService;
+pub mod Bookkeeping;
+pub mod Get;
+pub mod GetDelivery;
+pub mod GetPayment;
+pub mod OrderWorkflow;
+pub mod DeliveryWorkflow;
+pub mod PaymentLedger;
+pub mod WorkflowLink;
\ No newline at end of file
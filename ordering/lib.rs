@@ -0,0 +1,1581 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(min_specialization)]
+
+//! The ordering business of a split deployment: restaurants, customers,
+//! orders, disputes and loyalty. Deliveries are opened with the delivery
+//! contract and every payout is credited with the payment contract.
+
+pub use self::ordering::{FoodOrdering, FoodOrderingRef};
+
+#[openbrush::contract]
+pub mod ordering {
+    use ink::codegen::EmitEvent;
+    use ink::codegen::Env;
+    use ink::prelude::{
+        string::String,
+        vec::Vec,
+    };
+    use logics::{
+        impls::{
+            Bonding::Bonding,
+            CustomerService::CustomerServiceEvents,
+            Dispatch::Dispatch,
+            DisputeService::DisputeServiceEvents,
+            GroupOrderService::GroupOrderServiceEvents,
+            Holding::Holding,
+            Ledger::Ledger,
+            Loyalty::Loyalty,
+            LoyaltyService::LoyaltyServiceEvents,
+            ManagerService::ManagerServiceEvents,
+            OracleService::OracleServiceEvents,
+            OrderingManagerService::OrderingManagerServiceEvents,
+            PromoService::PromoServiceEvents,
+            RestaurantService::RestaurantServiceEvents,
+            ScheduleService::ScheduleServiceEvents,
+            Settlement::SettlementEvents,
+            WorkflowLink::WorkflowLinkEvents,
+            types::{Data, OrderingData, EVENT_SCHEMA_VERSION, OrderId, FoodId, RestaurantId, CustomerId, DeliverId, DeliveryId, DisputeId, DisputeResolution, OpeningSlot, Closure, PersonalData, PublicKey, CurrencyCode, ScheduleId, GroupId},
+        },
+        traits::{
+            CustomerService::CustomerService,
+            DeliveryWorkflow::DeliveryWorkflowRef,
+            DisputeService::DisputeService,
+            Get::Get,
+            GetDelivery::GetDeliveryRef,
+            GetPayment::GetPaymentRef,
+            GroupOrderService::GroupOrderService,
+            LoyaltyService::LoyaltyService,
+            ManagerService::ManagerService,
+            OracleService::OracleService,
+            OrderingManagerService::OrderingManagerService,
+            OrderWorkflow::OrderWorkflow,
+            PaymentLedger::PaymentLedgerRef,
+            PromoService::PromoService,
+            RestaurantService::RestaurantService,
+            ScheduleService::ScheduleService,
+            WorkflowLink::WorkflowLink,
+        },
+    };
+    use openbrush::contracts::psp22::{self, PSP22};
+    use openbrush::traits::Storage;
+
+    #[ink(storage)]
+    #[derive(Storage, Default)]
+    pub struct FoodOrdering {
+        #[storage_field]
+        food_order_data: Data,
+        #[storage_field]
+        ordering_data: OrderingData,
+        // Balances of the loyalty points token.
+        #[storage_field]
+        psp22: psp22::Data,
+    }
+
+    impl CustomerService for FoodOrdering {}
+
+    impl DisputeService for FoodOrdering {}
+
+    impl Get for FoodOrdering {}
+
+    impl GroupOrderService for FoodOrdering {}
+
+    impl LoyaltyService for FoodOrdering {}
+
+    impl ManagerService for FoodOrdering {}
+
+    impl OracleService for FoodOrdering {}
+
+    impl OrderingManagerService for FoodOrdering {}
+
+    impl OrderWorkflow for FoodOrdering {}
+
+    impl PromoService for FoodOrdering {}
+
+    impl PSP22 for FoodOrdering {}
+
+    impl RestaurantService for FoodOrdering {}
+
+    impl ScheduleService for FoodOrdering {}
+
+    impl WorkflowLink for FoodOrdering {}
+
+    impl FoodOrdering {
+        #[ink(constructor, payable)]
+        pub fn new() -> Self {
+            let mut instance = Self::default();
+            instance.food_order_data.manager = Self::env().caller();
+            instance.food_order_data.upgrader = Self::env().caller();
+            Self::env().emit_event(EventSchemaEvent {
+                version: EVENT_SCHEMA_VERSION,
+            });
+            instance
+        }
+
+        /// Replaces the contract code while keeping its storage.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) {
+            assert!(self.env().caller() == self.food_order_data.upgrader, "Only upgrader can set code!");
+            assert!(self.env().set_code_hash(&code_hash).is_ok(), "Code hash does not exist!");
+            self.env().emit_event(SetCodeEvent {
+                code_hash,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_upgrader(&self) -> AccountId {
+            self.food_order_data.upgrader
+        }
+    }
+
+    #[ink(event)]
+    pub struct SubmitOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        food_id: FoodId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        delivery_address: PersonalData,
+        phone_number: PersonalData,
+    }
+
+    #[ink(event)]
+    pub struct ConfirmDeliveryEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+    }
+
+    #[ink(event)]
+    pub struct CancelOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+    }
+
+    #[ink(event)]
+    pub struct AddFoodEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        food_name: String,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        description: String,
+        price: u128,
+        eta: u64,
+    }
+
+    #[ink(event)]
+    pub struct UpdateFoodEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        food_name: String,
+        description: String,
+        price: u128,
+        eta: u64,
+    }
+
+    #[ink(event)]
+    pub struct UpdateFoodAvailabilityEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        available: bool,
+        stock: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateFoodCurrencyEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        currency: Option<CurrencyCode>,
+    }
+
+    #[ink(event)]
+    pub struct RemoveFoodEvent {
+        #[ink(topic)]
+        food_id: FoodId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+    }
+
+    #[ink(event)]
+    pub struct UpdateOpeningHoursEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        opening_hours: Vec<OpeningSlot>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateClosuresEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        closures: Vec<Closure>,
+    }
+
+    #[ink(event)]
+    pub struct ConfirmOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        eta: u64,
+    }
+
+    #[ink(event)]
+    pub struct RejectOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+    }
+
+    #[ink(event)]
+    pub struct DeliverOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        delivery_id: DeliveryId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        delivery_address: PersonalData,
+    }
+
+    #[ink(event)]
+    pub struct AddRestaurantEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        restaurant_name: String,
+        restaurant_address: String,
+        phone_number: String,
+    }
+
+    #[ink(event)]
+    pub struct OpenDisputeEvent {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        evidence_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct RespondDisputeEvent {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        responder: AccountId,
+        response_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct ResolveDisputeEvent {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        order_id: OrderId,
+        resolution: DisputeResolution,
+    }
+
+    #[ink(event)]
+    pub struct SettleOrderEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        refund: u128,
+        restaurant_amount: u128,
+        deliver_amount: u128,
+        held_amount: u128,
+        platform_amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetCommissionEvent {
+        #[ink(topic)]
+        restaurant_id: Option<RestaurantId>,
+        food_commission: Option<u16>,
+        delivery_commission: u16,
+    }
+
+    #[ink(event)]
+    pub struct CreditBalanceEvent {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        order_id: OrderId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct AddCustomerEvent {
+        #[ink(topic)]
+        customer_id: CustomerId,
+        #[ink(topic)]
+        customer_account: AccountId,
+        customer_name: String,
+    }
+
+    #[ink(event)]
+    pub struct ChangeManagerEvent {
+        #[ink(topic)]
+        previous_manager: AccountId,
+        #[ink(topic)]
+        new_manager: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetDeliveryFeeEvent {
+        delivery_fee: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetWorkflowContractsEvent {
+        #[ink(topic)]
+        order_contract: AccountId,
+        #[ink(topic)]
+        delivery_contract: AccountId,
+        #[ink(topic)]
+        payment_contract: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetArbiterEvent {
+        #[ink(topic)]
+        arbiter: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct UpdateRestaurantEncryptionKeyEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        encryption_key: Option<PublicKey>,
+    }
+
+    #[ink(event)]
+    pub struct PostExchangeRateEvent {
+        #[ink(topic)]
+        currency: CurrencyCode,
+        rate: u128,
+        timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct SetOracleEvent {
+        #[ink(topic)]
+        oracle: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetMaxRateAgeEvent {
+        max_rate_age: u64,
+    }
+
+    #[ink(event)]
+    pub struct CreateScheduleEvent {
+        #[ink(topic)]
+        schedule_id: ScheduleId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        food_id: FoodId,
+        next_time: Timestamp,
+        prepaid: u128,
+    }
+
+    #[ink(event)]
+    pub struct TriggerScheduleEvent {
+        #[ink(topic)]
+        schedule_id: ScheduleId,
+        #[ink(topic)]
+        order_id: Option<OrderId>,
+        next_time: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct CloseScheduleEvent {
+        #[ink(topic)]
+        schedule_id: ScheduleId,
+        refund: u128,
+    }
+
+    #[ink(event)]
+    pub struct OpenGroupOrderEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        organizer_id: CustomerId,
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+    }
+
+    #[ink(event)]
+    pub struct AddGroupItemEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        food_id: FoodId,
+        price: u128,
+    }
+
+    #[ink(event)]
+    pub struct FundGroupShareEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SubmitGroupOrderEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        #[ink(topic)]
+        order_id: OrderId,
+    }
+
+    #[ink(event)]
+    pub struct CancelGroupOrderEvent {
+        #[ink(topic)]
+        group_id: GroupId,
+        refund: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetLoyaltyRateEvent {
+        loyalty_rate: u16,
+    }
+
+    #[ink(event)]
+    pub struct SetLoyaltyMultiplierEvent {
+        #[ink(topic)]
+        restaurant_id: RestaurantId,
+        multiplier: Option<u16>,
+    }
+
+    #[ink(event)]
+    pub struct AddPromoCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+        #[ink(topic)]
+        creator: AccountId,
+        #[ink(topic)]
+        restaurant_id: Option<RestaurantId>,
+    }
+
+    #[ink(event)]
+    pub struct RevokePromoCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct RedeemPromoCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        customer_id: CustomerId,
+        discount: u128,
+    }
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SetCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct EventSchemaEvent {
+        version: u16,
+    }
+
+    impl CustomerServiceEvents for FoodOrdering {
+        fn emit_add_customer_event(
+            &self,
+            customer_id: CustomerId,
+            customer_account: AccountId,
+            customer_name: String,
+        ) {
+            self.env().emit_event(AddCustomerEvent {
+                customer_id,
+                customer_account,
+                customer_name,
+            })
+        }
+
+        fn emit_submit_order_event(
+            &self,
+            order_id: OrderId,
+            food_id: FoodId,
+            restaurant_id: RestaurantId,
+            customer_id: CustomerId,
+            delivery_address: PersonalData,
+            phone_number: PersonalData,
+        ) {
+            self.env().emit_event(SubmitOrderEvent {
+                order_id,
+                food_id,
+                restaurant_id,
+                customer_id,
+                delivery_address,
+                phone_number,
+            })
+        }
+
+        fn emit_confirm_delivery_event(
+            &self,
+            order_id: OrderId,
+        ) {
+            self.env().emit_event(ConfirmDeliveryEvent {
+                order_id,
+            })
+        }
+
+        fn emit_cancel_order_event(
+            &self,
+            order_id: OrderId,
+            customer_id: CustomerId,
+        ) {
+            self.env().emit_event(CancelOrderEvent {
+                order_id,
+                customer_id,
+            })
+        }
+    }
+
+    impl DisputeServiceEvents for FoodOrdering {
+        fn emit_open_dispute_event(
+            &self,
+            dispute_id: DisputeId,
+            order_id: OrderId,
+            customer_id: CustomerId,
+            evidence_hash: Hash,
+        ) {
+            self.env().emit_event(OpenDisputeEvent {
+                dispute_id,
+                order_id,
+                customer_id,
+                evidence_hash,
+            })
+        }
+
+        fn emit_respond_dispute_event(
+            &self,
+            dispute_id: DisputeId,
+            responder: AccountId,
+            response_hash: Hash,
+        ) {
+            self.env().emit_event(RespondDisputeEvent {
+                dispute_id,
+                responder,
+                response_hash,
+            })
+        }
+
+        fn emit_resolve_dispute_event(
+            &self,
+            dispute_id: DisputeId,
+            order_id: OrderId,
+            resolution: DisputeResolution,
+        ) {
+            self.env().emit_event(ResolveDisputeEvent {
+                dispute_id,
+                order_id,
+                resolution,
+            })
+        }
+
+        fn emit_set_arbiter_event(
+            &self,
+            arbiter: AccountId,
+        ) {
+            self.env().emit_event(SetArbiterEvent {
+                arbiter,
+            })
+        }
+    }
+
+    impl GroupOrderServiceEvents for FoodOrdering {
+        fn emit_open_group_order_event(
+            &self,
+            group_id: GroupId,
+            organizer_id: CustomerId,
+            restaurant_id: RestaurantId,
+        ) {
+            self.env().emit_event(OpenGroupOrderEvent {
+                group_id,
+                organizer_id,
+                restaurant_id,
+            })
+        }
+
+        fn emit_add_group_item_event(
+            &self,
+            group_id: GroupId,
+            customer_id: CustomerId,
+            food_id: FoodId,
+            price: u128,
+        ) {
+            self.env().emit_event(AddGroupItemEvent {
+                group_id,
+                customer_id,
+                food_id,
+                price,
+            })
+        }
+
+        fn emit_fund_group_share_event(
+            &self,
+            group_id: GroupId,
+            customer_id: CustomerId,
+            amount: u128,
+        ) {
+            self.env().emit_event(FundGroupShareEvent {
+                group_id,
+                customer_id,
+                amount,
+            })
+        }
+
+        fn emit_submit_group_order_event(
+            &self,
+            group_id: GroupId,
+            order_id: OrderId,
+        ) {
+            self.env().emit_event(SubmitGroupOrderEvent {
+                group_id,
+                order_id,
+            })
+        }
+
+        fn emit_cancel_group_order_event(
+            &self,
+            group_id: GroupId,
+            refund: u128,
+        ) {
+            self.env().emit_event(CancelGroupOrderEvent {
+                group_id,
+                refund,
+            })
+        }
+    }
+
+    impl LoyaltyServiceEvents for FoodOrdering {
+        fn emit_set_loyalty_rate_event(
+            &self,
+            loyalty_rate: u16,
+        ) {
+            self.env().emit_event(SetLoyaltyRateEvent {
+                loyalty_rate,
+            })
+        }
+
+        fn emit_set_loyalty_multiplier_event(
+            &self,
+            restaurant_id: RestaurantId,
+            multiplier: Option<u16>,
+        ) {
+            self.env().emit_event(SetLoyaltyMultiplierEvent {
+                restaurant_id,
+                multiplier,
+            })
+        }
+    }
+
+    impl PromoServiceEvents for FoodOrdering {
+        fn emit_add_promo_code_event(
+            &self,
+            code_hash: Hash,
+            creator: AccountId,
+            restaurant_id: Option<RestaurantId>,
+        ) {
+            self.env().emit_event(AddPromoCodeEvent {
+                code_hash,
+                creator,
+                restaurant_id,
+            })
+        }
+
+        fn emit_revoke_promo_code_event(
+            &self,
+            code_hash: Hash,
+        ) {
+            self.env().emit_event(RevokePromoCodeEvent {
+                code_hash,
+            })
+        }
+
+        fn emit_redeem_promo_code_event(
+            &self,
+            code_hash: Hash,
+            order_id: OrderId,
+            customer_id: CustomerId,
+            discount: u128,
+        ) {
+            self.env().emit_event(RedeemPromoCodeEvent {
+                code_hash,
+                order_id,
+                customer_id,
+                discount,
+            })
+        }
+    }
+
+    impl psp22::Internal for FoodOrdering {
+        fn _emit_transfer_event(
+            &self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            amount: Balance,
+        ) {
+            self.env().emit_event(Transfer {
+                from,
+                to,
+                value: amount,
+            })
+        }
+
+        fn _emit_approval_event(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+            amount: Balance,
+        ) {
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: amount,
+            })
+        }
+    }
+
+    // Loyalty points are the contract's PSP22 token.
+    impl Loyalty for FoodOrdering {
+        fn _mint_points(
+            &mut self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            assert!(psp22::Internal::_mint_to(self, account, amount).is_ok(), "Minting loyalty points failed!");
+        }
+
+        fn _burn_points(
+            &mut self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            assert!(psp22::Internal::_burn_from(self, account, amount).is_ok(), "Burning loyalty points failed!");
+        }
+
+        fn _points_of(
+            &self,
+            account: AccountId,
+        ) -> u128 {
+            psp22::Internal::_balance_of(self, &account)
+        }
+    }
+
+    impl ManagerServiceEvents for FoodOrdering {
+        fn emit_change_manager_event(
+            &self,
+            previous_manager: AccountId,
+            new_manager: AccountId,
+        ) {
+            self.env().emit_event(ChangeManagerEvent {
+                previous_manager,
+                new_manager,
+            })
+        }
+    }
+
+    impl OrderingManagerServiceEvents for FoodOrdering {
+        fn emit_add_restaurant_event(
+            &self,
+            restaurant_id: RestaurantId,
+            restaurant_name: String,
+            restaurant_address: String,
+            phone_number: String,
+        ) {
+            self.env().emit_event(AddRestaurantEvent {
+                restaurant_id,
+                restaurant_name,
+                restaurant_address,
+                phone_number,
+            })
+        }
+
+        fn emit_set_delivery_fee_event(
+            &self,
+            delivery_fee: u128,
+        ) {
+            self.env().emit_event(SetDeliveryFeeEvent {
+                delivery_fee,
+            })
+        }
+
+        fn emit_set_commission_event(
+            &self,
+            restaurant_id: Option<RestaurantId>,
+            food_commission: Option<u16>,
+            delivery_commission: u16,
+        ) {
+            self.env().emit_event(SetCommissionEvent {
+                restaurant_id,
+                food_commission,
+                delivery_commission,
+            })
+        }
+    }
+
+    impl WorkflowLinkEvents for FoodOrdering {
+        fn emit_set_workflow_contracts_event(
+            &self,
+            order_contract: AccountId,
+            delivery_contract: AccountId,
+            payment_contract: AccountId,
+        ) {
+            self.env().emit_event(SetWorkflowContractsEvent {
+                order_contract,
+                delivery_contract,
+                payment_contract,
+            })
+        }
+    }
+
+    impl OracleServiceEvents for FoodOrdering {
+        fn emit_post_exchange_rate_event(
+            &self,
+            currency: CurrencyCode,
+            rate: u128,
+            timestamp: Timestamp,
+        ) {
+            self.env().emit_event(PostExchangeRateEvent {
+                currency,
+                rate,
+                timestamp,
+            })
+        }
+
+        fn emit_set_oracle_event(
+            &self,
+            oracle: AccountId,
+        ) {
+            self.env().emit_event(SetOracleEvent {
+                oracle,
+            })
+        }
+
+        fn emit_set_max_rate_age_event(
+            &self,
+            max_rate_age: u64,
+        ) {
+            self.env().emit_event(SetMaxRateAgeEvent {
+                max_rate_age,
+            })
+        }
+    }
+
+    impl RestaurantServiceEvents for FoodOrdering {
+        fn emit_add_food_event(
+            &self,
+            food_id: FoodId,
+            food_name: String,
+            restaurant_id: RestaurantId,
+            description: String,
+            price: u128,
+            eta: u64,
+        ) {
+            self.env().emit_event(AddFoodEvent {
+                food_id,
+                food_name,
+                restaurant_id,
+                description,
+                price,
+                eta,
+            })
+        }
+    
+        fn emit_update_food_event(
+            &self,
+            food_id: FoodId,
+            food_name: String,
+            description: String,
+            price: u128,
+            eta: u64,
+        ) {
+            self.env().emit_event(UpdateFoodEvent {
+                food_id,
+                food_name,
+                description,
+                price,
+                eta,
+            })
+        }
+    
+        fn emit_update_food_availability_event(
+            &self,
+            food_id: FoodId,
+            available: bool,
+            stock: Option<u32>,
+        ) {
+            self.env().emit_event(UpdateFoodAvailabilityEvent {
+                food_id,
+                available,
+                stock,
+            })
+        }
+
+        fn emit_update_food_currency_event(
+            &self,
+            food_id: FoodId,
+            currency: Option<CurrencyCode>,
+        ) {
+            self.env().emit_event(UpdateFoodCurrencyEvent {
+                food_id,
+                currency,
+            })
+        }
+
+        fn emit_remove_food_event(
+            &self,
+            food_id: FoodId,
+            restaurant_id: RestaurantId,
+        ) {
+            self.env().emit_event(RemoveFoodEvent {
+                food_id,
+                restaurant_id,
+            })
+        }
+    
+        fn emit_update_opening_hours_event(
+            &self,
+            restaurant_id: RestaurantId,
+            opening_hours: Vec<OpeningSlot>,
+        ) {
+            self.env().emit_event(UpdateOpeningHoursEvent {
+                restaurant_id,
+                opening_hours,
+            })
+        }
+
+        fn emit_update_closures_event(
+            &self,
+            restaurant_id: RestaurantId,
+            closures: Vec<Closure>,
+        ) {
+            self.env().emit_event(UpdateClosuresEvent {
+                restaurant_id,
+                closures,
+            })
+        }
+    
+        fn emit_confirm_order_event(
+            &self,
+            order_id: OrderId,
+            eta: u64,
+        ) {
+            self.env().emit_event(ConfirmOrderEvent {
+                order_id,
+                eta
+            })
+        }
+
+        fn emit_reject_order_event(
+            &self,
+            order_id: OrderId,
+            restaurant_id: RestaurantId,
+        ) {
+            self.env().emit_event(RejectOrderEvent {
+                order_id,
+                restaurant_id,
+            })
+        }
+    
+        fn emit_deliver_order_event(
+            &self,
+            order_id: OrderId,
+            delivery_id: DeliveryId,
+            restaurant_id: RestaurantId,
+            customer_id: CustomerId,
+            delivery_address: PersonalData,
+        ) {
+            self.env().emit_event(DeliverOrderEvent {
+                order_id,
+                delivery_id,
+                restaurant_id,
+                customer_id,
+                delivery_address,
+            })
+        }
+
+        fn emit_update_restaurant_encryption_key_event(
+            &self,
+            restaurant_id: RestaurantId,
+            encryption_key: Option<PublicKey>,
+        ) {
+            self.env().emit_event(UpdateRestaurantEncryptionKeyEvent {
+                restaurant_id,
+                encryption_key,
+            })
+        }
+    }
+
+    impl ScheduleServiceEvents for FoodOrdering {
+        fn emit_create_schedule_event(
+            &self,
+            schedule_id: ScheduleId,
+            customer_id: CustomerId,
+            food_id: FoodId,
+            next_time: Timestamp,
+            prepaid: u128,
+        ) {
+            self.env().emit_event(CreateScheduleEvent {
+                schedule_id,
+                customer_id,
+                food_id,
+                next_time,
+                prepaid,
+            })
+        }
+
+        fn emit_trigger_schedule_event(
+            &self,
+            schedule_id: ScheduleId,
+            order_id: Option<OrderId>,
+            next_time: Timestamp,
+        ) {
+            self.env().emit_event(TriggerScheduleEvent {
+                schedule_id,
+                order_id,
+                next_time,
+            })
+        }
+
+        fn emit_close_schedule_event(
+            &self,
+            schedule_id: ScheduleId,
+            refund: u128,
+        ) {
+            self.env().emit_event(CloseScheduleEvent {
+                schedule_id,
+                refund,
+            })
+        }
+    }
+
+    impl SettlementEvents for FoodOrdering {
+        fn emit_settle_order_event(
+            &self,
+            order_id: OrderId,
+            refund: u128,
+            restaurant_amount: u128,
+            deliver_amount: u128,
+            held_amount: u128,
+            platform_amount: u128,
+        ) {
+            self.env().emit_event(SettleOrderEvent {
+                order_id,
+                refund,
+                restaurant_amount,
+                deliver_amount,
+                held_amount,
+                platform_amount,
+            })
+        }
+
+        fn emit_credit_balance_event(
+            &self,
+            account: AccountId,
+            order_id: OrderId,
+            amount: u128,
+        ) {
+            self.env().emit_event(CreditBalanceEvent {
+                account,
+                order_id,
+                amount,
+            })
+        }
+    }
+
+    // Deliveries are kept by the delivery contract.
+    impl Dispatch for FoodOrdering {
+        fn _dispatch_delivery(
+            &mut self,
+            order_id: OrderId,
+            restaurant_id: RestaurantId,
+            customer_id: CustomerId,
+            delivery_address: PersonalData,
+        ) -> DeliveryId {
+            let delivery_contract = self.food_order_data.delivery_contract;
+            DeliveryWorkflowRef::dispatch_delivery(&delivery_contract, order_id, restaurant_id, customer_id, delivery_address)
+        }
+
+        fn _readdress_delivery(
+            &mut self,
+            order_id: OrderId,
+            delivery_address: PersonalData,
+        ) -> DeliveryId {
+            let delivery_contract = self.food_order_data.delivery_contract;
+            DeliveryWorkflowRef::update_delivery_address(&delivery_contract, order_id, delivery_address)
+        }
+
+        fn _cancel_delivery(
+            &mut self,
+            order_id: OrderId,
+        ) {
+            let delivery_contract = self.food_order_data.delivery_contract;
+            DeliveryWorkflowRef::cancel_delivery(&delivery_contract, order_id)
+        }
+    }
+
+    // Courier bonds are held by the delivery contract.
+    impl Bonding for FoodOrdering {
+        fn _slash_deliver(
+            &mut self,
+            deliver_id: DeliverId,
+            order_id: OrderId,
+            payers: Vec<(AccountId, u128)>,
+        ) -> u128 {
+            let delivery_contract = self.food_order_data.delivery_contract;
+            DeliveryWorkflowRef::slash_deliver(&delivery_contract, deliver_id, order_id, payers)
+        }
+
+        fn _bond_of(
+            &self,
+            deliver_id: DeliverId,
+        ) -> u128 {
+            GetDeliveryRef::get_deliver_bond(&self.food_order_data.delivery_contract, deliver_id).amount
+        }
+    }
+
+    // Balances and the treasury are kept by the payment contract, which
+    // receives the funds with each credit.
+    impl Ledger for FoodOrdering {
+        fn _add_balance(
+            &mut self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            if amount > 0 {
+                let payment_contract = self.food_order_data.payment_contract;
+                PaymentLedgerRef::credit_builder(&payment_contract, account)
+                    .transferred_value(amount)
+                    .invoke();
+            }
+        }
+
+        fn _add_treasury(
+            &mut self,
+            amount: u128,
+        ) {
+            if amount > 0 {
+                let payment_contract = self.food_order_data.payment_contract;
+                PaymentLedgerRef::credit_treasury_builder(&payment_contract)
+                    .transferred_value(amount)
+                    .invoke();
+            }
+        }
+
+        fn _take_treasury(
+            &mut self,
+            amount: u128,
+        ) {
+            let payment_contract = self.food_order_data.payment_contract;
+            PaymentLedgerRef::draw_treasury(&payment_contract, amount);
+        }
+
+        fn _treasury(
+            &self,
+        ) -> u128 {
+            GetPaymentRef::get_treasury_balance(&self.food_order_data.payment_contract)
+        }
+    }
+
+    // The payment contract keeps the charges and holds the restaurant share
+    // of an order until its processor captures the charge.
+    impl Holding for FoodOrdering {
+        fn _awaits_capture(
+            &self,
+            order_id: OrderId,
+        ) -> bool {
+            PaymentLedgerRef::awaits_capture(&self.food_order_data.payment_contract, order_id)
+        }
+
+        fn _hold_for_capture(
+            &mut self,
+            order_id: OrderId,
+            restaurant_account: AccountId,
+            amount: u128,
+        ) {
+            let payment_contract = self.food_order_data.payment_contract;
+            PaymentLedgerRef::hold_for_capture_builder(&payment_contract, order_id, restaurant_account)
+                .transferred_value(amount)
+                .invoke();
+        }
+
+        fn _cancel_charge(
+            &mut self,
+            order_id: OrderId,
+        ) {
+            let payment_contract = self.food_order_data.payment_contract;
+            PaymentLedgerRef::cancel_charge(&payment_contract, order_id);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+        use logics::impls::types::{
+            Deliver,
+            Order,
+            OrderStatus,
+        };
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller::<DefaultEnvironment>(account);
+        }
+
+        // Alice manages the contract, django's account stands in for the
+        // delivery contract and eve's for the payment contract.
+        fn setup() -> FoodOrdering {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut ordering = FoodOrdering::new();
+            ordering.set_workflow_contracts(accounts.alice, accounts.django, accounts.eve);
+            ordering
+        }
+
+        #[ink::test]
+        fn manager_links_workflow_contracts() {
+            let accounts = accounts();
+            let ordering = setup();
+            assert_eq!(ordering.get_workflow_contracts(), (accounts.alice, accounts.django, accounts.eve));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only manager can set workflow contracts!")]
+        fn only_manager_can_set_workflow_contracts() {
+            let accounts = accounts();
+            let mut ordering = setup();
+            set_caller(accounts.bob);
+            ordering.set_workflow_contracts(accounts.bob, accounts.bob, accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only delivery contract can pick up order!")]
+        fn only_delivery_contract_can_pick_up_order() {
+            let accounts = accounts();
+            let mut ordering = setup();
+            set_caller(accounts.bob);
+            ordering.pick_up_order(1, 1, Default::default());
+        }
+
+        #[ink::test]
+        fn deliver_of_picked_up_order_responds_to_dispute() {
+            let accounts = accounts();
+            #[cfg(feature = "chain-extension")]
+            logics::impls::Bookkeeping::mock::register();
+            let mut ordering = setup();
+            ordering.add_restaurant(accounts.bob, String::new(), String::new(), String::new());
+            set_caller(accounts.charlie);
+            ordering.add_customer(String::new(), PersonalData::Plain(String::new()), PersonalData::Plain(String::new()));
+            let order = Order {
+                food_id: 1,
+                restaurant_id: 1,
+                customer_id: 1,
+                status: OrderStatus::WaitingDeliver,
+                ..Default::default()
+            };
+            ordering.ordering_data.order_data.insert(&1, &order);
+            set_caller(accounts.django);
+            let deliver = Deliver {
+                deliver_account: accounts.frank,
+                ..Default::default()
+            };
+            ordering.pick_up_order(1, 1, deliver);
+            set_caller(accounts.charlie);
+            ordering.open_dispute(1, Hash::from([1; 32]));
+            set_caller(accounts.frank);
+            ordering.respond_dispute(1, Hash::from([2; 32]));
+            assert_eq!(ordering.get_dispute_from_id(1).deliver_response, Some(Hash::from([2; 32])));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only delivery contract can accept delivery!")]
+        fn only_delivery_contract_can_accept_delivery() {
+            let accounts = accounts();
+            let mut ordering = setup();
+            set_caller(accounts.bob);
+            ordering.accept_delivery(1);
+        }
+
+        #[ink::test]
+        fn payment_contract_charges_accepted_order() {
+            let accounts = accounts();
+            #[cfg(feature = "chain-extension")]
+            logics::impls::Bookkeeping::mock::register();
+            let mut ordering = setup();
+            let order = Order {
+                status: OrderStatus::DeliveryAcceptted,
+                ..Default::default()
+            };
+            ordering.ordering_data.order_data.insert(&1, &order);
+            set_caller(accounts.eve);
+            ordering.charge_order(1);
+            assert_eq!(ordering.get_order_from_id(1).status, OrderStatus::OrderCharged);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only payment contract can charge order!")]
+        fn only_payment_contract_can_charge_order() {
+            let accounts = accounts();
+            let mut ordering = setup();
+            set_caller(accounts.django);
+            ordering.charge_order(1);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use delivery::FoodDeliveryRef;
+        use ink::env::DefaultEnvironment;
+        use ink_e2e::{
+            build_message,
+            AccountKeyring,
+            PolkadotConfig,
+        };
+        use logics::{
+            impls::types::{hash_secret, DeliveryStatus, OrderStatus},
+            traits::{
+                BondService::BondService,
+                DeliverService::DeliverService,
+                GetDelivery::GetDelivery,
+                GetPayment::GetPayment,
+                PaymentManagerService::PaymentManagerService,
+                PaymentService::PaymentService,
+            },
+        };
+        use payment::FoodPaymentRef;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+        type Client = ink_e2e::Client<PolkadotConfig, DefaultEnvironment>;
+
+        const PRICE: u128 = 1_000_000_000;
+        const DELIVERY_FEE: u128 = 100_000_000;
+        const FOOD_ETA: u64 = 600_000;
+        const BOND: u128 = 1_000_000_000;
+        // 20% of the bond.
+        const SLASH: u128 = 200_000_000;
+        const HANDOFF_CODE: &str = "4f2a9c1be07d5a38f6c2e91d0b7a4f63";
+        // 0.5% of the price.
+        const PROCESSOR_FEE: u128 = 5_000_000;
+
+        // Sends a message to a deployed contract and returns its result.
+        macro_rules! call {
+            ($client:expr, $signer:expr, $value:expr, $contract:ty, $account:expr, $message:expr) => {
+                $client
+                    .call(&$signer, build_message::<$contract>($account).call($message), $value, None)
+                    .await
+                    .expect("call failed")
+                    .return_value()
+            };
+        }
+
+        // Reads from a deployed contract without submitting a transaction.
+        macro_rules! query {
+            ($client:expr, $contract:ty, $account:expr, $message:expr) => {
+                $client
+                    .call_dry_run(&ink_e2e::alice(), &build_message::<$contract>($account).call($message), 0, None)
+                    .await
+                    .return_value()
+            };
+        }
+
+        struct Deployment {
+            ordering: AccountId,
+            delivery: AccountId,
+            payment: AccountId,
+        }
+
+        fn account(keyring: AccountKeyring) -> AccountId {
+            ink_e2e::account_id(keyring)
+        }
+
+        fn plain(value: &str) -> PersonalData {
+            PersonalData::Plain(String::from(value))
+        }
+
+        async fn balance(client: &Client, account: AccountId) -> u128 {
+            client.balance(account).await.expect("balance failed")
+        }
+
+        /// Deploys and links the three contracts. Alice manages them, Bob
+        /// runs restaurant 1 serving food 1, Dave is deliver 1 and Charlie is
+        /// customer 1.
+        async fn deploy(client: &mut Client) -> Deployment {
+            let ordering = client
+                .instantiate("ordering", &ink_e2e::alice(), FoodOrderingRef::new(), 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+            let delivery = client
+                .instantiate("delivery", &ink_e2e::alice(), FoodDeliveryRef::new(), 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+            let payment = client
+                .instantiate("payment", &ink_e2e::alice(), FoodPaymentRef::new(), 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+            call!(client, ink_e2e::alice(), 0, FoodOrderingRef, ordering, |contract| contract.set_workflow_contracts(ordering, delivery, payment));
+            call!(client, ink_e2e::alice(), 0, FoodDeliveryRef, delivery, |contract| contract.set_workflow_contracts(ordering, delivery, payment));
+            call!(client, ink_e2e::alice(), 0, FoodPaymentRef, payment, |contract| contract.set_workflow_contracts(ordering, delivery, payment));
+
+            call!(client, ink_e2e::alice(), 0, FoodOrderingRef, ordering, |contract| contract.add_restaurant(account(AccountKeyring::Bob), String::from("Bob's"), String::from("1 Main St"), String::from("555-0100")));
+            call!(client, ink_e2e::alice(), 0, FoodOrderingRef, ordering, |contract| contract.set_delivery_fee(DELIVERY_FEE));
+            call!(client, ink_e2e::alice(), 0, FoodOrderingRef, ordering, |contract| contract.set_commission(1_000, 2_000));
+            call!(client, ink_e2e::alice(), 0, FoodDeliveryRef, delivery, |contract| contract.add_deliver(account(AccountKeyring::Dave), String::from("Dave"), String::from("2 Main St"), String::from("555-0101")));
+            call!(client, ink_e2e::bob(), 0, FoodOrderingRef, ordering, |contract| contract.add_food(String::from("Pizza"), String::from("Margherita"), PRICE, FOOD_ETA));
+            call!(client, ink_e2e::charlie(), 0, FoodOrderingRef, ordering, |contract| contract.add_customer(String::from("Charlie"), plain("3 Main St"), plain("555-0102")));
+            Deployment {
+                ordering,
+                delivery,
+                payment,
+            }
+        }
+
+        /// Requires a bond of `BOND`, slashes 20% of it and times deliveries
+        /// out after a millisecond, so the next block is already late. Dave
+        /// posts the bond with the delivery contract.
+        async fn bond_deliver(client: &mut Client, deployment: &Deployment) {
+            call!(client, ink_e2e::alice(), 0, FoodDeliveryRef, deployment.delivery, |contract| contract.set_bond_policy(BOND, 0, 2_000, 1));
+            call!(client, ink_e2e::dave(), BOND, FoodDeliveryRef, deployment.delivery, |contract| contract.post_bond());
+        }
+
+        /// Charlie orders food 1 with the handoff code, Bob confirms it and
+        /// has it dispatched, and Dave picks it up.
+        async fn pick_up(client: &mut Client, deployment: &Deployment) -> DeliveryId {
+            let handoff_hash = hash_secret(&String::from(HANDOFF_CODE));
+            call!(client, ink_e2e::charlie(), PRICE + DELIVERY_FEE, FoodOrderingRef, deployment.ordering, |contract| contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 0, None, Some(handoff_hash)));
+            call!(client, ink_e2e::bob(), 0, FoodOrderingRef, deployment.ordering, |contract| contract.confirm_order(1));
+            call!(client, ink_e2e::bob(), 0, FoodOrderingRef, deployment.ordering, |contract| contract.deliver_order(1));
+            let delivery_id = query!(client, FoodDeliveryRef, deployment.delivery, |contract| contract.get_delivery_from_order(1));
+            call!(client, ink_e2e::dave(), 0, FoodDeliveryRef, deployment.delivery, |contract| contract.confirm_delivery(delivery_id));
+            delivery_id
+        }
+
+        #[ink_e2e::test(additional_contracts = "../delivery/Cargo.toml ../payment/Cargo.toml")]
+        async fn handoff_credits_payment_contract(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let deployment = deploy(&mut client).await;
+            let delivery_id = pick_up(&mut client, &deployment).await;
+            assert_eq!(query!(client, FoodOrderingRef, deployment.ordering, |contract| contract.get_order_from_id(1)).status, OrderStatus::OrderDelivered);
+
+            let before = balance(&client, deployment.payment).await;
+            call!(client, ink_e2e::dave(), 0, FoodDeliveryRef, deployment.delivery, |contract| contract.complete_handoff(delivery_id, String::from(HANDOFF_CODE)));
+            assert_eq!(query!(client, FoodDeliveryRef, deployment.delivery, |contract| contract.get_delivery_from_id(delivery_id)).status, DeliveryStatus::HandedOff);
+            assert_eq!(query!(client, FoodOrderingRef, deployment.ordering, |contract| contract.get_order_from_id(1)).status, OrderStatus::DeliveryAcceptted);
+            // The ordering contract sends the whole payment along with the
+            // credits.
+            assert_eq!(balance(&client, deployment.payment).await, before + PRICE + DELIVERY_FEE);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Bob))), 900_000_000);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Dave))), 80_000_000);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_treasury_balance()), 120_000_000);
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "../delivery/Cargo.toml ../payment/Cargo.toml")]
+        async fn captured_charge_pays_held_share(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let deployment = deploy(&mut client).await;
+            call!(client, ink_e2e::alice(), 0, FoodPaymentRef, deployment.payment, |contract| contract.add_payment_processor(account(AccountKeyring::Eve), String::from("Eve Pay")));
+            call!(client, ink_e2e::alice(), 0, FoodPaymentRef, deployment.payment, |contract| contract.set_processor_fee_rate(1, 500));
+            let handoff_hash = hash_secret(&String::from(HANDOFF_CODE));
+            call!(client, ink_e2e::charlie(), PRICE + DELIVERY_FEE, FoodOrderingRef, deployment.ordering, |contract| contract.submit_order(1, 1, plain("3 Main St"), plain("555-0102"), None, 0, None, Some(handoff_hash)));
+            call!(client, ink_e2e::charlie(), 0, FoodPaymentRef, deployment.payment, |contract| contract.select_payment_processor(1, 1));
+            call!(client, ink_e2e::eve(), 0, FoodPaymentRef, deployment.payment, |contract| contract.authorize_charge(1, String::from("ch_1"), PROCESSOR_FEE));
+            call!(client, ink_e2e::bob(), 0, FoodOrderingRef, deployment.ordering, |contract| contract.confirm_order(1));
+            call!(client, ink_e2e::bob(), 0, FoodOrderingRef, deployment.ordering, |contract| contract.deliver_order(1));
+            let delivery_id = query!(client, FoodDeliveryRef, deployment.delivery, |contract| contract.get_delivery_from_order(1));
+            call!(client, ink_e2e::dave(), 0, FoodDeliveryRef, deployment.delivery, |contract| contract.confirm_delivery(delivery_id));
+            call!(client, ink_e2e::dave(), 0, FoodDeliveryRef, deployment.delivery, |contract| contract.complete_handoff(delivery_id, String::from(HANDOFF_CODE)));
+            // The restaurant share waits on the payment contract for the
+            // processor to capture the charge.
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Bob))), 0);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Dave))), 80_000_000);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_treasury_balance()), 120_000_000);
+
+            call!(client, ink_e2e::eve(), 0, FoodPaymentRef, deployment.payment, |contract| contract.capture_charge(1));
+            assert_eq!(query!(client, FoodOrderingRef, deployment.ordering, |contract| contract.get_order_from_id(1)).status, OrderStatus::OrderCharged);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Bob))), 900_000_000 - PROCESSOR_FEE);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Eve))), PROCESSOR_FEE);
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "../delivery/Cargo.toml ../payment/Cargo.toml")]
+        async fn timed_out_delivery_refunds_and_slashes_bond(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let deployment = deploy(&mut client).await;
+            bond_deliver(&mut client, &deployment).await;
+            let delivery_id = pick_up(&mut client, &deployment).await;
+
+            let delivery_before = balance(&client, deployment.delivery).await;
+            let payment_before = balance(&client, deployment.payment).await;
+            call!(client, ink_e2e::eve(), 0, FoodDeliveryRef, deployment.delivery, |contract| contract.time_out_delivery(delivery_id));
+            assert_eq!(query!(client, FoodDeliveryRef, deployment.delivery, |contract| contract.get_delivery_from_id(delivery_id)).status, DeliveryStatus::TimedOut);
+            assert_eq!(query!(client, FoodOrderingRef, deployment.ordering, |contract| contract.get_order_from_id(1)).status, OrderStatus::OrderRefunded);
+            // The slashed share of the bond leaves the delivery contract and
+            // is credited to Charlie next to the refund.
+            assert_eq!(balance(&client, deployment.delivery).await, delivery_before - SLASH);
+            assert_eq!(balance(&client, deployment.payment).await, payment_before + PRICE + DELIVERY_FEE + SLASH);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Charlie))), PRICE + DELIVERY_FEE + SLASH);
+            assert_eq!(query!(client, FoodDeliveryRef, deployment.delivery, |contract| contract.get_deliver_bond(1)).amount, BOND - SLASH);
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "../delivery/Cargo.toml ../payment/Cargo.toml")]
+        async fn dispute_against_deliver_slashes_bond(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let deployment = deploy(&mut client).await;
+            bond_deliver(&mut client, &deployment).await;
+            pick_up(&mut client, &deployment).await;
+            call!(client, ink_e2e::charlie(), 0, FoodOrderingRef, deployment.ordering, |contract| contract.open_dispute(1, Hash::from([1; 32])));
+
+            let delivery_before = balance(&client, deployment.delivery).await;
+            let payment_before = balance(&client, deployment.payment).await;
+            call!(client, ink_e2e::alice(), 0, FoodOrderingRef, deployment.ordering, |contract| contract.resolve_dispute(1, DisputeResolution::FullRefund, true));
+            assert_eq!(query!(client, FoodOrderingRef, deployment.ordering, |contract| contract.get_order_from_id(1)).status, OrderStatus::OrderRefunded);
+            assert_eq!(balance(&client, deployment.delivery).await, delivery_before - SLASH);
+            assert_eq!(balance(&client, deployment.payment).await, payment_before + PRICE + DELIVERY_FEE + SLASH);
+            assert_eq!(query!(client, FoodPaymentRef, deployment.payment, |contract| contract.get_balance(account(AccountKeyring::Charlie))), PRICE + DELIVERY_FEE + SLASH);
+            assert_eq!(query!(client, FoodDeliveryRef, deployment.delivery, |contract| contract.get_deliver_bond(1)).amount, BOND - SLASH);
+            Ok(())
+        }
+    }
+}
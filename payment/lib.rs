@@ -0,0 +1,512 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(min_specialization)]
+
+//! The payment business of a split deployment: the withdrawable balances,
+//! the platform treasury and the payment processors charging the orders.
+//! The ordering and delivery contracts credit them along with the funds.
+
+pub use self::payment::{FoodPayment, FoodPaymentRef};
+
+#[openbrush::contract]
+pub mod payment {
+    use ink::codegen::EmitEvent;
+    use ink::codegen::Env;
+    use ink::prelude::string::String;
+    use logics::{
+        impls::{
+            BalanceService::BalanceServiceEvents,
+            Charging::Charging,
+            ManagerService::ManagerServiceEvents,
+            PaymentManagerService::PaymentManagerServiceEvents,
+            PaymentService::PaymentServiceEvents,
+            WorkflowLink::WorkflowLinkEvents,
+            types::{Data, PaymentData, EVENT_SCHEMA_VERSION, Order, OrderId, PaymentProcessorId},
+        },
+        traits::{
+            BalanceService::BalanceService,
+            Get::GetRef,
+            GetPayment::GetPayment,
+            ManagerService::ManagerService,
+            OrderWorkflow::OrderWorkflowRef,
+            PaymentLedger::PaymentLedger,
+            PaymentManagerService::PaymentManagerService,
+            PaymentService::PaymentService,
+            WorkflowLink::WorkflowLink,
+        },
+    };
+    use openbrush::traits::Storage;
+
+    #[ink(storage)]
+    #[derive(Storage, Default)]
+    pub struct FoodPayment {
+        #[storage_field]
+        food_order_data: Data,
+        #[storage_field]
+        payment_data: PaymentData,
+    }
+
+    impl BalanceService for FoodPayment {}
+
+    impl GetPayment for FoodPayment {}
+
+    impl ManagerService for FoodPayment {}
+
+    impl PaymentLedger for FoodPayment {}
+
+    impl PaymentManagerService for FoodPayment {}
+
+    impl PaymentService for FoodPayment {}
+
+    impl WorkflowLink for FoodPayment {}
+
+    impl FoodPayment {
+        #[ink(constructor, payable)]
+        pub fn new() -> Self {
+            let mut instance = Self::default();
+            instance.food_order_data.manager = Self::env().caller();
+            instance.food_order_data.upgrader = Self::env().caller();
+            Self::env().emit_event(EventSchemaEvent {
+                version: EVENT_SCHEMA_VERSION,
+            });
+            instance
+        }
+
+        /// Replaces the contract code while keeping its storage.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) {
+            assert!(self.env().caller() == self.food_order_data.upgrader, "Only upgrader can set code!");
+            assert!(self.env().set_code_hash(&code_hash).is_ok(), "Code hash does not exist!");
+            self.env().emit_event(SetCodeEvent {
+                code_hash,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_upgrader(&self) -> AccountId {
+            self.food_order_data.upgrader
+        }
+    }
+
+    #[ink(event)]
+    pub struct WithdrawTreasuryEvent {
+        #[ink(topic)]
+        manager: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct WithdrawEvent {
+        #[ink(topic)]
+        account: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct ChangeManagerEvent {
+        #[ink(topic)]
+        previous_manager: AccountId,
+        #[ink(topic)]
+        new_manager: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SetWorkflowContractsEvent {
+        #[ink(topic)]
+        order_contract: AccountId,
+        #[ink(topic)]
+        delivery_contract: AccountId,
+        #[ink(topic)]
+        payment_contract: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AddPaymentProcessorEvent {
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        #[ink(topic)]
+        processor_account: AccountId,
+        processor_name: String,
+    }
+
+    #[ink(event)]
+    pub struct SetProcessorFeeRateEvent {
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        fee_rate: u16,
+    }
+
+    #[ink(event)]
+    pub struct SetCaptureTimeoutEvent {
+        capture_timeout: u64,
+    }
+
+    #[ink(event)]
+    pub struct AuthorizeChargeEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        charge_reference: String,
+        amount: u128,
+        fee: u128,
+    }
+
+    #[ink(event)]
+    pub struct CaptureChargeEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+        processor_amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SelectPaymentProcessorEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+    }
+
+    #[ink(event)]
+    pub struct VoidChargeEvent {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        processor_id: PaymentProcessorId,
+        restaurant_amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SetCodeEvent {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct EventSchemaEvent {
+        version: u16,
+    }
+
+    impl BalanceServiceEvents for FoodPayment {
+        fn emit_withdraw_event(
+            &self,
+            account: AccountId,
+            amount: u128,
+        ) {
+            self.env().emit_event(WithdrawEvent {
+                account,
+                amount,
+            })
+        }
+    }
+
+    impl ManagerServiceEvents for FoodPayment {
+        fn emit_change_manager_event(
+            &self,
+            previous_manager: AccountId,
+            new_manager: AccountId,
+        ) {
+            self.env().emit_event(ChangeManagerEvent {
+                previous_manager,
+                new_manager,
+            })
+        }
+    }
+
+    impl PaymentManagerServiceEvents for FoodPayment {
+        fn emit_add_payment_processor_event(
+            &self,
+            processor_id: PaymentProcessorId,
+            processor_account: AccountId,
+            processor_name: String,
+        ) {
+            self.env().emit_event(AddPaymentProcessorEvent {
+                processor_id,
+                processor_account,
+                processor_name,
+            })
+        }
+
+        fn emit_set_processor_fee_rate_event(
+            &self,
+            processor_id: PaymentProcessorId,
+            fee_rate: u16,
+        ) {
+            self.env().emit_event(SetProcessorFeeRateEvent {
+                processor_id,
+                fee_rate,
+            })
+        }
+
+        fn emit_set_capture_timeout_event(
+            &self,
+            capture_timeout: u64,
+        ) {
+            self.env().emit_event(SetCaptureTimeoutEvent {
+                capture_timeout,
+            })
+        }
+
+        fn emit_withdraw_treasury_event(
+            &self,
+            manager: AccountId,
+            amount: u128,
+        ) {
+            self.env().emit_event(WithdrawTreasuryEvent {
+                manager,
+                amount,
+            })
+        }
+    }
+
+    impl PaymentServiceEvents for FoodPayment {
+        fn emit_authorize_charge_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+            charge_reference: String,
+            amount: u128,
+            fee: u128,
+        ) {
+            self.env().emit_event(AuthorizeChargeEvent {
+                order_id,
+                processor_id,
+                charge_reference,
+                amount,
+                fee,
+            })
+        }
+
+        fn emit_capture_charge_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+            restaurant_amount: u128,
+            processor_amount: u128,
+        ) {
+            self.env().emit_event(CaptureChargeEvent {
+                order_id,
+                processor_id,
+                restaurant_amount,
+                processor_amount,
+            })
+        }
+
+        fn emit_select_payment_processor_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+        ) {
+            self.env().emit_event(SelectPaymentProcessorEvent {
+                order_id,
+                processor_id,
+            })
+        }
+
+        fn emit_void_charge_event(
+            &self,
+            order_id: OrderId,
+            processor_id: PaymentProcessorId,
+            restaurant_amount: u128,
+        ) {
+            self.env().emit_event(VoidChargeEvent {
+                order_id,
+                processor_id,
+                restaurant_amount,
+            })
+        }
+    }
+
+    impl WorkflowLinkEvents for FoodPayment {
+        fn emit_set_workflow_contracts_event(
+            &self,
+            order_contract: AccountId,
+            delivery_contract: AccountId,
+            payment_contract: AccountId,
+        ) {
+            self.env().emit_event(SetWorkflowContractsEvent {
+                order_contract,
+                delivery_contract,
+                payment_contract,
+            })
+        }
+    }
+
+    // Orders are kept by the order contract, which closes an order once its
+    // charge is captured.
+    impl Charging for FoodPayment {
+        fn _charged_order(
+            &self,
+            order_id: OrderId,
+        ) -> Order {
+            GetRef::get_order_from_id(&self.food_order_data.order_contract, order_id)
+        }
+
+        fn _order_accounts(
+            &self,
+            order_id: OrderId,
+        ) -> (AccountId, AccountId) {
+            OrderWorkflowRef::get_order_accounts(&self.food_order_data.order_contract, order_id)
+        }
+
+        fn _charge_order(
+            &mut self,
+            order_id: OrderId,
+        ) {
+            let order_contract = self.food_order_data.order_contract;
+            OrderWorkflowRef::charge_order(&order_contract, order_id);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+        use logics::impls::types::{
+            Charge,
+            ChargeStatus,
+        };
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller::<DefaultEnvironment>(account);
+        }
+
+        fn set_value(value: u128) {
+            test::set_value_transferred::<DefaultEnvironment>(value);
+        }
+
+        // Alice manages the contract, charlie's account stands in for the
+        // ordering contract and django's for the delivery contract.
+        fn setup() -> FoodPayment {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut payment = FoodPayment::new();
+            payment.set_workflow_contracts(accounts.charlie, accounts.django, accounts.alice);
+            payment
+        }
+
+        #[ink::test]
+        fn credited_balance_can_be_withdrawn() {
+            let accounts = accounts();
+            let mut payment = setup();
+            set_caller(accounts.charlie);
+            set_value(90);
+            payment.credit(accounts.bob);
+            set_caller(accounts.django);
+            set_value(10);
+            payment.credit(accounts.bob);
+            set_value(0);
+            assert_eq!(payment.get_balance(accounts.bob), 100);
+            test::set_account_balance::<DefaultEnvironment>(test::callee::<DefaultEnvironment>(), 1_000_000);
+            set_caller(accounts.bob);
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            payment.withdraw(100);
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap(), before + 100);
+            assert_eq!(payment.get_balance(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn ordering_contract_draws_treasury() {
+            let accounts = accounts();
+            let mut payment = setup();
+            set_caller(accounts.charlie);
+            set_value(30);
+            payment.credit_treasury();
+            set_value(0);
+            test::set_account_balance::<DefaultEnvironment>(test::callee::<DefaultEnvironment>(), 1_000_000);
+            payment.draw_treasury(20);
+            assert_eq!(payment.get_treasury_balance(), 10);
+        }
+
+        fn authorize(payment: &mut FoodPayment) {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            payment.add_payment_processor(accounts.eve, String::from("Eve Pay"));
+            let charge = Charge {
+                processor_id: 1,
+                amount: 110,
+                fee: 5,
+                ..Default::default()
+            };
+            payment.payment_data.charge_data.insert(&1, &charge);
+        }
+
+        #[ink::test]
+        fn voided_charge_pays_held_share_to_restaurant() {
+            let accounts = accounts();
+            let mut payment = setup();
+            authorize(&mut payment);
+            set_caller(accounts.charlie);
+            assert!(payment.awaits_capture(1));
+            set_value(90);
+            payment.hold_for_capture(1, accounts.bob);
+            set_value(0);
+            assert_eq!(payment.get_balance(accounts.bob), 0);
+            set_caller(accounts.alice);
+            payment.void_charge(1);
+            assert_eq!(payment.get_charge_from_order(1).status, ChargeStatus::Voided);
+            assert_eq!(payment.get_balance(accounts.bob), 90);
+            assert_eq!(payment.get_balance(accounts.eve), 0);
+        }
+
+        #[ink::test]
+        fn cancelled_charge_no_longer_awaits_capture() {
+            let accounts = accounts();
+            let mut payment = setup();
+            authorize(&mut payment);
+            set_caller(accounts.charlie);
+            payment.cancel_charge(1);
+            assert!(!payment.awaits_capture(1));
+            assert_eq!(payment.get_charge_from_order(1).status, ChargeStatus::Voided);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Order is not accepted yet!")]
+        fn void_charge_without_hold_fails() {
+            let accounts = accounts();
+            let mut payment = setup();
+            authorize(&mut payment);
+            set_caller(accounts.alice);
+            payment.void_charge(1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only order contract can hold for capture!")]
+        fn only_ordering_contract_can_hold_for_capture() {
+            let accounts = accounts();
+            let mut payment = setup();
+            authorize(&mut payment);
+            set_caller(accounts.django);
+            set_value(90);
+            payment.hold_for_capture(1, accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only workflow contracts can credit!")]
+        fn only_workflow_contracts_can_credit() {
+            let accounts = accounts();
+            let mut payment = setup();
+            set_caller(accounts.bob);
+            set_value(10);
+            payment.credit(accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only order contract can draw treasury!")]
+        fn only_ordering_contract_can_draw_treasury() {
+            let accounts = accounts();
+            let mut payment = setup();
+            set_caller(accounts.django);
+            payment.draw_treasury(10);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Client-side helpers for the privacy-preserving `PersonalData` variants of the
+//! food order contract.
+//!
+//! Addresses and phone numbers can be stored on chain either as a commitment
+//! (a salted blake2x256 hash, matching `ink::env::hash::Blake2x256`) or as
+//! sealed boxes addressed to the X25519 keys restaurants and delivers register
+//! with `set_restaurant_encryption_key` / `set_deliver_encryption_key`.
+//! Sealed boxes use the libsodium `crypto_box_seal` construction, so any
+//! libsodium-compatible client can open them as well.
+//!
+//! It also generates the one-time handoff codes a courier reveals to prove
+//! an order was handed to the customer.
+
+use blake2::{
+    digest::consts::U32,
+    Blake2b,
+    Digest,
+};
+use crypto_box::{
+    aead::{rand_core::RngCore, OsRng},
+    PublicKey as BoxPublicKey,
+    SecretKey as BoxSecretKey,
+};
+
+pub type PublicKey = [u8; 32];
+pub type SecretKey = [u8; 32];
+
+/// Mirrors `logics::impls::types::SealedBox` so it can be passed straight into
+/// contract calls.
+#[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Eq, PartialEq)]
+pub struct SealedBox {
+    pub recipient: PublicKey,
+    pub ciphertext: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// The ciphertext is not addressed to the given key or was tampered with.
+    Decryption,
+}
+
+/// Generates a fresh X25519 key pair for a restaurant or deliver.
+pub fn generate_keypair() -> (SecretKey, PublicKey) {
+    let secret = BoxSecretKey::generate(&mut OsRng);
+    let public = secret.public_key();
+    (secret.to_bytes(), *public.as_bytes())
+}
+
+/// Returns the public key belonging to `secret`.
+pub fn public_key(secret: &SecretKey) -> PublicKey {
+    *BoxSecretKey::from(*secret).public_key().as_bytes()
+}
+
+/// Seals `plaintext` for `recipient`. Only the holder of the matching secret
+/// key can open the result.
+pub fn seal(recipient: &PublicKey, plaintext: &[u8]) -> SealedBox {
+    let ciphertext = BoxPublicKey::from(*recipient)
+        .seal(&mut OsRng, plaintext)
+        .expect("sealing into a Vec cannot fail");
+    SealedBox {
+        recipient: *recipient,
+        ciphertext,
+    }
+}
+
+/// Seals `plaintext` once per recipient, e.g. for the restaurant and the
+/// platform, producing the payload of `PersonalData::Sealed`.
+pub fn seal_for_all(recipients: &[PublicKey], plaintext: &[u8]) -> Vec<SealedBox> {
+    recipients
+        .iter()
+        .map(|recipient| seal(recipient, plaintext))
+        .collect()
+}
+
+/// Opens a sealed box with the recipient's secret key.
+pub fn open(secret: &SecretKey, sealed: &SealedBox) -> Result<Vec<u8>, Error> {
+    BoxSecretKey::from(*secret)
+        .unseal(&sealed.ciphertext)
+        .map_err(|_| Error::Decryption)
+}
+
+/// Finds the box addressed to `secret`'s public key among `boxes` and opens it.
+pub fn open_any(secret: &SecretKey, boxes: &[SealedBox]) -> Result<Vec<u8>, Error> {
+    let public = public_key(secret);
+    let sealed = boxes
+        .iter()
+        .find(|sealed| sealed.recipient == public)
+        .ok_or(Error::Decryption)?;
+    open(secret, sealed)
+}
+
+/// Computes the commitment stored in `PersonalData::Commitment`: the
+/// blake2x256 hash of `salt ++ data`. The salt must be kept off chain and
+/// shared with whoever needs to verify the revealed value.
+pub fn commit(data: &[u8], salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(salt);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Checks a revealed value against a commitment.
+pub fn verify_commitment(commitment: &[u8; 32], data: &[u8], salt: &[u8; 32]) -> bool {
+    commit(data, salt) == *commitment
+}
+
+/// Generates a one-time handoff code and the blake2x256 hash to pass to
+/// `submit_order`. The customer shows the code to the courier on drop-off.
+/// The hash is public, so the code carries 128 random bits rather than a
+/// short PIN that could be recovered by trying every value.
+pub fn handoff_code() -> (String, [u8; 32]) {
+    let mut secret = [0u8; 16];
+    OsRng.fill_bytes(&mut secret);
+    let code: String = secret.iter().map(|byte| format!("{byte:02x}")).collect();
+    let hash = handoff_hash(&code);
+    (code, hash)
+}
+
+/// Hashes a handoff code the way the contract checks it.
+pub fn handoff_hash(code: &str) -> [u8; 32] {
+    Blake2b::<U32>::digest(code.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_box_opens_with_recipient_key() {
+        let (secret, public) = generate_keypair();
+        assert_eq!(public_key(&secret), public);
+        let sealed = seal(&public, b"3 Main St");
+        assert_eq!(sealed.recipient, public);
+        assert_eq!(open(&secret, &sealed), Ok(b"3 Main St".to_vec()));
+    }
+
+    #[test]
+    fn sealed_box_does_not_open_with_other_key() {
+        let (_, public) = generate_keypair();
+        let (other_secret, _) = generate_keypair();
+        let sealed = seal(&public, b"3 Main St");
+        assert_eq!(open(&other_secret, &sealed), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn open_any_finds_box_for_key() {
+        let (restaurant_secret, restaurant) = generate_keypair();
+        let (deliver_secret, deliver) = generate_keypair();
+        let boxes = seal_for_all(&[restaurant, deliver], b"3 Main St");
+        assert_eq!(
+            open_any(&restaurant_secret, &boxes),
+            Ok(b"3 Main St".to_vec())
+        );
+        assert_eq!(open_any(&deliver_secret, &boxes), Ok(b"3 Main St".to_vec()));
+    }
+
+    #[test]
+    fn open_any_rejects_wrong_key() {
+        let (_, restaurant) = generate_keypair();
+        let (other_secret, _) = generate_keypair();
+        let boxes = seal_for_all(&[restaurant], b"3 Main St");
+        assert_eq!(open_any(&other_secret, &boxes), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn open_any_rejects_box_relabelled_for_wrong_key() {
+        let (_, restaurant) = generate_keypair();
+        let (other_secret, other) = generate_keypair();
+        let mut sealed = seal(&restaurant, b"3 Main St");
+        sealed.recipient = other;
+        assert_eq!(open_any(&other_secret, &[sealed]), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn commitment_verifies_revealed_value() {
+        let salt = [7u8; 32];
+        let commitment = commit(b"555-0102", &salt);
+        assert!(verify_commitment(&commitment, b"555-0102", &salt));
+        assert!(!verify_commitment(&commitment, b"555-0103", &salt));
+    }
+
+    #[test]
+    fn commitment_rejects_tampered_salt() {
+        let salt = [7u8; 32];
+        let commitment = commit(b"555-0102", &salt);
+        let mut tampered = salt;
+        tampered[0] ^= 1;
+        assert!(!verify_commitment(&commitment, b"555-0102", &tampered));
+    }
+
+    #[test]
+    fn handoff_code_matches_its_hash() {
+        let (code, hash) = handoff_code();
+        assert_eq!(code.len(), 32);
+        assert_eq!(handoff_hash(&code), hash);
+        assert_ne!(handoff_code().0, code);
+    }
+}